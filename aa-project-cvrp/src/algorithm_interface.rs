@@ -3,9 +3,376 @@
  * is defined as a trait.
  */
 
-pub trait CVRPSolver
+use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/* The solvers are Sync, so that they can
+ * be run in parallel from many threads. */
+pub trait CVRPSolver : Sync
 {
 
     fn solve(&self) -> Vec< Vec<usize>>;
 
-}
\ No newline at end of file
+    /* Solve within the limits of the control, reporting
+     * to it every new best solution. The solvers which
+     * are not anytime are not started once the search
+     * is stopped, returning no routes, and otherwise
+     * run to completion and report only their result. */
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        if control.should_stop()
+        {
+            return Vec::new();
+        }
+
+        let result : Vec< Vec<usize>> = self.solve();
+        control.report(&result);
+
+        return result;
+
+    }
+
+    /* Seed used by randomized solvers, which
+     * should be reported with the result so
+     * that the run can be reproduced exactly.
+     * Deterministic solvers return None. */
+    fn get_seed(&self) -> Option<u64>
+    {
+        None
+    }
+
+}
+
+/* Compute the cost of a set of routes given
+ * the full distance matrix of the instance.
+ * Each route starts and ends at the depot,
+ * which is the node 0. */
+pub fn compute_routes_cost(
+    distance_matrix : &[Vec<f64>],
+    routes          : &Vec< Vec<usize>>)
+    -> f64
+{
+
+    let mut result : f64 = 0.0;
+
+    for route in routes
+    {
+
+        let mut previous_node : usize = 0;
+
+        for &current_node in route
+        {
+            result        += distance_matrix[previous_node][current_node];
+            previous_node  = current_node;
+        }
+
+        result += distance_matrix[previous_node][0];
+
+    }
+
+    return result;
+
+}
+
+/* Interface of the algorithms which improve
+ * the routes computed by a CVRPSolver, such as
+ * the local search procedures. Once the control
+ * is stopped the improvement ends, returning
+ * the routes improved so far. */
+pub trait CVRPImprover : Sync
+{
+
+    fn improve(&self, routes : Vec< Vec<usize>>, control : &SolveControl) -> Vec< Vec<usize>>;
+
+}
+
+/* Stopping criterion of the metaheuristics,
+ * given as a number of iterations or as
+ * a time limit. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum StoppingCriterion
+{
+
+    Iterations(usize),
+
+    Time(Duration),
+
+}
+
+impl StoppingCriterion
+{
+
+    /* Check if the search should stop, after the given
+     * number of iterations from the starting instant. */
+    pub fn is_met(&self, iterations : usize, start : Instant) -> bool
+    {
+
+        match *self
+        {
+            StoppingCriterion::Iterations(limit) => iterations >= limit,
+            StoppingCriterion::Time(limit)       => start.elapsed() >= limit,
+        }
+
+    }
+
+    /* Fraction of the search completed, between 0 and 1. */
+    pub fn progress(&self, iterations : usize, start : Instant) -> f64
+    {
+
+        let result : f64 = match *self
+        {
+            StoppingCriterion::Iterations(limit) =>
+                iterations as f64 / limit.max(1) as f64,
+            StoppingCriterion::Time(limit) =>
+                start.elapsed().as_secs_f64() / limit.as_secs_f64().max(f64::MIN_POSITIVE),
+        };
+
+        return result.min(1.0);
+
+    }
+
+}
+
+/* Callback receiving each new best solution,
+ * with its cost and the time elapsed since
+ * the start of the search. */
+pub type NewBestCallback<'c> = dyn Fn(&Vec< Vec<usize>>, f64, Duration) + Sync + 'c;
+
+/* Cost of a solution, used to compare the
+ * solutions reported to the callback. */
+pub type CostFunction<'c> = dyn Fn(&Vec< Vec<usize>>) -> f64 + Sync + 'c;
+
+/* Limits and progress reporting of an anytime search:
+ * the search stops, returning the best solution found,
+ * when the deadline passes or the cancellation flag is
+ * raised, and every new best solution is passed to the
+ * callback as soon as it is found. */
+pub struct SolveControl<'c>
+{
+
+    pub start         : Instant,
+
+    pub deadline      : Option<Instant>,
+
+    pub cancelled     : Option<&'c AtomicBool>,
+
+    /* The solutions reported are compared by the
+     * cost function, and passed to the callback,
+     * only if both are given. */
+    pub cost_function : Option<&'c CostFunction<'c>>,
+
+    pub on_new_best   : Option<&'c NewBestCallback<'c>>,
+
+    /* Cost of the best solution reported. */
+    best_cost         : Mutex<f64>,
+
+}
+
+/* Methods of SolveControl. */
+#[allow(dead_code)]
+impl<'c> SolveControl<'c>
+{
+
+    /* A control starting now, with the given cost
+     * function, deadline, cancellation flag and
+     * callback. */
+    pub fn new(
+        cost_function : Option<&'c CostFunction<'c>>,
+        deadline      : Option<Instant>,
+        cancelled     : Option<&'c AtomicBool>,
+        on_new_best   : Option<&'c NewBestCallback<'c>>)
+        -> SolveControl<'c>
+    {
+
+        SolveControl {
+            start     : Instant::now(),
+            deadline,
+            cancelled,
+            cost_function,
+            on_new_best,
+            best_cost : Mutex::new(f64::INFINITY),
+        }
+
+    }
+
+    /* A control without limits nor
+     * callback, starting now. */
+    pub fn unlimited() -> SolveControl<'c>
+    {
+
+        SolveControl::new(None, None, None, None)
+
+    }
+
+    /* Check if the search should stop, because the
+     * deadline has passed or it was cancelled. */
+    pub fn should_stop(&self) -> bool
+    {
+
+        self.deadline.is_some_and(|deadline| Instant::now() >= deadline) ||
+            self.cancelled.is_some_and(|flag| flag.load(Ordering::Relaxed))
+
+    }
+
+    /* Report the solution, passing it to the callback
+     * if it is better than the ones reported before.
+     * Return whether it was the best, false when
+     * there is no callback. No routes at all, as
+     * returned by a solver stopped before starting,
+     * are not a solution and are not reported. */
+    pub fn report(&self, routes : &Vec< Vec<usize>>) -> bool
+    {
+
+        let (cost_function, callback) = match (self.cost_function, self.on_new_best)
+        {
+            (Some(cost_function), Some(callback)) if !routes.is_empty() => (cost_function, callback),
+            _ => return false,
+        };
+
+        let cost          : f64             = cost_function(routes);
+        let mut best_cost : MutexGuard<f64> = self.best_cost.lock().unwrap();
+
+        if cost >= *best_cost
+        {
+            return false;
+        }
+        *best_cost = cost;
+
+        callback(routes, cost, self.start.elapsed());
+
+        return true;
+
+    }
+
+    /* Cost of the best solution reported,
+     * infinite if none was reported. */
+    pub fn best_cost(&self) -> f64
+    {
+
+        *self.best_cost.lock().unwrap()
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use std::thread;
+    use super::*;
+    use crate::model::{GraphInstance, read_test_instance};
+    use crate::bounds::bounds_instance_trait::BoundsInstanceTrait;
+    use crate::savings_algorithm::savings_algorithm_imp::{SavingsSolver, SavingsParameters};
+    use crate::route_first_algorithm::route_first_algorithm_imp::RouteFirstSolver;
+    use crate::fisher_jaikumar_algorithm::fisher_jaikumar_algorithm_imp::FisherJaikumarSolver;
+    use crate::local_search::inter_route_imp::InterRouteLocalSearch;
+    use crate::local_search::intra_route_imp::IntraRouteOptimizer;
+    use crate::local_search::post_optimization_imp::PostOptimizationSolver;
+    use crate::iterated_local_search::iterated_local_search_imp::IteratedLocalSearch;
+    use crate::hybrid_genetic_search::hgs_imp::HgsSolver;
+    use crate::route_pool::route_pool_imp::RoutePoolSolver;
+
+    /* Time within which a stopped search should
+     * return, far from the time of a full run. */
+    const PROMPT : Duration = Duration::from_secs(2);
+
+    #[test]
+    fn stopped_searches_return_promptly()
+    {
+
+        let graph      : GraphInstance         = read_test_instance("F-n135-k7");
+        let savings    : SavingsSolver         = SavingsSolver::new(&graph);
+        let inter      : InterRouteLocalSearch = InterRouteLocalSearch::new(&graph);
+        let intra      : IntraRouteOptimizer   = IntraRouteOptimizer { instance : &graph };
+        let parametric : SavingsSolver         = SavingsSolver {
+            parameters_grid : SavingsParameters::default_grid(),
+            ..SavingsSolver::new(&graph)
+        };
+        let route_first     : RouteFirstSolver       = RouteFirstSolver { instance : &graph };
+        let fisher_jaikumar : FisherJaikumarSolver   = FisherJaikumarSolver { instance : &graph };
+        let post_optimized  : PostOptimizationSolver = PostOptimizationSolver {
+            solver    : &savings,
+            improvers : vec![&inter, &intra],
+        };
+        let iterated   : IteratedLocalSearch = IteratedLocalSearch {
+            stopping_criterion : StoppingCriterion::Time(Duration::from_secs(60)),
+            ..IteratedLocalSearch::new(&graph, &savings, vec![&inter, &intra], 1)
+        };
+        let genetic    : HgsSolver           = HgsSolver::new(&graph, Duration::from_secs(60), 1);
+        let route_pool : RoutePoolSolver     = RoutePoolSolver::new(&graph, vec![&parametric, &route_first], vec![&inter]);
+
+        let solvers : Vec<&dyn CVRPSolver> = vec![
+            &parametric, &route_first, &fisher_jaikumar, &post_optimized, &iterated, &genetic, &route_pool,
+        ];
+
+        let cancelled : AtomicBool = AtomicBool::new(true);
+        let past      : Instant    = Instant::now();
+        let controls  : Vec<SolveControl> = vec![
+            SolveControl::new(None, None, Some(&cancelled), None),
+            SolveControl::new(None, Some(past), None, None),
+        ];
+
+        for control in &controls
+        {
+            for solver in &solvers
+            {
+                let start : Instant = Instant::now();
+                solver.solve_with_control(control);
+                assert!(start.elapsed() < PROMPT);
+            }
+        }
+
+    }
+
+    #[test]
+    fn cancellation_stops_a_running_search()
+    {
+
+        let graph     : GraphInstance = read_test_instance("F-n135-k7");
+        let genetic   : HgsSolver     = HgsSolver::new(&graph, Duration::from_secs(60), 1);
+        let cancelled : AtomicBool    = AtomicBool::new(false);
+
+        let distance_matrix : Vec< Vec<f64>> = BoundsInstanceTrait::get_distance_matrix(&graph);
+        let routes_cost     = |routes : &Vec< Vec<usize>>| compute_routes_cost(&distance_matrix, routes);
+        let reported        : Mutex<Vec<f64>> = Mutex::new(Vec::new());
+        let on_new_best     = |_ : &Vec< Vec<usize>>, cost : f64, _ : Duration| reported.lock().unwrap().push(cost);
+        let control         : SolveControl = SolveControl::new(Some(&routes_cost), None, Some(&cancelled), Some(&on_new_best));
+
+        let start  : Instant          = Instant::now();
+        let routes : Vec< Vec<usize>> = thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(200));
+                cancelled.store(true, Ordering::Relaxed);
+            });
+            genetic.solve_with_control(&control)
+        });
+        assert!(start.elapsed() < PROMPT);
+
+        /* The costs reported decrease, down to
+         * the cost of the routes returned. */
+        let reported : Vec<f64> = reported.lock().unwrap().clone();
+        assert!(!reported.is_empty());
+        assert!(reported.windows(2).all(|w| w[1] < w[0]));
+        assert_eq!(*reported.last().unwrap(), compute_routes_cost(&distance_matrix, &routes));
+        assert_eq!(control.best_cost(), *reported.last().unwrap());
+
+    }
+
+    #[test]
+    fn solvers_are_not_started_once_stopped()
+    {
+
+        let graph     : GraphInstance = read_test_instance("gr-n17-k3");
+        let savings   : SavingsSolver = SavingsSolver::new(&graph);
+        let cancelled : AtomicBool    = AtomicBool::new(true);
+
+        assert!(savings.solve_with_control(&SolveControl::new(None, None, Some(&cancelled), None)).is_empty());
+        assert!(!savings.solve_with_control(&SolveControl::unlimited()).is_empty());
+
+    }
+
+}
@@ -0,0 +1,315 @@
+use std::time::Instant;
+use rand::{Rng, RngCore, SeedableRng};
+use rand::rngs::StdRng;
+use crate::alns::alns_instance_trait::AlnsInstanceTrait;
+use crate::alns::operators::{AlnsProblem, DestroyOperator, RepairOperator};
+use crate::alns::destroy_operators::{RandomRemoval, WorstRemoval, ShawRemoval, RouteRemoval};
+use crate::alns::repair_operators::{GreedyInsertion, RegretInsertion};
+use crate::algorithm_interface::{CVRPSolver, StoppingCriterion, compute_routes_cost, SolveControl};
+
+/* Here the adaptive large neighbourhood search of
+ * Ropke and Pisinger is implemented. At each
+ * iteration part of the current solution is
+ * destroyed and repaired by operators chosen with
+ * a roulette wheel, whose weights are adapted to
+ * the success of the operators. The new solution
+ * is accepted as in simulated annealing. */
+pub struct AlnsSolver<'a>
+{
+
+    pub instance           : &'a dyn AlnsInstanceTrait,
+
+    /* Solver computing the initial routes. */
+    pub initial_solver     : &'a dyn CVRPSolver,
+
+    pub destroy_operators  : Vec< Box<dyn DestroyOperator>>,
+
+    pub repair_operators   : Vec< Box<dyn RepairOperator>>,
+
+    pub stopping_criterion : StoppingCriterion,
+
+    /* Range of the fraction of the customers
+     * removed at each iteration. */
+    pub removal_fraction   : (f64, f64),
+
+    /* Maximum number of customers removed
+     * at each iteration. */
+    pub max_removals       : usize,
+
+    /* Number of iterations after which the
+     * weights of the operators are updated. */
+    pub segment_length     : usize,
+
+    /* Weight given to the scores of the last
+     * segment when updating the weights. */
+    pub reaction_factor    : f64,
+
+    /* Scores of the operators which give a new
+     * best solution, a solution better than the
+     * current one, or a worse accepted solution. */
+    pub scores             : [f64; 3],
+
+    /* The start temperature is such that a solution
+     * worse than the initial one by this fraction is
+     * accepted with probability one half. */
+    pub start_temperature  : f64,
+
+    pub cooling_rate       : f64,
+
+    pub seed               : u64,
+
+}
+
+/* Methods of AlnsSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> AlnsSolver<'a>
+{
+
+    /* ALNS with all the destroy and repair operators,
+     * and the parameters suggested by Ropke and
+     * Pisinger. */
+    pub fn new(
+        instance       : &'a dyn AlnsInstanceTrait,
+        initial_solver : &'a dyn CVRPSolver,
+        seed           : u64)
+        -> AlnsSolver<'a>
+    {
+
+        AlnsSolver {
+            instance,
+            initial_solver,
+            destroy_operators  : vec![
+                Box::new(RandomRemoval),
+                Box::new(WorstRemoval { randomness : 3.0 }),
+                Box::new(ShawRemoval {
+                    distance_weight : 9.0,
+                    demand_weight   : 2.0,
+                    route_weight    : 5.0,
+                    randomness      : 6.0,
+                }),
+                Box::new(RouteRemoval),
+            ],
+            repair_operators   : vec![
+                Box::new(GreedyInsertion),
+                Box::new(RegretInsertion { k : 2 }),
+                Box::new(RegretInsertion { k : 3 }),
+            ],
+            stopping_criterion : StoppingCriterion::Iterations(2000),
+            removal_fraction   : (0.1, 0.4),
+            max_removals       : 30,
+            segment_length     : 100,
+            reaction_factor    : 0.1,
+            scores             : [33.0, 9.0, 13.0],
+            start_temperature  : 0.05,
+            cooling_rate       : 0.999,
+            seed,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn AlnsInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    pub fn set_seed(&mut self, seed : u64)
+    {
+
+        self.seed = seed;
+
+    }
+
+}
+
+/* Select an index with probability
+ * proportional to its weight. */
+fn roulette_wheel(weights : &[f64], rng : &mut dyn RngCore) -> usize
+{
+
+    let total     : f64 = weights.iter().sum();
+    let mut value : f64 = rng.gen::<f64>() * total;
+
+    for (i, &w) in weights.iter().enumerate()
+    {
+        if value < w
+        {
+            return i;
+        }
+        value -= w;
+    }
+
+    return weights.len() - 1;
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for AlnsSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let instance : &dyn AlnsInstanceTrait = self.instance;
+        let problem  : AlnsProblem            = AlnsProblem {
+            distance_matrix : instance.get_distance_matrix(),
+            nodes_demand    : instance.get_nodes_demand(),
+            capacity        : instance.get_capacity(),
+        };
+        let start    : Instant                = Instant::now();
+        let mut rng  : StdRng                 = StdRng::seed_from_u64(self.seed);
+
+        let mut current_routes : Vec< Vec<usize>> = self.initial_solver.solve_with_control(control);
+        let mut current_cost   : f64              = compute_routes_cost(&problem.distance_matrix, &current_routes);
+        let mut best_routes    : Vec< Vec<usize>> = current_routes.clone();
+        let mut best_cost      : f64              = current_cost;
+        control.report(&best_routes);
+
+        let customers_number : usize = current_routes.iter().map(|r| r.len()).sum();
+        let min_removals     : usize = ((self.removal_fraction.0 * customers_number as f64) as usize)
+            .clamp(1, self.max_removals.max(1));
+        let max_removals     : usize = ((self.removal_fraction.1 * customers_number as f64) as usize)
+            .clamp(min_removals, self.max_removals.max(min_removals));
+
+        let mut temperature : f64 = self.start_temperature * current_cost / 2.0_f64.ln();
+
+        /* Weights of the operators, and their scores and
+         * number of uses in the current segment. */
+        let mut destroy_weights : Vec<f64>   = vec![1.0; self.destroy_operators.len()];
+        let mut repair_weights  : Vec<f64>   = vec![1.0; self.repair_operators.len()];
+        let mut destroy_scores  : Vec<f64>   = vec![0.0; self.destroy_operators.len()];
+        let mut repair_scores   : Vec<f64>   = vec![0.0; self.repair_operators.len()];
+        let mut destroy_uses    : Vec<usize> = vec![0; self.destroy_operators.len()];
+        let mut repair_uses     : Vec<usize> = vec![0; self.repair_operators.len()];
+
+        let mut iteration : usize = 0;
+        while customers_number > 0 && !self.stopping_criterion.is_met(iteration, start) && !control.should_stop()
+        {
+
+            iteration += 1;
+
+            let d : usize = roulette_wheel(&destroy_weights, &mut rng);
+            let r : usize = roulette_wheel(&repair_weights, &mut rng);
+
+            let removals    : usize            = rng.gen_range(min_removals..=max_removals);
+            let mut routes  : Vec< Vec<usize>> = current_routes.clone();
+            let removed     : Vec<usize>       =
+                self.destroy_operators[d].destroy(&problem, &mut routes, removals, &mut rng);
+
+            routes.retain(|r| !r.is_empty());
+            routes = self.repair_operators[r].repair(&problem, routes, removed, &mut rng);
+
+            let cost : f64 = compute_routes_cost(&problem.distance_matrix, &routes);
+
+            /* Score of the operators, and acceptance
+             * of the new solution. */
+            let mut score : f64 = 0.0;
+            if cost < best_cost - 1e-9
+            {
+                score       = self.scores[0];
+                best_cost   = cost;
+                best_routes = routes.clone();
+                control.report(&best_routes);
+            }
+            else if cost < current_cost - 1e-9
+            {
+                score = self.scores[1];
+            }
+
+            let accepted : bool = cost < current_cost ||
+                rng.gen::<f64>() < (-(cost - current_cost) / temperature).exp();
+
+            if accepted
+            {
+                if score == 0.0
+                {
+                    score = self.scores[2];
+                }
+                current_routes = routes;
+                current_cost   = cost;
+            }
+
+            destroy_scores[d] += score;
+            repair_scores[r]  += score;
+            destroy_uses[d]   += 1;
+            repair_uses[r]    += 1;
+
+            temperature *= self.cooling_rate;
+
+            /* Update the weights at the end of the segment. */
+            if iteration.is_multiple_of(self.segment_length.max(1))
+            {
+
+                for (weights, scores, uses) in [
+                    (&mut destroy_weights, &mut destroy_scores, &mut destroy_uses),
+                    (&mut repair_weights, &mut repair_scores, &mut repair_uses)]
+                {
+                    for i in 0..weights.len()
+                    {
+                        if uses[i] > 0
+                        {
+                            weights[i] = weights[i] * (1.0 - self.reaction_factor)
+                                + self.reaction_factor * scores[i] / uses[i] as f64;
+                        }
+                        weights[i] = weights[i].max(1e-3);
+                        scores[i]  = 0.0;
+                        uses[i]    = 0;
+                    }
+                }
+
+            }
+
+        }
+
+        return best_routes;
+
+    }
+
+    fn get_seed(&self) -> Option<u64>
+    {
+
+        Some(self.seed)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use std::time::Duration;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+
+    #[test]
+    fn deadline_stops_the_search()
+    {
+
+        let graph   : GraphInstance = read_test_instance("F-n72-k4");
+        let savings : SavingsSolver = SavingsSolver::new(&graph);
+        let solver  : AlnsSolver    = AlnsSolver {
+            stopping_criterion : StoppingCriterion::Iterations(usize::MAX),
+            ..AlnsSolver::new(&graph, &savings, 1)
+        };
+
+        let start   : Instant          = Instant::now();
+        let control : SolveControl     = SolveControl::new(None, Some(start + Duration::from_millis(100)), None, None);
+        let routes  : Vec< Vec<usize>> = solver.solve_with_control(&control);
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_feasible(&graph, &routes);
+
+    }
+
+}
@@ -0,0 +1,329 @@
+use rand::{Rng, RngCore};
+use rand::seq::SliceRandom;
+use crate::alns::operators::{AlnsProblem, DestroyOperator};
+
+/* Remove the given customers from the routes. */
+fn remove_customers(routes : &mut [Vec<usize>], customers : &[usize])
+{
+
+    for route in routes.iter_mut()
+    {
+        route.retain(|n| !customers.contains(n));
+    }
+
+}
+
+/* Customers currently in the routes. */
+fn routed_customers(routes : &[Vec<usize>]) -> Vec<usize>
+{
+
+    routes.iter().flatten().cloned().collect()
+
+}
+
+/* Index in a list sorted by preference, biased
+ * towards the first elements by the randomness
+ * exponent, as proposed by Ropke and Pisinger.
+ * The larger the exponent, the stronger the bias. */
+fn biased_index(length : usize, randomness : f64, rng : &mut dyn RngCore) -> usize
+{
+
+    let y : f64 = rng.gen::<f64>();
+
+    ((y.powf(randomness) * length as f64) as usize).min(length - 1)
+
+}
+
+/* Remove customers chosen uniformly at random. */
+pub struct RandomRemoval;
+
+impl DestroyOperator for RandomRemoval
+{
+
+    fn destroy(
+        &self,
+        _problem : &AlnsProblem,
+        routes   : &mut Vec< Vec<usize>>,
+        removals : usize,
+        rng      : &mut dyn RngCore)
+        -> Vec<usize>
+    {
+
+        let customers : Vec<usize> = routed_customers(routes);
+        let removed   : Vec<usize> = customers
+            .choose_multiple(rng, removals.min(customers.len()))
+            .cloned()
+            .collect();
+
+        remove_customers(routes, &removed);
+
+        return removed;
+
+    }
+
+}
+
+/* Remove the customers whose removal saves
+ * the most, with some randomness. */
+pub struct WorstRemoval
+{
+
+    pub randomness : f64,
+
+}
+
+impl DestroyOperator for WorstRemoval
+{
+
+    fn destroy(
+        &self,
+        problem  : &AlnsProblem,
+        routes   : &mut Vec< Vec<usize>>,
+        removals : usize,
+        rng      : &mut dyn RngCore)
+        -> Vec<usize>
+    {
+
+        let d              = |a : usize, b : usize| problem.distance_matrix[a][b];
+        let mut removed    : Vec<usize> = Vec::new();
+
+        while removed.len() < removals
+        {
+
+            /* Saving of the removal of each customer,
+             * as (saving, route, position). */
+            let mut savings : Vec<(f64, usize, usize)> = Vec::new();
+            for (r, route) in routes.iter().enumerate()
+            {
+                for p in 0..route.len()
+                {
+                    let previous : usize = if p == 0 { 0 } else { route[p - 1] };
+                    let next     : usize = if p + 1 == route.len() { 0 } else { route[p + 1] };
+                    savings.push((d(previous, route[p]) + d(route[p], next) - d(previous, next), r, p));
+                }
+            }
+
+            if savings.is_empty()
+            {
+                break;
+            }
+
+            savings.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+
+            let (_, r, p) = savings[biased_index(savings.len(), self.randomness, rng)];
+            removed.push(routes[r].remove(p));
+
+        }
+
+        return removed;
+
+    }
+
+}
+
+/* Remove customers related to each other, so that
+ * they can be exchanged by the repair operator.
+ * The relatedness of two customers is given by
+ * their distance, their difference of demand and
+ * whether they are in the same route. */
+pub struct ShawRemoval
+{
+
+    pub distance_weight : f64,
+
+    pub demand_weight   : f64,
+
+    pub route_weight    : f64,
+
+    pub randomness      : f64,
+
+}
+
+impl DestroyOperator for ShawRemoval
+{
+
+    fn destroy(
+        &self,
+        problem  : &AlnsProblem,
+        routes   : &mut Vec< Vec<usize>>,
+        removals : usize,
+        rng      : &mut dyn RngCore)
+        -> Vec<usize>
+    {
+
+        let mut route_of : Vec<usize> = vec![usize::MAX; problem.nodes_demand.len()];
+        for (r, route) in routes.iter().enumerate()
+        {
+            for &n in route
+            {
+                route_of[n] = r;
+            }
+        }
+
+        /* Distances and demands are normalized. */
+        let max_distance : f64 = problem.distance_matrix.iter()
+            .flatten()
+            .cloned()
+            .fold(f64::MIN_POSITIVE, f64::max);
+        let max_demand   : f64 = problem.nodes_demand.iter().cloned().max().unwrap_or(1).max(1) as f64;
+
+        let relatedness = |i : usize, j : usize| -> f64
+            {
+                self.distance_weight * problem.distance_matrix[i][j] / max_distance
+                    + self.demand_weight
+                        * (problem.nodes_demand[i] as f64 - problem.nodes_demand[j] as f64).abs() / max_demand
+                    + self.route_weight * if route_of[i] == route_of[j] { 0.0 } else { 1.0 }
+            };
+
+        let mut remaining : Vec<usize> = routed_customers(routes);
+        if remaining.is_empty()
+        {
+            return Vec::new();
+        }
+
+        let first       : usize      = remaining.swap_remove(rng.gen_range(0..remaining.len()));
+        let mut removed : Vec<usize> = vec![first];
+
+        while removed.len() < removals && !remaining.is_empty()
+        {
+
+            let reference : usize = removed[rng.gen_range(0..removed.len())];
+
+            remaining.sort_by(|&a, &b| relatedness(reference, a).partial_cmp(&relatedness(reference, b)).unwrap());
+            removed.push(remaining.remove(biased_index(remaining.len(), self.randomness, rng)));
+
+        }
+
+        remove_customers(routes, &removed);
+
+        return removed;
+
+    }
+
+}
+
+/* Remove whole routes, chosen at random, until
+ * at least the given number of customers is
+ * removed. */
+pub struct RouteRemoval;
+
+impl DestroyOperator for RouteRemoval
+{
+
+    fn destroy(
+        &self,
+        _problem : &AlnsProblem,
+        routes   : &mut Vec< Vec<usize>>,
+        removals : usize,
+        rng      : &mut dyn RngCore)
+        -> Vec<usize>
+    {
+
+        let mut removed : Vec<usize> = Vec::new();
+
+        while removed.len() < removals
+        {
+
+            let not_empty : Vec<usize> = (0..routes.len())
+                .filter(|&r| !routes[r].is_empty())
+                .collect();
+
+            match not_empty.choose(rng)
+            {
+                Some(&r) => removed.append(&mut routes[r]),
+                _ => break,
+            }
+
+        }
+
+        return removed;
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::algorithm_interface::CVRPSolver;
+    use crate::alns::alns_instance_trait::AlnsInstanceTrait;
+    use crate::model::{GraphInstance, read_test_instance};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+
+    /* Destroy the savings routes of F-n45-k4, and check
+     * that the removed customers are no longer routed,
+     * and that the other ones kept their order. */
+    fn check_destroy(operator : &dyn DestroyOperator, removals : usize, exact : bool)
+    {
+
+        let graph   : GraphInstance    = read_test_instance("F-n45-k4");
+        let problem : AlnsProblem      = AlnsProblem {
+            distance_matrix : graph.get_distance_matrix(),
+            nodes_demand    : graph.get_nodes_demand(),
+            capacity        : graph.get_capacity(),
+        };
+        let initial : Vec< Vec<usize>> = SavingsSolver::new(&graph).solve();
+        let mut rng : StdRng           = StdRng::seed_from_u64(7);
+
+        for _ in 0..20
+        {
+
+            let mut routes : Vec< Vec<usize>> = initial.clone();
+            let removed    : Vec<usize>       = operator.destroy(&problem, &mut routes, removals, &mut rng);
+
+            if exact
+            {
+                assert_eq!(removed.len(), removals);
+            }
+            else
+            {
+                assert!(removed.len() >= removals);
+            }
+
+            let mut all : Vec<usize> = routed_customers(&routes);
+            all.extend(&removed);
+            all.sort();
+            assert_eq!(all, (1..graph.get_nodes_demand().len()).collect::<Vec<usize>>());
+
+            for (route, kept) in initial.iter().zip(&routes)
+            {
+                let expected : Vec<usize> = route.iter().filter(|n| !removed.contains(n)).cloned().collect();
+                assert_eq!(kept, &expected);
+            }
+
+        }
+
+    }
+
+    #[test]
+    fn random_removal_removes_the_given_number()
+    {
+        check_destroy(&RandomRemoval, 8, true);
+    }
+
+    #[test]
+    fn worst_removal_removes_the_given_number()
+    {
+        check_destroy(&WorstRemoval { randomness : 3.0 }, 8, true);
+    }
+
+    #[test]
+    fn shaw_removal_removes_the_given_number()
+    {
+        check_destroy(
+            &ShawRemoval { distance_weight : 9.0, demand_weight : 2.0, route_weight : 5.0, randomness : 6.0 },
+            8,
+            true);
+    }
+
+    #[test]
+    fn route_removal_removes_at_least_the_given_number()
+    {
+        check_destroy(&RouteRemoval, 8, false);
+    }
+
+}
@@ -0,0 +1,5 @@
+pub mod alns_instance_trait;
+pub mod operators;
+pub mod destroy_operators;
+pub mod repair_operators;
+pub mod alns_imp;
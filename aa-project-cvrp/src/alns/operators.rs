@@ -0,0 +1,46 @@
+use rand::RngCore;
+
+/* Data of the instance shared by
+ * the destroy and repair operators. */
+pub struct AlnsProblem
+{
+
+    pub distance_matrix : Vec< Vec<f64>>,
+
+    pub nodes_demand    : Vec<usize>,
+
+    pub capacity        : usize,
+
+}
+
+/* A destroy operator removes some customers
+ * from the routes, and returns them. */
+pub trait DestroyOperator : Sync
+{
+
+    /* Remove about the given number of customers,
+     * leaving the emptied routes in place. */
+    fn destroy(
+        &self,
+        problem  : &AlnsProblem,
+        routes   : &mut Vec< Vec<usize>>,
+        removals : usize,
+        rng      : &mut dyn RngCore)
+        -> Vec<usize>;
+
+}
+
+/* A repair operator inserts the removed
+ * customers back into the routes. */
+pub trait RepairOperator : Sync
+{
+
+    fn repair(
+        &self,
+        problem : &AlnsProblem,
+        routes  : Vec< Vec<usize>>,
+        removed : Vec<usize>,
+        rng     : &mut dyn RngCore)
+        -> Vec< Vec<usize>>;
+
+}
@@ -0,0 +1,124 @@
+use rand::RngCore;
+use crate::alns::operators::{AlnsProblem, RepairOperator};
+use crate::construction::insertion_imp::{insert_customers, InsertionCriterion};
+
+/* Insert each time the customer with the
+ * cheapest feasible insertion. */
+pub struct GreedyInsertion;
+
+impl RepairOperator for GreedyInsertion
+{
+
+    fn repair(
+        &self,
+        problem : &AlnsProblem,
+        routes  : Vec< Vec<usize>>,
+        removed : Vec<usize>,
+        _rng    : &mut dyn RngCore)
+        -> Vec< Vec<usize>>
+    {
+
+        insert_customers(
+            &problem.distance_matrix,
+            &problem.nodes_demand,
+            problem.capacity,
+            InsertionCriterion::Cheapest,
+            routes,
+            removed)
+
+    }
+
+}
+
+/* Insert each time the customer with the largest
+ * regret over its k best routes. */
+pub struct RegretInsertion
+{
+
+    pub k : usize,
+
+}
+
+impl RepairOperator for RegretInsertion
+{
+
+    fn repair(
+        &self,
+        problem : &AlnsProblem,
+        routes  : Vec< Vec<usize>>,
+        removed : Vec<usize>,
+        _rng    : &mut dyn RngCore)
+        -> Vec< Vec<usize>>
+    {
+
+        insert_customers(
+            &problem.distance_matrix,
+            &problem.nodes_demand,
+            problem.capacity,
+            InsertionCriterion::Regret(self.k),
+            routes,
+            removed)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::algorithm_interface::CVRPSolver;
+    use crate::alns::alns_instance_trait::AlnsInstanceTrait;
+    use crate::alns::operators::DestroyOperator;
+    use crate::alns::destroy_operators::{RandomRemoval, RouteRemoval};
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+
+    #[test]
+    fn repair_gives_feasible_routes()
+    {
+
+        let repair_operators : Vec< Box<dyn RepairOperator>> = vec![
+            Box::new(GreedyInsertion),
+            Box::new(RegretInsertion { k : 2 }),
+            Box::new(RegretInsertion { k : 3 }),
+        ];
+        let destroy_operators : Vec< Box<dyn DestroyOperator>> = vec![
+            Box::new(RandomRemoval),
+            Box::new(RouteRemoval),
+        ];
+
+        for name in ["F-n45-k4", "bays-n29-k5"]
+        {
+
+            let graph   : GraphInstance    = read_test_instance(name);
+            let problem : AlnsProblem      = AlnsProblem {
+                distance_matrix : graph.get_distance_matrix(),
+                nodes_demand    : graph.get_nodes_demand(),
+                capacity        : graph.get_capacity(),
+            };
+            let initial : Vec< Vec<usize>> = SavingsSolver::new(&graph).solve();
+            let mut rng : StdRng           = StdRng::seed_from_u64(3);
+
+            for destroy in &destroy_operators
+            {
+                for repair in &repair_operators
+                {
+
+                    let mut routes : Vec< Vec<usize>> = initial.clone();
+                    let removed    : Vec<usize>       = destroy.destroy(&problem, &mut routes, 10, &mut rng);
+
+                    routes.retain(|r| !r.is_empty());
+                    assert_feasible(&graph, &repair.repair(&problem, routes, removed, &mut rng));
+
+                }
+            }
+
+        }
+
+    }
+
+}
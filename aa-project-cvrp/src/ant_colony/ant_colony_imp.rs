@@ -0,0 +1,381 @@
+use std::time::Instant;
+use rand::{Rng, RngCore, SeedableRng};
+use rand::rngs::StdRng;
+use crate::ant_colony::ant_colony_instance_trait::AntColonyInstanceTrait;
+use crate::local_search::moves::Neighbourhood;
+use crate::local_search::intra_route_imp::optimize_route_with;
+use crate::algorithm_interface::{CVRPSolver, StoppingCriterion, compute_routes_cost, SolveControl};
+
+/* Here the rank-based ant system of Bullnheimer,
+ * Hartl and Strauss is implemented. Each ant builds
+ * the routes one customer at a time, choosing the
+ * next customer with a probability given by the
+ * pheromone trail, the inverse of the distance and
+ * the saving of the edge. Only the best ants of each
+ * iteration, weighted by rank, and the best solution
+ * found deposit pheromone. */
+pub struct AntColonySolver<'a>
+{
+
+    pub instance           : &'a dyn AntColonyInstanceTrait,
+
+    /* Number of ants of each iteration. */
+    pub ants               : usize,
+
+    pub stopping_criterion : StoppingCriterion,
+
+    /* Exponents of the pheromone, of the inverse
+     * of the distance and of the saving. */
+    pub alpha              : f64,
+
+    pub beta               : f64,
+
+    pub gamma              : f64,
+
+    /* Fraction of the pheromone evaporated
+     * at each iteration. */
+    pub evaporation        : f64,
+
+    /* Number of elitist ants: the best solution found
+     * and the best ants of the iteration but one. */
+    pub elitist_ants       : usize,
+
+    /* Number of nearest nodes among which the next
+     * customer is chosen, when any fits. */
+    pub candidates         : usize,
+
+    /* Improve the routes of each ant with 2-opt. */
+    pub two_opt            : bool,
+
+    pub seed               : u64,
+
+}
+
+/* Methods of AntColonySolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> AntColonySolver<'a>
+{
+
+    /* Rank-based ant system with 2-opt and the
+     * parameters suggested by Bullnheimer et al. */
+    pub fn new(instance : &'a dyn AntColonyInstanceTrait, seed : u64) -> AntColonySolver<'a>
+    {
+
+        AntColonySolver {
+            instance,
+            ants               : 25,
+            stopping_criterion : StoppingCriterion::Iterations(100),
+            alpha              : 1.0,
+            beta               : 2.0,
+            gamma              : 2.0,
+            evaporation        : 0.25,
+            elitist_ants       : 6,
+            candidates         : 20,
+            two_opt            : true,
+            seed,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn AntColonyInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    pub fn set_seed(&mut self, seed : u64)
+    {
+
+        self.seed = seed;
+
+    }
+
+}
+
+/* Select an index with probability
+ * proportional to its weight. */
+fn roulette_wheel(weights : &[f64], rng : &mut dyn RngCore) -> usize
+{
+
+    let total     : f64 = weights.iter().sum();
+    let mut value : f64 = rng.gen::<f64>() * total;
+
+    for (i, &w) in weights.iter().enumerate()
+    {
+        if value < w
+        {
+            return i;
+        }
+        value -= w;
+    }
+
+    return weights.len() - 1;
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for AntColonySolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let instance        : &dyn AntColonyInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>              = instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>                  = instance.get_nodes_demand();
+        let capacity        : usize                       = instance.get_capacity();
+        let node_number     : usize                       = nodes_demand.len();
+        let start           : Instant                     = Instant::now();
+        let mut rng         : StdRng                      = StdRng::seed_from_u64(self.seed);
+
+        /* A customer whose demand exceeds the capacity
+         * can not be served by any ant, so no routes
+         * are given, as by the other solvers which
+         * find none. */
+        if nodes_demand.iter().any(|&q| q > capacity)
+        {
+            return Vec::new();
+        }
+
+        /* Attractiveness of each edge, from the distance and
+         * from the saving, which is taken as one for the edges
+         * of the depot and for the non positive savings. */
+        let mut attractiveness : Vec< Vec<f64>> = vec![vec![0.0; node_number]; node_number];
+        for i in 0..node_number
+        {
+            for j in 0..node_number
+            {
+                if i != j
+                {
+                    attractiveness[i][j] = (1.0 / distance_matrix[i][j].max(1e-9)).powf(self.beta);
+                }
+            }
+        }
+        for (i, j, saving) in instance.compute_savings()
+        {
+            let weight : f64 = (saving.max(1) as f64).powf(self.gamma);
+            attractiveness[i][j] *= weight;
+            attractiveness[j][i] *= weight;
+        }
+
+        /* Candidate lists of every node, depot
+         * included, made of customers only. */
+        let candidates : Vec< Vec<usize>> = (0..node_number)
+            .map(|i| {
+                let mut nodes : Vec<usize> = (1..node_number).filter(|&j| j != i).collect();
+                nodes.sort_by(|&a, &b| distance_matrix[i][a].partial_cmp(&distance_matrix[i][b]).unwrap());
+                nodes.truncate(self.candidates);
+                nodes
+            })
+            .collect();
+
+        /* The pheromone is uniform until the first
+         * solution gives its scale. */
+        let mut pheromone : Vec< Vec<f64>> = vec![vec![1.0; node_number]; node_number];
+
+        let mut best_routes : Vec< Vec<usize>> = Vec::new();
+        let mut best_cost   : f64              = f64::INFINITY;
+
+        /* At least one iteration is completed,
+         * so that some routes are found. */
+        let mut iteration : usize = 0;
+        while !self.stopping_criterion.is_met(iteration, start) && (iteration == 0 || !control.should_stop())
+        {
+
+            iteration += 1;
+
+            let mut solutions : Vec<(f64, Vec< Vec<usize>>)> = Vec::with_capacity(self.ants);
+
+            for _ in 0..self.ants.max(1)
+            {
+
+                let mut visited   : Vec<bool>        = vec![false; node_number];
+                let mut remaining : usize            = node_number - 1;
+                let mut routes    : Vec< Vec<usize>> = vec![Vec::new()];
+                let mut load      : usize            = 0;
+                let mut current   : usize            = 0;
+
+                while remaining > 0
+                {
+
+                    let fits = |j : &usize| !visited[*j] && load + nodes_demand[*j] <= capacity;
+
+                    /* The candidate list is extended to every
+                     * customer when none of the candidates fits. */
+                    let mut feasible : Vec<usize> = candidates[current].iter().cloned().filter(fits).collect();
+                    if feasible.is_empty()
+                    {
+                        feasible = (1..node_number).filter(fits).collect();
+                    }
+
+                    /* Every customer fits into an empty
+                     * route, so a new one is opened. */
+                    if feasible.is_empty()
+                    {
+                        routes.push(Vec::new());
+                        load    = 0;
+                        current = 0;
+                        continue;
+                    }
+
+                    let weights : Vec<f64> = feasible.iter()
+                        .map(|&j| pheromone[current][j].powf(self.alpha) * attractiveness[current][j])
+                        .collect();
+                    let next    : usize    = feasible[roulette_wheel(&weights, &mut rng)];
+
+                    routes.last_mut().unwrap().push(next);
+                    visited[next]  = true;
+                    load          += nodes_demand[next];
+                    remaining     -= 1;
+                    current        = next;
+
+                }
+
+                routes.retain(|r| !r.is_empty());
+
+                if self.two_opt
+                {
+                    for route in routes.iter_mut()
+                    {
+                        optimize_route_with(&distance_matrix, route, &[Neighbourhood::TwoOpt], control);
+                    }
+                }
+
+                solutions.push((compute_routes_cost(&distance_matrix, &routes), routes));
+
+            }
+
+            solutions.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+            if solutions[0].0 < best_cost
+            {
+                best_cost   = solutions[0].0;
+                best_routes = solutions[0].1.clone();
+                control.report(&best_routes);
+            }
+
+            if iteration == 1
+            {
+                let initial : f64 = 1.0 / (self.evaporation * best_cost);
+                pheromone = vec![vec![initial; node_number]; node_number];
+            }
+
+            /* Evaporation, then deposit by the best solution
+             * and by the best ants of the iteration. */
+            let elitist : f64 = self.elitist_ants.max(1) as f64;
+            for row in pheromone.iter_mut()
+            {
+                for value in row.iter_mut()
+                {
+                    *value *= 1.0 - self.evaporation;
+                }
+            }
+
+            let deposits = std::iter::once((elitist, best_cost, &best_routes))
+                .chain(solutions.iter()
+                    .take(self.elitist_ants.saturating_sub(1))
+                    .enumerate()
+                    .map(|(rank, (cost, routes))| (elitist - 1.0 - rank as f64, *cost, routes)));
+
+            for (weight, cost, routes) in deposits
+            {
+                let amount : f64 = weight / cost;
+                for route in routes
+                {
+                    let mut previous : usize = 0;
+                    for &node in route.iter().chain(std::iter::once(&0))
+                    {
+                        pheromone[previous][node] += amount;
+                        pheromone[node][previous] += amount;
+                        previous = node;
+                    }
+                }
+            }
+
+        }
+
+        return best_routes;
+
+    }
+
+    fn get_seed(&self) -> Option<u64>
+    {
+
+        Some(self.seed)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use std::time::Duration;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+
+    #[test]
+    fn deadline_stops_the_search()
+    {
+
+        let graph   : GraphInstance   = read_test_instance("F-n72-k4");
+        let solver  : AntColonySolver = AntColonySolver {
+            stopping_criterion : StoppingCriterion::Iterations(usize::MAX),
+            ..AntColonySolver::new(&graph, 1)
+        };
+
+        let start   : Instant          = Instant::now();
+        let control : SolveControl     = SolveControl::new(None, Some(start + Duration::from_millis(100)), None, None);
+        let routes  : Vec< Vec<usize>> = solver.solve_with_control(&control);
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_feasible(&graph, &routes);
+
+    }
+
+    #[test]
+    fn ants_give_feasible_routes()
+    {
+
+        for name in ["F-n45-k4", "gr-n24-k4", "bays-n29-k5"]
+        {
+
+            let graph  : GraphInstance   = read_test_instance(name);
+            let solver : AntColonySolver = AntColonySolver {
+                stopping_criterion : StoppingCriterion::Iterations(5),
+                ..AntColonySolver::new(&graph, 1)
+            };
+
+            assert_feasible(&graph, &solver.solve());
+
+        }
+
+    }
+
+    #[test]
+    fn demand_above_the_capacity_gives_no_routes()
+    {
+
+        let mut graph : GraphInstance = read_test_instance("gr-n17-k3");
+        graph.instance.specification.capacity = graph.get_nodes_demand().into_iter().max().unwrap() - 1;
+
+        let solver : AntColonySolver = AntColonySolver {
+            stopping_criterion : StoppingCriterion::Iterations(5),
+            ..AntColonySolver::new(&graph, 1)
+        };
+
+        assert!(solver.solve().is_empty());
+
+    }
+
+}
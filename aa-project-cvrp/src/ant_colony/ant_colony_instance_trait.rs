@@ -0,0 +1,18 @@
+/* This trait define the methods required
+ * by the ant colony optimization, available
+ * only for GraphInstance when explicitly
+ * implemented. */
+pub trait AntColonyInstanceTrait : Sync
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>>;
+
+    fn get_capacity(&self) -> usize;
+
+    fn get_nodes_demand(&self) -> Vec<usize>;
+
+    /* Savings of the couples of customers,
+     * as computed for the savings algorithm. */
+    fn compute_savings(&self) -> Vec<(usize, usize, usize)>;
+
+}
@@ -0,0 +1,2 @@
+pub mod ant_colony_instance_trait;
+pub mod ant_colony_imp;
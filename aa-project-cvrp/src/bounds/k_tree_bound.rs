@@ -0,0 +1,292 @@
+/* Lagrangian K-tree lower bound of Fisher on the cost
+ * of the routes. A K-tree is here a spanning tree in
+ * which the depot has degree K, plus the K cheapest
+ * further edges of the depot to distinct customers,
+ * which may repeat edges of the tree: every solution
+ * with K routes is one of them, removing an edge of
+ * the depot from each route, the route of a single
+ * customer keeping its edge twice. The constraints
+ * on the degree of the customers, which is two in
+ * any solution, are relaxed with Lagrangian
+ * multipliers, optimized by subgradient ascent.
+ * The bound holds for K routes only, so the bound
+ * on the cost of any solution is the least over
+ * the numbers of routes a better solution may use. */
+
+/* Bounds of the subgradient ascent: the number of
+ * iterations, and the number of iterations without
+ * improvement after which the step is halved. */
+const SUBGRADIENT_ITERATIONS : usize = 1000;
+const HALVING_PATIENCE       : usize = 20;
+const MIN_STEP_FACTOR        : f64   = 1e-4;
+
+/* Lower bound on the cost of the solutions with at
+ * least the given number of routes. The upper bound,
+ * the cost of a known solution, sets the subgradient
+ * step and the most routes to consider: K routes
+ * cost at least the two edges of the depot to the
+ * K customers closest to it, and the solutions not
+ * cheaper than the upper bound need no bound. The
+ * ascent for each number of routes starts from the
+ * multipliers of the previous one. */
+pub fn k_tree_bound(
+    distance_matrix : &[Vec<f64>],
+    min_vehicles    : usize,
+    upper_bound     : f64)
+    -> f64
+{
+
+    let node_number : usize = distance_matrix.len();
+    if node_number < 2
+    {
+        return 0.0;
+    }
+
+    let mut depot_costs : Vec<f64> = (1..node_number)
+        .map(|j| distance_matrix[0][j] + distance_matrix[j][0])
+        .collect();
+    depot_costs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let min_vehicles : usize = min_vehicles.max(1).min(node_number - 1);
+    let mut max_vehicles : usize = min_vehicles;
+    let mut depot_cost   : f64   = depot_costs.iter().take(min_vehicles + 1).sum();
+    while max_vehicles < node_number - 1 && depot_cost < upper_bound
+    {
+        max_vehicles += 1;
+        depot_cost   += depot_costs.get(max_vehicles).cloned().unwrap_or(0.0);
+    }
+
+    let mut multipliers : Vec<f64> = vec![0.0; node_number];
+    let mut result      : f64      = upper_bound;
+    for vehicles in min_vehicles..=max_vehicles
+    {
+        result = result.min(subgradient_ascent(distance_matrix, vehicles, upper_bound, result, &mut multipliers));
+    }
+
+    return result;
+
+}
+
+/* Lower bound on the cost of the solutions with the
+ * given number of routes, by subgradient ascent from
+ * the given multipliers, left at their last values.
+ * The ascent ends once the bound reaches the target,
+ * the least bound of the numbers of routes before. */
+fn subgradient_ascent(
+    distance_matrix : &[Vec<f64>],
+    vehicles        : usize,
+    upper_bound     : f64,
+    target          : f64,
+    multipliers     : &mut [f64])
+    -> f64
+{
+
+    let node_number : usize = distance_matrix.len();
+
+    let mut best        : f64      = f64::NEG_INFINITY;
+    let mut step_factor : f64      = 2.0;
+    let mut stalled     : usize    = 0;
+
+    for _ in 0..SUBGRADIENT_ITERATIONS
+    {
+
+        let costs : Vec< Vec<f64>> = (0..node_number)
+            .map(|i| (0..node_number)
+                .map(|j| distance_matrix[i][j] - multipliers[i] - multipliers[j])
+                .collect())
+            .collect();
+
+        let (value, degrees) : (f64, Vec<usize>) = minimum_k_tree(&costs, vehicles);
+        let bound            : f64               = value + 2.0 * multipliers.iter().sum::<f64>();
+
+        if bound > best + 1e-9
+        {
+            best    = bound;
+            stalled = 0;
+        }
+        else
+        {
+            stalled += 1;
+            if stalled >= HALVING_PATIENCE
+            {
+                step_factor /= 2.0;
+                stalled      = 0;
+            }
+        }
+
+        let subgradient : Vec<f64> = degrees.iter().map(|&d| 2.0 - d as f64).collect();
+        let norm        : f64      = subgradient.iter().skip(1).map(|g| g * g).sum();
+
+        /* Every customer has degree two: the
+         * K-tree is a solution, and optimal. */
+        if norm == 0.0 || step_factor < MIN_STEP_FACTOR || best >= target
+        {
+            break;
+        }
+
+        let step : f64 = step_factor * (upper_bound - bound).max(1e-6) / norm;
+        for i in 1..node_number
+        {
+            multipliers[i] += step * subgradient[i];
+        }
+
+    }
+
+    return best;
+
+}
+
+/* Edge of the depot added to the tree, as the
+ * increase of the cost, the customer joined to
+ * the depot and the edge removed from its cycle,
+ * if any. */
+type DepotExchange = (f64, usize, Option<(usize, usize)>);
+
+/* Minimum K-tree over the given costs, with the
+ * degree of each node. The minimum spanning tree
+ * in which the depot has degree K is found as in
+ * Glover and Klingman: starting from the minimum
+ * spanning tree of the customers, joined to the
+ * depot by its cheapest edge, at each step the
+ * depot edge is added which, removing the costliest
+ * edge on its cycle, increases the cost the least. */
+fn minimum_k_tree(costs : &[Vec<f64>], vehicles : usize) -> (f64, Vec<usize>)
+{
+
+    let node_number   : usize            = costs.len();
+    let mut adjacency : Vec< Vec<usize>> = vec![Vec::new(); node_number];
+    let mut value     : f64              = 0.0;
+
+    /* Prim's algorithm over the customers. */
+    let mut in_tree  : Vec<bool>  = vec![false; node_number];
+    let mut distance : Vec<f64>   = vec![f64::INFINITY; node_number];
+    let mut parent   : Vec<usize> = vec![0; node_number];
+    distance[1] = 0.0;
+    for _ in 1..node_number
+    {
+        let u : usize = (1..node_number)
+            .filter(|&v| !in_tree[v])
+            .min_by(|&a, &b| distance[a].partial_cmp(&distance[b]).unwrap())
+            .unwrap();
+        in_tree[u] = true;
+        if u != 1
+        {
+            adjacency[u].push(parent[u]);
+            adjacency[parent[u]].push(u);
+            value += costs[u][parent[u]];
+        }
+        for v in 1..node_number
+        {
+            if !in_tree[v] && costs[u][v] < distance[v]
+            {
+                distance[v] = costs[u][v];
+                parent[v]   = u;
+            }
+        }
+    }
+
+    /* Join the depot by its cheapest edge, then
+     * raise its degree by the best exchanges. */
+    for _ in 0..vehicles
+    {
+
+        /* Costliest edge not incident to the depot on the
+         * path from the depot to each customer, None for
+         * the customers already adjacent to the depot. */
+        let mut costliest : Vec<Option<(f64, usize, usize)>> = vec![None; node_number];
+        let mut stack     : Vec<(usize, usize)>              = Vec::new();
+        for &c in &adjacency[0]
+        {
+            stack.push((c, 0));
+        }
+        while let Some((u, from)) = stack.pop()
+        {
+            for &v in &adjacency[u]
+            {
+                if v != from && v != 0
+                {
+                    let edge : (f64, usize, usize) = (costs[u][v], u, v);
+                    costliest[v] = match costliest[u]
+                    {
+                        Some(e) if e.0 >= edge.0 => Some(e),
+                        _ => Some(edge),
+                    };
+                    stack.push((v, u));
+                }
+            }
+        }
+
+        let exchange : Option<DepotExchange> = if adjacency[0].is_empty()
+        {
+            (1..node_number)
+                .map(|j| (costs[0][j], j, None))
+                .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+        }
+        else
+        {
+            (1..node_number)
+                .filter_map(|j| costliest[j].map(|(c, u, v)| (costs[0][j] - c, j, Some((u, v)))))
+                .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+        };
+
+        let (delta, j, removed) = match exchange
+        {
+            Some(e) => e,
+            None    => break,
+        };
+
+        if let Some((u, v)) = removed
+        {
+            adjacency[u].retain(|&w| w != v);
+            adjacency[v].retain(|&w| w != u);
+        }
+        adjacency[0].push(j);
+        adjacency[j].push(0);
+        value += delta;
+
+    }
+
+    let mut degrees : Vec<usize> = adjacency.iter().map(|a| a.len()).collect();
+
+    /* The cheapest further edges of the depot. */
+    let mut depot_edges : Vec<usize> = (1..node_number).collect();
+    depot_edges.sort_by(|&a, &b| costs[0][a].partial_cmp(&costs[0][b]).unwrap());
+    for &j in depot_edges.iter().take(vehicles)
+    {
+        value      += costs[0][j];
+        degrees[j] += 1;
+        degrees[0] += 1;
+    }
+
+    return (value, degrees);
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::model::{GraphInstance, read_test_instance};
+    use crate::bounds::bounds_instance_trait::BoundsInstanceTrait;
+    use crate::bounds::vehicle_bound::vehicles_lower_bound;
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+    use crate::algorithm_interface::{CVRPSolver, compute_routes_cost};
+
+    #[test]
+    fn bound_does_not_exceed_the_optimum()
+    {
+
+        /* The optimum of gr-n17-k3 is 2685. */
+        let graph           : GraphInstance  = read_test_instance("gr-n17-k3");
+        let distance_matrix : Vec< Vec<f64>> = BoundsInstanceTrait::get_distance_matrix(&graph);
+        let vehicles        : usize          = vehicles_lower_bound(
+            &BoundsInstanceTrait::get_nodes_demand(&graph), BoundsInstanceTrait::get_capacity(&graph));
+        let upper_bound     : f64            = compute_routes_cost(&distance_matrix, &SavingsSolver::new(&graph).solve());
+
+        let bound : f64 = k_tree_bound(&distance_matrix, vehicles, upper_bound);
+        assert!(bound > 0.0 && bound <= 2685.0);
+
+    }
+
+}
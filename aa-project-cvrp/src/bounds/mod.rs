@@ -0,0 +1,3 @@
+pub mod bounds_instance_trait;
+pub mod vehicle_bound;
+pub mod k_tree_bound;
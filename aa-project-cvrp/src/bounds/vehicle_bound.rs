@@ -0,0 +1,140 @@
+/* Bounds on the number of vehicles, seen as the
+ * number of bins of a bin packing problem, where
+ * the items are the demands of the customers. */
+
+/* Lower bound on the number of vehicles: the larger
+ * between the ceiling of the total demand over the
+ * capacity and the bound L2 of Martello and Toth.
+ * For each threshold a up to half the capacity, the
+ * demands greater than the capacity minus a require
+ * a vehicle each, and so do the demands greater
+ * than half the capacity, whose vehicles can be
+ * filled by the demands between a and half the
+ * capacity only in their residual capacity. */
+pub fn vehicles_lower_bound(nodes_demand : &[usize], capacity : usize) -> usize
+{
+
+    let demands : Vec<usize> = nodes_demand.iter().skip(1).cloned().filter(|&q| q > 0).collect();
+    let total   : usize      = demands.iter().sum();
+
+    let mut result : usize = total.div_ceil(capacity);
+
+    let mut thresholds : Vec<usize> = demands.iter().cloned().filter(|&q| 2 * q <= capacity).collect();
+    thresholds.push(0);
+    thresholds.sort();
+    thresholds.dedup();
+
+    for a in thresholds
+    {
+
+        let large  : usize      = demands.iter().filter(|&&q| q > capacity - a).count();
+        let medium : Vec<usize> = demands.iter().cloned()
+            .filter(|&q| q <= capacity - a && 2 * q > capacity)
+            .collect();
+        let small  : usize      = demands.iter().cloned()
+            .filter(|&q| 2 * q <= capacity && q >= a)
+            .sum();
+
+        let residual : usize = medium.len() * capacity - medium.iter().sum::<usize>();
+        let extra    : usize = small.saturating_sub(residual).div_ceil(capacity);
+
+        result = result.max(large + medium.len() + extra);
+
+    }
+
+    return result;
+
+}
+
+/* Assign the customers to vehicles by first fit
+ * decreasing: by decreasing demand, each customer
+ * goes to the first vehicle with enough residual
+ * capacity, or to a new one. The number of vehicles
+ * bounds the number needed from above, so it is
+ * optimal when it meets the lower bound. */
+pub fn first_fit_decreasing(nodes_demand : &[usize], capacity : usize) -> Vec< Vec<usize>>
+{
+
+    let mut customers : Vec<usize> = (1..nodes_demand.len()).collect();
+    customers.sort_by(|&a, &b| nodes_demand[b].cmp(&nodes_demand[a]));
+
+    let mut vehicles : Vec< Vec<usize>> = Vec::new();
+    let mut loads    : Vec<usize>       = Vec::new();
+
+    for customer in customers
+    {
+        match (0..vehicles.len()).find(|&v| loads[v] + nodes_demand[customer] <= capacity)
+        {
+            Some(v) =>
+            {
+                vehicles[v].push(customer);
+                loads[v] += nodes_demand[customer];
+            },
+            None =>
+            {
+                vehicles.push(vec![customer]);
+                loads.push(nodes_demand[customer]);
+            },
+        }
+    }
+
+    return vehicles;
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+
+    #[test]
+    fn vehicles_lower_bound_of_large_demands()
+    {
+
+        /* The total demand fits in two vehicles, but
+         * no two demands above half the capacity fit
+         * together. */
+        assert_eq!(vehicles_lower_bound(&[0, 6, 6, 6], 10), 3);
+        assert_eq!(vehicles_lower_bound(&[0, 5, 5, 5, 5], 10), 2);
+        assert_eq!(vehicles_lower_bound(&[0, 3, 3, 3, 3], 10), 2);
+
+        /* Three demands of 7 need a vehicle each, and
+         * their residual capacity of 3 cannot take the
+         * demands of 4: L2 exceeds the total bound. */
+        assert_eq!(vehicles_lower_bound(&[0, 7, 7, 7, 4, 4], 10), 4);
+
+        /* Customers without demand need no vehicle. */
+        assert_eq!(vehicles_lower_bound(&[0, 0, 0], 10), 0);
+
+    }
+
+    #[test]
+    fn first_fit_decreasing_within_capacity()
+    {
+
+        let nodes_demand : Vec<usize> = vec![0, 2, 5, 3, 1, 4, 2, 3];
+
+        let vehicles : Vec< Vec<usize>> = first_fit_decreasing(&nodes_demand, 10);
+
+        /* 5 4 1 and 3 3 2 2, meeting the lower bound. */
+        assert_eq!(vehicles.len(), 2);
+        assert_eq!(vehicles.len(), vehicles_lower_bound(&nodes_demand, 10));
+        assert_eq!(vehicles[0], vec![2, 5, 4]);
+
+        let mut served : Vec<usize> = vehicles.concat();
+        served.sort();
+        assert_eq!(served, (1..nodes_demand.len()).collect::<Vec<usize>>());
+        assert!(vehicles.iter().all(|v| v.iter().map(|&i| nodes_demand[i]).sum::<usize>() <= 10));
+
+        /* Demands of 5, 4, 3, 3, 3, 2 fit in {5, 3, 2}
+         * and {4, 3, 3}, but first fit decreasing fills
+         * {5, 4} and {3, 3, 3} first, then needs a third
+         * vehicle, above the lower bound. */
+        let nodes_demand : Vec<usize> = vec![0, 5, 4, 3, 3, 3, 2];
+        assert_eq!(first_fit_decreasing(&nodes_demand, 10).len(), 3);
+        assert_eq!(vehicles_lower_bound(&nodes_demand, 10), 2);
+
+    }
+
+}
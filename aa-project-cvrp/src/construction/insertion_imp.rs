@@ -0,0 +1,319 @@
+use crate::construction::construction_instance_trait::ConstructionInstanceTrait;
+use crate::algorithm_interface::CVRPSolver;
+
+/* Criterion used to select the next
+ * customer to be inserted into the routes. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum InsertionCriterion
+{
+
+    /* Insert the customer with the cheapest
+     * feasible insertion. */
+    Cheapest,
+
+    /* Insert the customer farthest from
+     * the nodes already in the routes. */
+    Farthest,
+
+    /* Insert the customer with the largest
+     * regret, summed over its k best routes. */
+    Regret(usize),
+
+}
+
+/* Here the insertion heuristics are
+ * implemented. Customers are inserted one
+ * at a time, at the cheapest feasible
+ * position, into the existing routes or into
+ * a new route, the next customer being chosen
+ * according to the insertion criterion. */
+pub struct InsertionSolver<'a>
+{
+
+    pub instance  : &'a dyn ConstructionInstanceTrait,
+
+    pub criterion : InsertionCriterion,
+
+}
+
+/* Methods of InsertionSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> InsertionSolver<'a>
+{
+
+    pub fn add_instance(&'a mut self, i : &'a dyn ConstructionInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+}
+
+/* Best feasible insertion of a customer in each
+ * route, as (cost, route, position), sorted by
+ * increasing cost. The route routes.len()
+ * stands for a new route. */
+pub fn insertion_costs(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    routes          : &[Vec<usize>],
+    loads           : &[usize],
+    customer        : usize)
+    -> Vec<(f64, usize, usize)>
+{
+
+    let mut result : Vec<(f64, usize, usize)> = Vec::new();
+
+    for (r, route) in routes.iter().enumerate()
+    {
+
+        if loads[r] + nodes_demand[customer] > capacity
+        {
+            continue;
+        }
+
+        let mut best : (f64, usize, usize) = (f64::INFINITY, r, 0);
+
+        for position in 0..=route.len()
+        {
+
+            let previous : usize = if position == 0 { 0 } else { route[position - 1] };
+            let next     : usize = if position == route.len() { 0 } else { route[position] };
+            let cost     : f64   = distance_matrix[previous][customer]
+                + distance_matrix[customer][next]
+                - distance_matrix[previous][next];
+
+            if cost < best.0
+            {
+                best = (cost, r, position);
+            }
+
+        }
+
+        result.push(best);
+
+    }
+
+    if nodes_demand[customer] <= capacity
+    {
+        result.push((2.0 * distance_matrix[0][customer], routes.len(), 0));
+    }
+
+    result.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    return result;
+
+}
+
+/* Insert the customers into the (possibly
+ * partial) routes according to the criterion.
+ * Customers which can not be served by any
+ * vehicle are left out. */
+pub fn insert_customers(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    criterion       : InsertionCriterion,
+    routes          : Vec< Vec<usize>>,
+    customers       : Vec<usize>)
+    -> Vec< Vec<usize>>
+{
+
+    let mut result    : Vec< Vec<usize>> = routes;
+    let mut loads     : Vec<usize>       = result.iter()
+        .map(|r| r.iter().map(|&n| nodes_demand[n]).sum())
+        .collect();
+    let mut unrouted  : Vec<usize>       = customers;
+
+    /* Distance of each node from the nodes
+     * already routed, depot included, used
+     * by the farthest insertion. */
+    let mut routed_distance : Vec<f64> = distance_matrix[0].clone();
+    for route in &result
+    {
+        for &n in route
+        {
+            for m in 0..routed_distance.len()
+            {
+                routed_distance[m] = routed_distance[m].min(distance_matrix[n][m]);
+            }
+        }
+    }
+
+    while !unrouted.is_empty()
+    {
+
+        let mut selected : Option<(usize, (f64, usize, usize))> = None;
+        let mut score    : f64                                  = f64::NEG_INFINITY;
+
+        for (index, &customer) in unrouted.iter().enumerate()
+        {
+
+            let costs : Vec<(f64, usize, usize)> = insertion_costs(
+                distance_matrix, nodes_demand, capacity, &result, &loads, customer);
+
+            if costs.is_empty()
+            {
+                continue;
+            }
+
+            /* Every criterion is expressed as a
+             * score to be maximized. */
+            let customer_score : f64 = match criterion
+            {
+                InsertionCriterion::Cheapest => -costs[0].0,
+                InsertionCriterion::Farthest => routed_distance[customer],
+                InsertionCriterion::Regret(k) =>
+                    costs.iter()
+                        .take(k.max(2))
+                        .skip(1)
+                        .map(|c| c.0 - costs[0].0)
+                        .sum::<f64>(),
+            };
+
+            /* Ties are broken by the cheapest insertion. */
+            if customer_score > score ||
+                (customer_score == score && costs[0].0 < selected.map_or(f64::INFINITY, |s| s.1.0))
+            {
+                selected = Some((index, costs[0]));
+                score    = customer_score;
+            }
+
+        }
+
+        let (index, (_, route, position)) = match selected
+        {
+            Some(s) => s,
+            _ => break,
+        };
+
+        let customer : usize = unrouted.swap_remove(index);
+
+        if route == result.len()
+        {
+            result.push(Vec::new());
+            loads.push(0);
+        }
+
+        result[route].insert(position, customer);
+        loads[route] += nodes_demand[customer];
+
+        for m in 0..routed_distance.len()
+        {
+            routed_distance[m] = routed_distance[m].min(distance_matrix[customer][m]);
+        }
+
+    }
+
+    return result;
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for InsertionSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        let instance        : &dyn ConstructionInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>                 = instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>                     = instance.get_nodes_demand();
+
+        insert_customers(
+            &distance_matrix,
+            &nodes_demand,
+            instance.get_capacity(),
+            self.criterion,
+            Vec::new(),
+            (1..nodes_demand.len()).collect())
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+
+    #[test]
+    fn every_criterion_serves_every_customer()
+    {
+
+        for name in ["F-n45-k4", "gr-n24-k4", "bays-n29-k5"]
+        {
+
+            let graph : GraphInstance = read_test_instance(name);
+
+            for criterion in [
+                InsertionCriterion::Cheapest,
+                InsertionCriterion::Farthest,
+                InsertionCriterion::Regret(2),
+                InsertionCriterion::Regret(3),
+            ]
+            {
+                let solver : InsertionSolver = InsertionSolver { instance : &graph, criterion };
+                assert_feasible(&graph, &solver.solve());
+            }
+
+        }
+
+    }
+
+    #[test]
+    fn insertion_costs_are_sorted_and_feasible()
+    {
+
+        /* Customers 1 and 2 on the first route, full,
+         * customer 3 on the second one. */
+        let graph           : GraphInstance    = read_test_instance("gr-n17-k3");
+        let distance_matrix : Vec< Vec<f64>>   = graph.get_distance_matrix();
+        let nodes_demand    : Vec<usize>       = vec![0, 2, 2, 1, 1];
+        let routes          : Vec< Vec<usize>> = vec![vec![1, 2], vec![3]];
+        let loads           : Vec<usize>       = vec![4, 1];
+
+        let costs : Vec<(f64, usize, usize)> =
+            insertion_costs(&distance_matrix, &nodes_demand, 4, &routes, &loads, 4);
+
+        assert_eq!(costs.len(), 2);
+        assert!(costs[0].0 <= costs[1].0);
+        assert!(costs.iter().all(|&(_, route, _)| route != 0));
+        assert!(costs.iter().any(|&(cost, route, _)| route == 2 && cost == 2.0 * distance_matrix[0][4]));
+
+    }
+
+    #[test]
+    fn regret_inserts_first_the_customer_with_fewer_options()
+    {
+
+        /* The route [1] has room for one more customer.
+         * Customer 3 is the cheapest to insert into it,
+         * at 0.5 instead of 2 for a new route, while
+         * customer 2 would cost 1 instead of 10. */
+        let distance_matrix : Vec< Vec<f64>> = vec![
+            vec![0.0, 5.0, 5.0, 1.0],
+            vec![5.0, 0.0, 1.0, 4.5],
+            vec![5.0, 1.0, 0.0, 5.0],
+            vec![1.0, 4.5, 5.0, 0.0],
+        ];
+        let nodes_demand    : Vec<usize>     = vec![0, 1, 1, 1];
+
+        let insert = |criterion : InsertionCriterion| -> Vec< Vec<usize>>
+        {
+            insert_customers(&distance_matrix, &nodes_demand, 2, criterion, vec![vec![1]], vec![2, 3])
+        };
+
+        assert_eq!(insert(InsertionCriterion::Cheapest)[1], vec![2]);
+        assert_eq!(insert(InsertionCriterion::Regret(2))[1], vec![3]);
+        assert_eq!(insert(InsertionCriterion::Regret(3))[1], vec![3]);
+
+    }
+
+}
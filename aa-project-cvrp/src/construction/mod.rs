@@ -0,0 +1,3 @@
+pub mod construction_instance_trait;
+pub mod nearest_neighbour_imp;
+pub mod insertion_imp;
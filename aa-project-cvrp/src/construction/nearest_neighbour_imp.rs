@@ -0,0 +1,118 @@
+use crate::construction::construction_instance_trait::ConstructionInstanceTrait;
+use crate::algorithm_interface::CVRPSolver;
+
+/* Here the nearest neighbour heuristic is
+ * implemented. Each route is extended with
+ * the nearest customer which fits into the
+ * remaining capacity, and a new route is
+ * opened when no customer fits. */
+pub struct NearestNeighbourSolver<'a>
+{
+
+    pub instance : &'a dyn ConstructionInstanceTrait,
+
+}
+
+/* Methods of NearestNeighbourSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> NearestNeighbourSolver<'a>
+{
+
+    pub fn add_instance(&'a mut self, i : &'a dyn ConstructionInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for NearestNeighbourSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        let instance        : &dyn ConstructionInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>                 = instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>                     = instance.get_nodes_demand();
+        let capacity        : usize                          = instance.get_capacity();
+        let node_number     : usize                          = nodes_demand.len();
+
+        let mut visited     : Vec<bool>                      = vec![false; node_number];
+        let mut remaining   : usize                          = node_number - 1;
+        let mut routes      : Vec< Vec<usize>>               = Vec::new();
+
+        while remaining > 0
+        {
+
+            let mut route   : Vec<usize> = Vec::new();
+            let mut load    : usize      = 0;
+            let mut current : usize      = 0;
+
+            loop
+            {
+
+                let next : Option<usize> = (1..node_number)
+                    .filter(|&n| !visited[n] && load + nodes_demand[n] <= capacity)
+                    .min_by(|&a, &b|
+                        distance_matrix[current][a].partial_cmp(&distance_matrix[current][b]).unwrap());
+
+                match next
+                {
+                    Some(n) =>
+                        {
+                            route.push(n);
+                            visited[n]  = true;
+                            load       += nodes_demand[n];
+                            remaining  -= 1;
+                            current     = n;
+                        }
+                    _ => break,
+                }
+
+            }
+
+            /* A customer whose demand exceeds the
+             * capacity can not be served. */
+            if route.is_empty()
+            {
+                break;
+            }
+
+            routes.push(route);
+
+        }
+
+        return routes;
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+
+    #[test]
+    fn serves_every_customer()
+    {
+
+        for name in ["F-n45-k4", "gr-n24-k4", "bays-n29-k5"]
+        {
+
+            let graph  : GraphInstance          = read_test_instance(name);
+            let solver : NearestNeighbourSolver = NearestNeighbourSolver { instance : &graph };
+
+            assert_feasible(&graph, &solver.solve());
+
+        }
+
+    }
+
+}
@@ -0,0 +1,460 @@
+use std::collections::HashMap;
+use crate::exact_algorithm::exact_instance_trait::ExactInstanceTrait;
+use crate::route_pool::set_partitioning::{solve_set_partitioning, SetPartitioningResult};
+use crate::algorithm_interface::{CVRPSolver, SolveControl, compute_routes_cost};
+
+/* Here an exact algorithm for small instances is
+ * implemented. The cost of every route within the
+ * capacity is computed by a Held-Karp dynamic
+ * programming over the subsets of customers, then
+ * the optimal partition of the customers into
+ * routes is found by the branch and bound of the
+ * set partitioning over all of them. The solution
+ * of the fallback solver bounds the search, and is
+ * returned when the instance is too large or the
+ * search is stopped before finding a better one. */
+pub struct ExactSolver<'a>
+{
+
+    pub instance        : &'a dyn ExactInstanceTrait,
+
+    pub fallback_solver : &'a dyn CVRPSolver,
+
+}
+
+/* The subsets of customers are represented as bit
+ * masks, where the customer i is the bit i - 1. */
+const MAX_CUSTOMERS : usize = 32;
+
+/* Bound on the number of routes within the
+ * capacity, above which the instance is
+ * considered too large. */
+const MAX_ROUTES : usize = 2_000_000;
+
+/* Number of subsets enumerated between
+ * two checks of the control. */
+const CONTROL_INTERVAL : usize = 1024;
+
+/* Methods of ExactSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> ExactSolver<'a>
+{
+
+    pub fn new(instance : &'a dyn ExactInstanceTrait, fallback_solver : &'a dyn CVRPSolver) -> ExactSolver<'a>
+    {
+
+        ExactSolver {
+            instance,
+            fallback_solver,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn ExactInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    /* Optimal routes, None if the instance
+     * is too large to be solved. */
+    pub fn optimal_routes(&self) -> Option<Vec< Vec<usize>>>
+    {
+
+        return match self.search_routes(f64::INFINITY, &SolveControl::unlimited())
+        {
+            Some((routes, true)) => routes,
+            _                    => None,
+        };
+
+    }
+
+    /* Cheapest routes costing less than the upper
+     * bound, found until the control is stopped, and
+     * whether the search was completed. None if the
+     * instance is too large or the search is stopped
+     * while enumerating the routes. */
+    fn search_routes(&self, upper_bound : f64, control : &SolveControl) -> Option<(Option<Vec< Vec<usize>>>, bool)>
+    {
+
+        let instance        : &dyn ExactInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>          = instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>              = instance.get_nodes_demand();
+        let capacity        : usize                   = instance.get_capacity();
+        let customers       : usize                   = nodes_demand.len() - 1;
+
+        if customers > MAX_CUSTOMERS || nodes_demand.iter().any(|&q| q > capacity)
+        {
+            return None;
+        }
+        if customers == 0
+        {
+            return Some((Some(Vec::new()), true));
+        }
+
+        let (masks, costs) : (Vec<u32>, Vec<f64>) = route_costs(&distance_matrix, &nodes_demand, capacity, control)?;
+
+        /* Every route is a column of the set partitioning. */
+        let routes    : Vec<(f64, Vec<usize>)> = masks.iter()
+            .zip(costs.iter())
+            .map(|(&mask, &cost)| (cost, members(mask).map(|i| i + 1).collect()))
+            .collect();
+        let partition : SetPartitioningResult   = solve_set_partitioning(
+            customers + 1, &routes, upper_bound, usize::MAX, control);
+
+        let result : Option<Vec< Vec<usize>>> = partition.best.map(|(_, selected)| {
+            selected.iter()
+                .map(|&r| optimal_route(&distance_matrix, &routes[r].1))
+                .collect()
+        });
+
+        return Some((result, partition.completed));
+
+    }
+
+}
+
+/* Customers in the subset, as bit positions. */
+fn members(mask : u32) -> impl Iterator<Item = usize>
+{
+
+    (0..MAX_CUSTOMERS).filter(move |&i| mask & (1u32 << i) != 0)
+
+}
+
+/* Enumerate the subsets of customers within the
+ * capacity, and compute the cost of the optimal
+ * route serving each of them. None if they are
+ * more than MAX_ROUTES, or if the control is
+ * stopped meanwhile. */
+fn route_costs(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    control         : &SolveControl)
+    -> Option<(Vec<u32>, Vec<f64>)>
+{
+
+    let customers : usize = nodes_demand.len() - 1;
+
+    /* Depth-first enumeration, adding the
+     * customers in increasing order. */
+    let mut masks : Vec<u32>                 = Vec::new();
+    let mut stack : Vec<(u32, usize, usize)> = (0..customers)
+        .map(|i| (1u32 << i, i, nodes_demand[i + 1]))
+        .collect();
+    while let Some((mask, last, load)) = stack.pop()
+    {
+        masks.push(mask);
+        if masks.len() > MAX_ROUTES ||
+            (masks.len().is_multiple_of(CONTROL_INTERVAL) && control.should_stop())
+        {
+            return None;
+        }
+        for i in last + 1..customers
+        {
+            if load + nodes_demand[i + 1] <= capacity
+            {
+                stack.push((mask | 1u32 << i, i, load + nodes_demand[i + 1]));
+            }
+        }
+    }
+
+    /* Every subset follows the subsets it contains. */
+    masks.sort_by_key(|mask| (mask.count_ones(), *mask));
+
+    let index : HashMap<u32, usize> = masks.iter()
+        .enumerate()
+        .map(|(s, &mask)| (mask, s))
+        .collect();
+
+    /* Cost of the shortest path from the depot through
+     * the subset, ending at each of its customers, stored
+     * in order of the customers from offsets[s]. */
+    let mut offsets : Vec<usize> = Vec::with_capacity(masks.len());
+    let mut paths   : Vec<f64>   = Vec::new();
+    let mut costs   : Vec<f64>   = Vec::with_capacity(masks.len());
+
+    for (s, &mask) in masks.iter().enumerate()
+    {
+
+        if s.is_multiple_of(CONTROL_INTERVAL) && control.should_stop()
+        {
+            return None;
+        }
+
+        offsets.push(paths.len());
+
+        let mut cost : f64 = f64::INFINITY;
+
+        for j in members(mask)
+        {
+
+            let previous : u32 = mask & !(1u32 << j);
+            let path     : f64 = if previous == 0
+            {
+                distance_matrix[0][j + 1]
+            }
+            else
+            {
+                let offset : usize = offsets[index[&previous]];
+                members(previous)
+                    .enumerate()
+                    .map(|(rank, k)| paths[offset + rank] + distance_matrix[k + 1][j + 1])
+                    .fold(f64::INFINITY, f64::min)
+            };
+
+            paths.push(path);
+            cost = cost.min(path + distance_matrix[j + 1][0]);
+
+        }
+
+        costs.push(cost);
+
+    }
+
+    return Some((masks, costs));
+
+}
+
+/* Optimal order of the customers of a
+ * route, by Held-Karp dynamic programming. */
+fn optimal_route(distance_matrix : &[Vec<f64>], customers : &[usize]) -> Vec<usize>
+{
+
+    let size        : usize            = customers.len();
+    let mut paths   : Vec< Vec<f64>>   = vec![vec![f64::INFINITY; size]; 1 << size];
+    let mut parents : Vec< Vec<usize>> = vec![vec![usize::MAX; size]; 1 << size];
+
+    for j in 0..size
+    {
+        paths[1 << j][j] = distance_matrix[0][customers[j]];
+    }
+
+    for mask in 1..1usize << size
+    {
+        for j in (0..size).filter(|&j| mask & 1 << j != 0)
+        {
+            let previous : usize = mask & !(1 << j);
+            for k in (0..size).filter(|&k| previous & 1 << k != 0)
+            {
+                let path : f64 = paths[previous][k] + distance_matrix[customers[k]][customers[j]];
+                if path < paths[mask][j]
+                {
+                    paths[mask][j]   = path;
+                    parents[mask][j] = k;
+                }
+            }
+        }
+    }
+
+    /* Follow the parents back from the
+     * best last customer. */
+    let full     : usize = (1 << size) - 1;
+    let mut last : usize = (0..size)
+        .min_by(|&a, &b| {
+            (paths[full][a] + distance_matrix[customers[a]][0])
+                .partial_cmp(&(paths[full][b] + distance_matrix[customers[b]][0]))
+                .unwrap()
+        })
+        .unwrap();
+
+    let mut result : Vec<usize> = Vec::with_capacity(size);
+    let mut mask   : usize      = full;
+    while last != usize::MAX
+    {
+        result.push(customers[last]);
+        let previous : usize = parents[mask][last];
+        mask &= !(1 << last);
+        last  = previous;
+    }
+
+    result.reverse();
+
+    return result;
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for ExactSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    /* Only the partitions cheaper than the solution
+     * of the fallback solver are searched, so that
+     * it is returned when none is found in time. */
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        if control.should_stop()
+        {
+            return Vec::new();
+        }
+
+        let fallback : Vec< Vec<usize>> = self.fallback_solver.solve_with_control(control);
+        if fallback.is_empty()
+        {
+            return fallback;
+        }
+
+        let distance_matrix : Vec< Vec<f64>> = self.instance.get_distance_matrix();
+        let upper_bound     : f64            = compute_routes_cost(&distance_matrix, &fallback);
+
+        let result : Vec< Vec<usize>> = match self.search_routes(upper_bound, control)
+        {
+            Some((Some(routes), _)) => routes,
+            _                       => fallback,
+        };
+        control.report(&result);
+
+        return result;
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use std::time::{Duration, Instant};
+    use crate::model::{GraphInstance, read_test_instance};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+
+    /* Instance given by its distance matrix. */
+    struct MatrixInstance
+    {
+
+        distance_matrix : Vec< Vec<f64>>,
+
+        capacity        : usize,
+
+        nodes_demand    : Vec<usize>,
+
+    }
+
+    impl ExactInstanceTrait for MatrixInstance
+    {
+
+        fn get_distance_matrix(&self) -> Vec< Vec<f64>>
+        {
+            return self.distance_matrix.clone();
+        }
+
+        fn get_capacity(&self) -> usize
+        {
+            return self.capacity;
+        }
+
+        fn get_nodes_demand(&self) -> Vec<usize>
+        {
+            return self.nodes_demand.clone();
+        }
+
+    }
+
+    /* Fallback serving every customer alone. */
+    struct SingleCustomerRoutes
+    {
+
+        customers : usize,
+
+    }
+
+    impl CVRPSolver for SingleCustomerRoutes
+    {
+
+        fn solve(&self) -> Vec< Vec<usize>>
+        {
+            return (1..=self.customers).map(|i| vec![i]).collect();
+        }
+
+    }
+
+    /* Every customer is served exactly once,
+     * within the capacity. */
+    fn assert_feasible(instance : &dyn ExactInstanceTrait, routes : &[Vec<usize>])
+    {
+
+        let nodes_demand : Vec<usize> = instance.get_nodes_demand();
+
+        let mut served : Vec<usize> = routes.concat();
+        served.sort();
+        assert_eq!(served, (1..nodes_demand.len()).collect::<Vec<usize>>());
+        assert!(routes.iter().all(|r| r.iter().map(|&i| nodes_demand[i]).sum::<usize>() <= instance.get_capacity()));
+
+    }
+
+    #[test]
+    fn optimum_of_customers_on_a_line()
+    {
+
+        /* Depot at 0 and five customers at -2, -1, 1,
+         * 2 and 3, two per vehicle: the optimum serves
+         * {-2, -1}, {1} and {2, 3}, at cost 4 + 2 + 6. */
+        let positions : Vec<f64>  = vec![0.0, -2.0, -1.0, 1.0, 2.0, 3.0];
+        let instance  : MatrixInstance = MatrixInstance {
+            distance_matrix : positions.iter()
+                .map(|a| positions.iter().map(|b| (a - b).abs()).collect())
+                .collect(),
+            capacity        : 2,
+            nodes_demand    : vec![0, 1, 1, 1, 1, 1],
+        };
+
+        let fallback : SingleCustomerRoutes = SingleCustomerRoutes { customers : 5 };
+        let solver   : ExactSolver          = ExactSolver::new(&instance, &fallback);
+
+        for routes in [solver.optimal_routes().unwrap(), solver.solve()]
+        {
+            assert_feasible(&instance, &routes);
+            assert_eq!(compute_routes_cost(&instance.distance_matrix, &routes), 12.0);
+        }
+
+    }
+
+    #[test]
+    fn optima_of_small_instances()
+    {
+
+        for (name, optimum) in [("gr-n17-k3", 2685.0), ("gr-n21-k3", 3704.0), ("gr-n24-k4", 2053.0)]
+        {
+
+            let graph   : GraphInstance = read_test_instance(name);
+            let savings : SavingsSolver = SavingsSolver::new(&graph);
+            let solver  : ExactSolver   = ExactSolver::new(&graph, &savings);
+
+            let routes  : Vec< Vec<usize>> = solver.optimal_routes().unwrap();
+            assert_feasible(&graph, &routes);
+            assert_eq!(compute_routes_cost(&ExactInstanceTrait::get_distance_matrix(&graph), &routes), optimum);
+
+        }
+
+    }
+
+    #[test]
+    fn deadline_returns_the_fallback_promptly()
+    {
+
+        let graph   : GraphInstance = read_test_instance("gr-n24-k4");
+        let savings : SavingsSolver = SavingsSolver::new(&graph);
+        let solver  : ExactSolver   = ExactSolver::new(&graph, &savings);
+
+        let start   : Instant      = Instant::now();
+        let control : SolveControl = SolveControl::new(None, Some(start + Duration::from_millis(100)), None, None);
+        let routes  : Vec< Vec<usize>> = solver.solve_with_control(&control);
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_feasible(&graph, &routes);
+
+    }
+
+}
@@ -0,0 +1,2 @@
+pub mod exact_instance_trait;
+pub mod exact_algorithm_imp;
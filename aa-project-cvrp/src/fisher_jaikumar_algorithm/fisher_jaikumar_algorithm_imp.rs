@@ -0,0 +1,190 @@
+use crate::fisher_jaikumar_algorithm::fisher_jaikumar_instance_trait::FisherJaikumarInstanceTrait;
+use crate::fisher_jaikumar_algorithm::generalized_assignment::solve_generalized_assignment;
+use crate::local_search::intra_route_imp::optimize_route_with;
+use crate::local_search::moves::Neighbourhood;
+use crate::algorithm_interface::{CVRPSolver, SolveControl};
+
+/* Here the cluster-first route-second algorithm
+ * of Fisher and Jaikumar is implemented. K seed
+ * customers are selected, one per vehicle, the
+ * customers are assigned to the seeds solving a
+ * generalized assignment problem, and each cluster
+ * is then sequenced as a TSP tour through the depot. */
+pub struct FisherJaikumarSolver<'a>
+{
+
+    pub instance : &'a dyn FisherJaikumarInstanceTrait,
+
+}
+
+/* Methods of FisherJaikumarSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> FisherJaikumarSolver<'a>
+{
+
+    pub fn add_instance(&'a mut self, i : &'a dyn FisherJaikumarInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    /* Select the seeds one at a time, each time taking
+     * the customer farthest from the depot and from
+     * the seeds already selected. */
+    fn select_seeds(
+        &self,
+        distance_matrix : &[Vec<f64>],
+        seeds_number    : usize)
+        -> Vec<usize>
+    {
+
+        let node_number  : usize      = distance_matrix.len();
+        let mut distance : Vec<f64>   = distance_matrix[0].clone();
+        let mut seeds    : Vec<usize> = Vec::with_capacity(seeds_number);
+
+        while seeds.len() < seeds_number
+        {
+
+            let seed : usize = (1..node_number)
+                .filter(|n| !seeds.contains(n))
+                .max_by(|&a, &b| distance[a].partial_cmp(&distance[b]).unwrap())
+                .unwrap();
+
+            for n in 0..node_number
+            {
+                distance[n] = distance[n].min(distance_matrix[seed][n]);
+            }
+
+            seeds.push(seed);
+
+        }
+
+        return seeds;
+
+    }
+
+    /* Sequence the customers of a cluster with the
+     * nearest neighbour heuristic, starting from the
+     * depot, then improve the route with 2-opt and
+     * Or-opt until the search is stopped. */
+    fn sequence_cluster(
+        &self,
+        distance_matrix : &[Vec<f64>],
+        cluster         : Vec<usize>,
+        control         : &SolveControl)
+        -> Vec<usize>
+    {
+
+        let mut unvisited : Vec<usize> = cluster;
+        let mut route     : Vec<usize> = Vec::with_capacity(unvisited.len());
+        let mut current   : usize      = 0;
+
+        while !unvisited.is_empty()
+        {
+
+            let index : usize = (0..unvisited.len())
+                .min_by(|&a, &b|
+                    distance_matrix[current][unvisited[a]].partial_cmp(&distance_matrix[current][unvisited[b]]).unwrap())
+                .unwrap();
+
+            current = unvisited.swap_remove(index);
+            route.push(current);
+
+        }
+
+        optimize_route_with(distance_matrix, &mut route, &[Neighbourhood::TwoOpt, Neighbourhood::OrOpt], control);
+
+        return route;
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for FisherJaikumarSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    /* Once the search is stopped no other number of
+     * vehicles is tried, and the clusters are not
+     * improved further. */
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        if control.should_stop()
+        {
+            return Vec::new();
+        }
+
+        let instance        : &dyn FisherJaikumarInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>                   = instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>                       = instance.get_nodes_demand();
+        let capacity        : usize                            = instance.get_capacity();
+        let node_number     : usize                            = nodes_demand.len();
+
+        /* The number of vehicles is taken from the instance,
+         * or from the total demand if it is not given. */
+        let total_demand  : usize = nodes_demand.iter().sum();
+        let mut vehicles  : usize = instance.get_vehicles_number()
+            .unwrap_or(total_demand.div_ceil(capacity))
+            .max(1);
+
+        let customers : Vec<usize> = (1..node_number).collect();
+        let weights   : Vec<usize> = customers.iter().map(|&c| nodes_demand[c]).collect();
+
+        /* When no feasible assignment is found, which may
+         * happen since the assignment is heuristic, one
+         * more vehicle is used. */
+        while vehicles < node_number && !control.should_stop()
+        {
+
+            let seeds : Vec<usize> = self.select_seeds(&distance_matrix, vehicles);
+
+            /* Cost of inserting each customer in the
+             * route from the depot to the seed. */
+            let costs : Vec< Vec<f64>> = customers.iter()
+                .map(|&c| seeds.iter()
+                    .map(|&s| distance_matrix[0][c] + distance_matrix[c][s] - distance_matrix[0][s])
+                    .collect())
+                .collect();
+
+            if let Some(assignment) = solve_generalized_assignment(&costs, &weights, &vec![capacity; vehicles])
+            {
+
+                let mut clusters : Vec< Vec<usize>> = vec![Vec::new(); vehicles];
+                for (i, &k) in assignment.iter().enumerate()
+                {
+                    clusters[k].push(customers[i]);
+                }
+
+                let routes : Vec< Vec<usize>> = clusters.into_iter()
+                    .filter(|c| !c.is_empty())
+                    .map(|c| self.sequence_cluster(&distance_matrix, c, control))
+                    .collect();
+                control.report(&routes);
+
+                return routes;
+
+            }
+
+            vehicles += 1;
+
+        }
+
+        /* One route for each customer. */
+        let routes : Vec< Vec<usize>> = customers.into_iter().map(|c| vec![c]).collect();
+        control.report(&routes);
+
+        return routes;
+
+    }
+
+}
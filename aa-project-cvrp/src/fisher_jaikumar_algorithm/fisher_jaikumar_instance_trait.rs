@@ -0,0 +1,18 @@
+/* This trait define the methods required
+ * by the Fisher-Jaikumar algorithm, available
+ * only for GraphInstance when explicitly
+ * implemented. */
+pub trait FisherJaikumarInstanceTrait : Sync
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>>;
+
+    fn get_capacity(&self) -> usize;
+
+    fn get_nodes_demand(&self) -> Vec<usize>;
+
+    /* Number of vehicles given by the instance,
+     * None if it is not specified. */
+    fn get_vehicles_number(&self) -> Option<usize>;
+
+}
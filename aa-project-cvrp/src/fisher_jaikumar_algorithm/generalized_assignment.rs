@@ -0,0 +1,216 @@
+/* Here the heuristic of Martello and Toth for
+ * the generalized assignment problem is
+ * implemented. Each item is assigned to exactly
+ * one agent, minimizing the total cost, without
+ * exceeding the capacity of the agents. */
+
+/* Desirability measures used by the heuristic,
+ * the lower the better. */
+#[derive(Clone, Copy)]
+enum Desirability
+{
+
+    /* Cost of the assignment. */
+    Cost,
+
+    /* Cost of the assignment per unit of weight. */
+    CostPerWeight,
+
+    /* Fraction of the residual capacity used. */
+    ResidualCapacity,
+
+}
+
+/* Assign the items with the largest regret first,
+ * where the regret of an item is the difference
+ * between its two most desirable feasible agents.
+ * None is returned when an item can not fit into
+ * any agent. */
+fn regret_assignment(
+    costs       : &[Vec<f64>],
+    weights     : &[usize],
+    capacities  : &[usize],
+    measure     : Desirability)
+    -> Option< Vec<usize>>
+{
+
+    let item_number  : usize              = costs.len();
+    let agent_number : usize              = capacities.len();
+    let mut residual : Vec<usize>         = capacities.to_vec();
+    let mut result   : Vec<Option<usize>> = vec![None; item_number];
+
+    for _ in 0..item_number
+    {
+
+        /* Item with the largest regret, and its best agent. */
+        let mut selected : Option<(usize, usize)> = None;
+        let mut regret   : f64                    = f64::NEG_INFINITY;
+
+        for i in (0..item_number).filter(|&i| result[i].is_none())
+        {
+
+            let mut first  : Option<(f64, usize)> = None;
+            let mut second : f64                  = f64::INFINITY;
+
+            for j in (0..agent_number).filter(|&j| weights[i] <= residual[j])
+            {
+
+                let value : f64 = match measure
+                {
+                    Desirability::Cost             => costs[i][j],
+                    Desirability::CostPerWeight    => costs[i][j] / weights[i].max(1) as f64,
+                    /* An item without weight uses no capacity,
+                     * even of an agent with none left. */
+                    Desirability::ResidualCapacity if weights[i] == 0 => 0.0,
+                    Desirability::ResidualCapacity => weights[i] as f64 / residual[j] as f64,
+                };
+
+                match first
+                {
+                    Some((best, _)) if value >= best => second = second.min(value),
+                    Some((best, _)) =>
+                        {
+                            second = best;
+                            first  = Some((value, j));
+                        }
+                    _ => first = Some((value, j)),
+                }
+
+            }
+
+            /* An item with a single feasible agent has
+             * an infinite regret. */
+            match first
+            {
+                Some((best, j)) if second - best > regret =>
+                    {
+                        selected = Some((i, j));
+                        regret   = second - best;
+                    }
+                Some(_) => (),
+                _ => return None,
+            }
+
+        }
+
+        let (i, j) = selected?;
+        result[i]    = Some(j);
+        residual[j] -= weights[i];
+
+    }
+
+    return result.into_iter().collect();
+
+}
+
+/* Improve a feasible assignment moving single items
+ * to a cheaper agent with enough residual capacity,
+ * until no such move exists. */
+fn shift_improvement(
+    costs      : &[Vec<f64>],
+    weights    : &[usize],
+    capacities : &[usize],
+    assignment : &mut [usize])
+{
+
+    let mut residual : Vec<usize> = capacities.to_vec();
+    for (i, &j) in assignment.iter().enumerate()
+    {
+        residual[j] -= weights[i];
+    }
+
+    let mut improved : bool = true;
+    while improved
+    {
+
+        improved = false;
+
+        for i in 0..assignment.len()
+        {
+
+            let current : usize         = assignment[i];
+            let best    : Option<usize> = (0..capacities.len())
+                .filter(|&j| j != current && weights[i] <= residual[j])
+                .filter(|&j| costs[i][j] < costs[i][current] - 1e-9)
+                .min_by(|&a, &b| costs[i][a].partial_cmp(&costs[i][b]).unwrap());
+
+            if let Some(j) = best
+            {
+                residual[current] += weights[i];
+                residual[j]       -= weights[i];
+                assignment[i]      = j;
+                improved           = true;
+            }
+
+        }
+
+    }
+
+}
+
+/* Solve the generalized assignment problem, where
+ * costs[i][j] is the cost of assigning the item i
+ * to the agent j. Every desirability measure is
+ * tried and the cheapest assignment is returned,
+ * or None if no feasible assignment is found. */
+pub fn solve_generalized_assignment(
+    costs      : &[Vec<f64>],
+    weights    : &[usize],
+    capacities : &[usize])
+    -> Option< Vec<usize>>
+{
+
+    let mut result : Option<(f64, Vec<usize>)> = None;
+
+    for measure in [Desirability::Cost, Desirability::CostPerWeight, Desirability::ResidualCapacity]
+    {
+
+        if let Some(mut assignment) = regret_assignment(costs, weights, capacities, measure)
+        {
+
+            shift_improvement(costs, weights, capacities, &mut assignment);
+
+            let cost : f64 = assignment.iter()
+                .enumerate()
+                .map(|(i, &j)| costs[i][j])
+                .sum();
+
+            if result.as_ref().is_none_or(|(best, _)| cost < *best)
+            {
+                result = Some((cost, assignment));
+            }
+
+        }
+
+    }
+
+    return result.map(|(_, assignment)| assignment);
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+
+    #[test]
+    fn residual_capacity_with_full_agent()
+    {
+
+        /* The first item fills the first agent, which
+         * is still feasible for the item without weight. */
+        let costs      : Vec< Vec<f64>> = vec![vec![1.0, 1.0], vec![1.0, 2.0]];
+        let weights    : Vec<usize>     = vec![2, 0];
+        let capacities : Vec<usize>     = vec![2, 1];
+
+        let assignment : Option< Vec<usize>> =
+            regret_assignment(&costs, &weights, &capacities, Desirability::ResidualCapacity);
+        assert_eq!(assignment.map(|a| a[0]), Some(0));
+
+        let assignment : Option< Vec<usize>> = solve_generalized_assignment(&costs, &weights, &capacities);
+        assert_eq!(assignment, Some(vec![0, 0]));
+
+    }
+
+}
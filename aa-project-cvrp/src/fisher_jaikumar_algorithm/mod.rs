@@ -0,0 +1,3 @@
+pub mod fisher_jaikumar_instance_trait;
+pub mod fisher_jaikumar_algorithm_imp;
+pub mod generalized_assignment;
@@ -0,0 +1,92 @@
+use std::sync::Mutex;
+use crate::hybrid_genetic_search::population::Individual;
+
+/* The islands of the island model, where many
+ * populations evolve in parallel, one per thread.
+ * Periodically each island sends copies of its best
+ * individuals to the next island of a ring, which
+ * adds them to its own population. */
+pub struct Archipelago
+{
+
+    /* Number of iterations between two migrations. */
+    pub migration_interval : usize,
+
+    /* Number of individuals sent at each migration. */
+    pub migrants           : usize,
+
+    /* Individuals arrived at each island, not
+     * yet added to its population. */
+    arrivals               : Vec< Mutex< Vec<Individual>>>,
+
+}
+
+/* Methods of Archipelago. */
+#[allow(dead_code)]
+impl Archipelago
+{
+
+    pub fn new(islands : usize, migration_interval : usize, migrants : usize) -> Archipelago
+    {
+
+        Archipelago {
+            migration_interval,
+            migrants,
+            arrivals : (0..islands).map(|_| Mutex::new(Vec::new())).collect(),
+        }
+
+    }
+
+    pub fn islands(&self) -> usize
+    {
+
+        self.arrivals.len()
+
+    }
+
+    /* Send the individuals from the
+     * island to the next one. */
+    pub fn emigrate(&self, island : usize, individuals : Vec<Individual>)
+    {
+
+        let next : usize = (island + 1) % self.arrivals.len();
+        self.arrivals[next].lock().unwrap().extend(individuals);
+
+    }
+
+    /* Take the individuals arrived at the island. */
+    pub fn immigrate(&self, island : usize) -> Vec<Individual>
+    {
+
+        std::mem::take(&mut *self.arrivals[island].lock().unwrap())
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+
+    #[test]
+    fn migrants_travel_along_the_ring()
+    {
+
+        let archipelago : Archipelago = Archipelago::new(3, 10, 1);
+        let migrant     : Individual  = Individual::new(vec![vec![1, 2]], 5.0, 0, 3);
+
+        archipelago.emigrate(2, vec![migrant.clone()]);
+        archipelago.emigrate(0, vec![migrant]);
+
+        assert_eq!(archipelago.immigrate(0).len(), 1);
+        assert_eq!(archipelago.immigrate(1).len(), 1);
+        assert!(archipelago.immigrate(2).is_empty());
+
+        /* The individuals are taken only once. */
+        assert!(archipelago.immigrate(0).is_empty());
+
+    }
+
+}
@@ -0,0 +1,671 @@
+use std::time::{Duration, Instant};
+use rand::{Rng, RngCore, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use crate::hybrid_genetic_search::hgs_instance_trait::HgsInstanceTrait;
+use crate::hybrid_genetic_search::population::{Individual, Population};
+use crate::hybrid_genetic_search::archipelago::Archipelago;
+use crate::route_first_algorithm::split::{split, split_penalized};
+use crate::local_search::moves::{Move, Neighbourhood, EPSILON, nearest_neighbours, customer_positions};
+use crate::local_search::intra_route_imp::optimize_route_with;
+use crate::algorithm_interface::{CVRPSolver, StoppingCriterion, compute_routes_cost, SolveControl};
+
+/* Here the hybrid genetic search of Vidal is
+ * implemented. Individuals are giant tours, decoded
+ * into routes by Split and educated by a granular
+ * local search. Offspring are generated by ordered
+ * crossover of parents chosen by biased fitness,
+ * which rewards both cost and diversity. */
+pub struct HgsSolver<'a>
+{
+
+    pub instance           : &'a dyn HgsInstanceTrait,
+
+    pub stopping_criterion : StoppingCriterion,
+
+    /* Parameters of the population. */
+    pub population_size    : usize,
+
+    pub generation_size    : usize,
+
+    pub elite_individuals  : usize,
+
+    pub close_individuals  : usize,
+
+    /* Number of nearest customers considered
+     * by the moves of the education. */
+    pub granularity        : usize,
+
+    /* The population is restarted after this number
+     * of iterations without improvement. */
+    pub restart_iterations : usize,
+
+    pub seed               : u64,
+
+}
+
+/* Methods of HgsSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> HgsSolver<'a>
+{
+
+    /* Hybrid genetic search with the parameters
+     * suggested by Vidal, running for the given
+     * amount of time. */
+    pub fn new(
+        instance   : &'a dyn HgsInstanceTrait,
+        time_limit : Duration,
+        seed       : u64)
+        -> HgsSolver<'a>
+    {
+
+        HgsSolver {
+            instance,
+            stopping_criterion : StoppingCriterion::Time(time_limit),
+            population_size    : 25,
+            generation_size    : 40,
+            elite_individuals  : 4,
+            close_individuals  : 5,
+            granularity        : 20,
+            restart_iterations : 20000,
+            seed,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn HgsInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    pub fn set_seed(&mut self, seed : u64)
+    {
+
+        self.seed = seed;
+
+    }
+
+    /* Run on the given island of the archipelago, with
+     * the other islands evolving in parallel. */
+    pub fn solve_on_island(
+        &self,
+        control     : &SolveControl,
+        archipelago : &Archipelago,
+        island      : usize)
+        -> Vec< Vec<usize>>
+    {
+
+        self.evolve(control, Some((archipelago, island)))
+
+    }
+
+    /* Evolve the population, exchanging individuals
+     * with the other islands when on an island. */
+    fn evolve(&self, control : &SolveControl, island : Option<(&Archipelago, usize)>) -> Vec< Vec<usize>>
+    {
+
+        let instance        : &dyn HgsInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>        = instance.get_distance_matrix();
+        let start           : Instant               = Instant::now();
+        let mut rng         : StdRng                = StdRng::seed_from_u64(self.seed);
+
+        let education : Education = Education {
+            neighbours      : nearest_neighbours(&distance_matrix, self.granularity),
+            distance_matrix,
+            nodes_demand    : instance.get_nodes_demand(),
+            capacity        : instance.get_capacity(),
+        };
+
+        /* A customer whose demand exceeds the capacity
+         * can not be served, so no routes are given. */
+        if education.nodes_demand.iter().any(|&q| q > education.capacity)
+        {
+            return Vec::new();
+        }
+
+        let customers : Vec<usize> = (1..education.nodes_demand.len()).collect();
+        if customers.len() < 2
+        {
+            return customers.into_iter().map(|c| vec![c]).collect();
+        }
+
+        /* The penalty starts from the ratio between the
+         * largest distance and the largest demand. */
+        let max_distance : f64 = education.distance_matrix.iter().flatten().cloned().fold(0.0, f64::max);
+        let max_demand   : f64 = education.nodes_demand.iter().cloned().max().unwrap_or(1).max(1) as f64;
+        let mut penalty  : f64 = (max_distance / max_demand).clamp(0.1, 1000.0);
+
+        let mut best       : Option<Individual> = None;
+        let mut population : Subpopulations     =
+            self.initial_population(&education, penalty, &mut best, &mut rng, control);
+        if let Some(individual) = &best
+        {
+            control.report(&individual.routes);
+        }
+
+        let mut feasible_children : usize = 0;
+        let mut iteration         : usize = 0;
+        let mut last_improvement  : usize = 0;
+        while !self.stopping_criterion.is_met(iteration, start) && !control.should_stop()
+        {
+
+            iteration += 1;
+
+            let child_tour : Vec<usize> = {
+                let parent_1 : &Individual = population.select_parent(&mut rng);
+                let parent_2 : &Individual = population.select_parent(&mut rng);
+                ordered_crossover(&parent_1.giant_tour, &parent_2.giant_tour, &mut rng)
+            };
+
+            let (feasible, improved) = education.add_offspring(&child_tour, penalty, &mut population, &mut best, &mut rng, control);
+
+            if feasible
+            {
+                feasible_children += 1;
+            }
+            if improved
+            {
+                last_improvement = iteration;
+                if let Some(individual) = &best
+                {
+                    control.report(&individual.routes);
+                }
+            }
+
+            /* Adapt the penalty so that about the target
+             * fraction of the children is feasible. */
+            if iteration.is_multiple_of(PENALTY_UPDATE_ITERATIONS)
+            {
+                let fraction : f64 = feasible_children as f64 / PENALTY_UPDATE_ITERATIONS as f64;
+                if fraction < FEASIBLE_TARGET - 0.05
+                {
+                    penalty = (penalty * 1.2).min(100000.0);
+                }
+                else if fraction > FEASIBLE_TARGET + 0.05
+                {
+                    penalty = (penalty * 0.85).max(0.1);
+                }
+                population.infeasible.set_penalty(penalty);
+                feasible_children = 0;
+            }
+
+            if iteration - last_improvement >= self.restart_iterations
+            {
+                population       = self.initial_population(&education, penalty, &mut best, &mut rng, control);
+                last_improvement = iteration;
+                if let Some(individual) = &best
+                {
+                    control.report(&individual.routes);
+                }
+            }
+
+            /* Send the best feasible individuals to the
+             * next island, and add the ones arrived. */
+            if let Some((archipelago, index)) = island
+            {
+                if iteration.is_multiple_of(archipelago.migration_interval.max(1))
+                {
+                    archipelago.emigrate(index, population.feasible.best_individuals(archipelago.migrants));
+                    for individual in archipelago.immigrate(index)
+                    {
+                        if best.as_ref().is_none_or(|b| individual.cost < b.cost - EPSILON)
+                        {
+                            best             = Some(individual.clone());
+                            last_improvement = iteration;
+                            control.report(&individual.routes);
+                        }
+                        population.add(individual);
+                    }
+                }
+            }
+
+        }
+
+        /* When no feasible individual is found the
+         * routes of Split are returned. */
+        return match best
+        {
+            Some(individual) => individual.routes,
+            _ => split(&education.distance_matrix, &education.nodes_demand, education.capacity, &customers)
+                .unwrap(),
+        };
+
+    }
+
+    /* Population made of random giant tours, educated,
+     * until the search is stopped after a first feasible
+     * individual is found. */
+    fn initial_population(
+        &self,
+        education : &Education,
+        penalty   : f64,
+        best      : &mut Option<Individual>,
+        rng       : &mut dyn RngCore,
+        control   : &SolveControl)
+        -> Subpopulations
+    {
+
+        let new_subpopulation = || Population::new(
+            self.population_size, self.generation_size, self.elite_individuals, self.close_individuals);
+
+        let mut population : Subpopulations = Subpopulations {
+            feasible   : new_subpopulation(),
+            infeasible : new_subpopulation(),
+        };
+        population.infeasible.set_penalty(penalty);
+
+        let customers : Vec<usize> = (1..education.nodes_demand.len()).collect();
+        for _ in 0..(4 * self.population_size)
+        {
+            if best.is_some() && control.should_stop()
+            {
+                break;
+            }
+            let mut giant_tour : Vec<usize> = customers.clone();
+            giant_tour.shuffle(rng);
+            education.add_offspring(&giant_tour, penalty, &mut population, best, rng, control);
+        }
+
+        return population;
+
+    }
+
+}
+
+/* Ordered crossover: a random section of the first
+ * parent is copied into the child, the remaining
+ * customers follow in the order of the second
+ * parent, starting after the section. */
+fn ordered_crossover(
+    parent_1 : &[usize],
+    parent_2 : &[usize],
+    rng      : &mut dyn RngCore)
+    -> Vec<usize>
+{
+
+    let length : usize = parent_1.len();
+    let start  : usize = rng.gen_range(0..length);
+    let end    : usize = rng.gen_range(0..length);
+
+    let mut child  : Vec<usize> = vec![0; length];
+    let mut copied : Vec<bool>  = vec![false; length + 1];
+
+    let mut position : usize = start;
+    loop
+    {
+        child[position]            = parent_1[position];
+        copied[parent_1[position]] = true;
+        if position == end
+        {
+            break;
+        }
+        position = (position + 1) % length;
+    }
+
+    for k in 1..=length
+    {
+        let customer : usize = parent_2[(end + k) % length];
+        if !copied[customer]
+        {
+            position        = (position + 1) % length;
+            child[position] = customer;
+        }
+    }
+
+    return child;
+
+}
+
+/* Data of the instance used by the education. */
+struct Education
+{
+
+    distance_matrix : Vec< Vec<f64>>,
+
+    nodes_demand    : Vec<usize>,
+
+    capacity        : usize,
+
+    neighbours      : Vec< Vec<usize>>,
+
+}
+
+impl Education
+{
+
+    /* Decode the giant tour with Split, allowing
+     * overloaded routes with the given penalty.
+     * Every demand fits the capacity, as checked
+     * before the search, so the tour is split. */
+    fn decode(&self, giant_tour : &[usize], penalty : f64) -> Vec< Vec<usize>>
+    {
+
+        split_penalized(&self.distance_matrix, &self.nodes_demand, self.capacity, penalty, giant_tour)
+            .unwrap()
+
+    }
+
+    /* Improve the routes, visiting the customers in
+     * random order and applying the first granular
+     * move of each customer which decreases the cost
+     * plus the penalty times the excess of load, until
+     * a local optimum is reached or the control
+     * is stopped. */
+    fn educate(
+        &self,
+        routes  : Vec< Vec<usize>>,
+        penalty : f64,
+        rng     : &mut dyn RngCore,
+        control : &SolveControl)
+        -> Individual
+    {
+
+        let node_number : usize            = self.nodes_demand.len();
+        let mut routes  : Vec< Vec<usize>> = routes;
+        routes.push(Vec::new());
+
+        let neighbourhoods : [Neighbourhood; 4] = [
+            Neighbourhood::Relocate,
+            Neighbourhood::Swap,
+            Neighbourhood::TwoOptStar,
+            Neighbourhood::TwoOpt,
+        ];
+
+        let excess = |load : usize| -> f64 { load.saturating_sub(self.capacity) as f64 };
+
+        let mut order : Vec<usize> = (1..node_number).collect();
+        order.shuffle(rng);
+
+        let mut loads : Vec<usize> = routes.iter()
+            .map(|r| r.iter().map(|&n| self.nodes_demand[n]).sum())
+            .collect();
+        let mut positions : Vec<(usize, usize)> = customer_positions(&routes, node_number);
+
+        let mut improved : bool = true;
+        while improved && !control.should_stop()
+        {
+
+            improved = false;
+
+            for &u in &order
+            {
+                for neighbourhood in &neighbourhoods
+                {
+
+                    let mut moves : Vec<Move> = neighbourhood.customer_granular_moves(
+                        &routes, &positions, u, &self.neighbours[u]);
+
+                    /* A customer can be moved alone
+                     * into an empty route. */
+                    if *neighbourhood == Neighbourhood::Relocate
+                    {
+                        let empty_route : usize = routes.iter().position(|r| r.is_empty()).unwrap();
+                        moves.push(Move::Relocate {
+                            from_route : positions[u].0, from : positions[u].1, to_route : empty_route, to : 0,
+                        });
+                    }
+
+                    let improving_move : Option<(Move, Vec<(usize, usize)>)> = moves.into_iter()
+                        .map(|m| (m, m.loads_after(&routes, &loads, &self.nodes_demand)))
+                        .find(|(m, loads_after)| {
+                            let excess_delta : f64 = loads_after.iter()
+                                .map(|&(r, load)| excess(load) - excess(loads[r]))
+                                .sum();
+                            m.delta(&self.distance_matrix, &routes) + penalty * excess_delta < -EPSILON
+                        });
+
+                    if let Some((m, loads_after)) = improving_move
+                    {
+                        for (r, load) in loads_after
+                        {
+                            loads[r] = load;
+                        }
+                        m.apply(&mut routes);
+                        if routes.iter().all(|r| !r.is_empty())
+                        {
+                            routes.push(Vec::new());
+                            loads.push(0);
+                        }
+                        positions = customer_positions(&routes, node_number);
+                        improved  = true;
+                        break;
+                    }
+
+                }
+            }
+
+        }
+
+        routes.retain(|r| !r.is_empty());
+        for route in routes.iter_mut()
+        {
+            optimize_route_with(
+                &self.distance_matrix, route, &[Neighbourhood::TwoOpt, Neighbourhood::OrOpt], control);
+        }
+
+        let cost         : f64   = compute_routes_cost(&self.distance_matrix, &routes);
+        let total_excess : usize = routes.iter()
+            .map(|r| r.iter().map(|&n| self.nodes_demand[n]).sum::<usize>().saturating_sub(self.capacity))
+            .sum();
+
+        Individual::new(routes, cost, total_excess, node_number)
+
+    }
+
+
+    /* Educate a new individual, repair it half of the
+     * times it is infeasible, educating it again with
+     * a larger penalty, and add it to the population.
+     * Return whether the individual was feasible and
+     * whether the best solution was improved. */
+    fn add_offspring(
+        &self,
+        giant_tour : &[usize],
+        penalty    : f64,
+        population : &mut Subpopulations,
+        best       : &mut Option<Individual>,
+        rng        : &mut dyn RngCore,
+        control    : &SolveControl)
+        -> (bool, bool)
+    {
+
+        let individual : Individual = self.educate(self.decode(giant_tour, penalty), penalty, rng, control);
+        let feasible   : bool       = individual.is_feasible();
+
+        let repaired : Option<Individual> = if !feasible && rng.gen_bool(0.5)
+        {
+            Some(self.educate(individual.routes.clone(), 10.0 * penalty, rng, control))
+        }
+        else
+        {
+            None
+        };
+
+        let mut improved : bool = false;
+        for candidate in std::iter::once(individual).chain(repaired)
+        {
+            if candidate.is_feasible() &&
+                best.as_ref().is_none_or(|b| candidate.cost < b.cost - EPSILON)
+            {
+                *best    = Some(candidate.clone());
+                improved = true;
+            }
+            population.add(candidate);
+        }
+
+        return (feasible, improved);
+
+    }
+
+}
+
+/* Feasible and infeasible subpopulations. */
+struct Subpopulations
+{
+
+    feasible   : Population,
+
+    infeasible : Population,
+
+}
+
+impl Subpopulations
+{
+
+    /* Select a parent by binary tournament on the
+     * biased fitness, over both the subpopulations. */
+    fn select_parent(&self, rng : &mut dyn RngCore) -> &Individual
+    {
+
+        let feasible_count : usize = self.feasible.individuals.len();
+        let total_count    : usize = feasible_count + self.infeasible.individuals.len();
+
+        let candidate = |index : usize| -> (f64, &Individual)
+            {
+                if index < feasible_count
+                {
+                    (self.feasible.biased_fitness(index), &self.feasible.individuals[index])
+                }
+                else
+                {
+                    let index : usize = index - feasible_count;
+                    (self.infeasible.biased_fitness(index), &self.infeasible.individuals[index])
+                }
+            };
+
+        let a : (f64, &Individual) = candidate(rng.gen_range(0..total_count));
+        let b : (f64, &Individual) = candidate(rng.gen_range(0..total_count));
+
+        if a.0 <= b.0 { a.1 } else { b.1 }
+
+    }
+
+    fn add(&mut self, individual : Individual)
+    {
+
+        if individual.is_feasible()
+        {
+            self.feasible.add(individual);
+        }
+        else
+        {
+            self.infeasible.add(individual);
+        }
+
+    }
+
+}
+
+/* Fraction of feasible individuals targeted
+ * by the adaptation of the penalty. */
+const FEASIBLE_TARGET : f64 = 0.2;
+
+/* Number of iterations between two
+ * updates of the penalty. */
+const PENALTY_UPDATE_ITERATIONS : usize = 100;
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for HgsSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        self.evolve(control, None)
+
+    }
+
+    fn get_seed(&self) -> Option<u64>
+    {
+
+        Some(self.seed)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use std::time::Duration;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+
+    #[test]
+    fn deadline_stops_the_search()
+    {
+
+        let graph   : GraphInstance = read_test_instance("F-n72-k4");
+        let solver  : HgsSolver     = HgsSolver::new(&graph, Duration::from_secs(60), 1);
+
+        let start   : Instant          = Instant::now();
+        let control : SolveControl     = SolveControl::new(None, Some(start + Duration::from_millis(100)), None, None);
+        let routes  : Vec< Vec<usize>> = solver.solve_with_control(&control);
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_feasible(&graph, &routes);
+
+    }
+
+    #[test]
+    fn crossover_gives_a_permutation()
+    {
+
+        let mut rng      : StdRng     = StdRng::seed_from_u64(3);
+        let parent_1     : Vec<usize> = (1..=20).collect();
+        let mut parent_2 : Vec<usize> = parent_1.clone();
+
+        for _ in 0..100
+        {
+            parent_2.shuffle(&mut rng);
+            let mut child : Vec<usize> = ordered_crossover(&parent_1, &parent_2, &mut rng);
+            child.sort();
+            assert_eq!(child, parent_1);
+        }
+
+    }
+
+    #[test]
+    fn same_seed_gives_same_routes()
+    {
+
+        let graph  : GraphInstance = read_test_instance("F-n45-k4");
+        let solver : HgsSolver     = HgsSolver {
+            stopping_criterion : StoppingCriterion::Iterations(300),
+            ..HgsSolver::new(&graph, Duration::from_secs(60), 5)
+        };
+
+        let routes : Vec< Vec<usize>> = solver.solve();
+        assert_feasible(&graph, &routes);
+        assert_eq!(routes, solver.solve());
+
+    }
+
+    #[test]
+    fn demand_above_the_capacity_gives_no_routes()
+    {
+
+        let mut graph : GraphInstance = read_test_instance("gr-n17-k3");
+        graph.instance.specification.capacity = graph.get_nodes_demand().into_iter().max().unwrap() - 1;
+
+        let solver : HgsSolver = HgsSolver {
+            stopping_criterion : StoppingCriterion::Iterations(10),
+            ..HgsSolver::new(&graph, Duration::from_secs(60), 1)
+        };
+
+        assert!(solver.solve().is_empty());
+
+    }
+
+}
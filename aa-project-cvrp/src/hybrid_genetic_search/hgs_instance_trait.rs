@@ -0,0 +1,14 @@
+/* This trait define the methods required
+ * by the hybrid genetic search, available
+ * only for GraphInstance when explicitly
+ * implemented. */
+pub trait HgsInstanceTrait : Sync
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>>;
+
+    fn get_capacity(&self) -> usize;
+
+    fn get_nodes_demand(&self) -> Vec<usize>;
+
+}
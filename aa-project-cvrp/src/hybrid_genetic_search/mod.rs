@@ -0,0 +1,4 @@
+pub mod hgs_instance_trait;
+pub mod population;
+pub mod hgs_imp;
+pub mod archipelago;
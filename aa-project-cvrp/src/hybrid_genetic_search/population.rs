@@ -0,0 +1,377 @@
+/* An individual of the population: the giant tour
+ * used as chromosome, and the routes it is decoded
+ * into, after the education. */
+#[derive(Clone)]
+pub struct Individual
+{
+
+    pub giant_tour   : Vec<usize>,
+
+    pub routes       : Vec< Vec<usize>>,
+
+    pub cost         : f64,
+
+    /* Total excess of load of the routes. */
+    pub excess       : usize,
+
+    /* Successor and predecessor of each customer
+     * in the routes, the depot being 0. */
+    successors   : Vec<usize>,
+
+    predecessors : Vec<usize>,
+
+}
+
+impl Individual
+{
+
+    /* Build the individual from its routes, the
+     * giant tour being their concatenation. */
+    pub fn new(
+        routes      : Vec< Vec<usize>>,
+        cost        : f64,
+        excess      : usize,
+        node_number : usize)
+        -> Individual
+    {
+
+        let mut successors   : Vec<usize> = vec![0; node_number];
+        let mut predecessors : Vec<usize> = vec![0; node_number];
+
+        for route in &routes
+        {
+            for (p, &n) in route.iter().enumerate()
+            {
+                predecessors[n] = if p == 0 { 0 } else { route[p - 1] };
+                successors[n]   = if p + 1 == route.len() { 0 } else { route[p + 1] };
+            }
+        }
+
+        Individual {
+            giant_tour : routes.iter().flatten().cloned().collect(),
+            routes,
+            cost,
+            excess,
+            successors,
+            predecessors,
+        }
+
+    }
+
+    pub fn is_feasible(&self) -> bool
+    {
+
+        self.excess == 0
+
+    }
+
+    /* Cost of the routes increased by the
+     * penalty times the excess of load. */
+    pub fn penalized_cost(&self, penalty : f64) -> f64
+    {
+
+        self.cost + penalty * self.excess as f64
+
+    }
+
+    /* Broken-pairs distance: fraction of the customers
+     * whose successor in this individual is adjacent
+     * to them in neither direction in the other. */
+    pub fn distance(&self, other : &Individual) -> f64
+    {
+
+        let customers : usize = self.successors.len() - 1;
+        let broken    : usize = (1..self.successors.len())
+            .filter(|&n|
+                self.successors[n] != other.successors[n] &&
+                    self.successors[n] != other.predecessors[n])
+            .count();
+
+        broken as f64 / customers.max(1) as f64
+
+    }
+
+}
+
+/* The population of the hybrid genetic search, with
+ * the survivors selected by biased fitness, which
+ * accounts for both the cost and the contribution
+ * to the diversity of the individuals. */
+pub struct Population
+{
+
+    pub individuals : Vec<Individual>,
+
+    /* Number of individuals kept by
+     * the survivors selection. */
+    size        : usize,
+
+    /* Number of offspring generated before
+     * the survivors selection. */
+    generation  : usize,
+
+    /* Number of best individuals which are
+     * preserved by the biased fitness. */
+    elite       : usize,
+
+    /* Number of closest individuals used to
+     * measure the diversity contribution. */
+    close       : usize,
+
+    /* Distances between the individuals, and their
+     * biased fitness, updated at every change. */
+    distances   : Vec< Vec<f64>>,
+
+    fitness     : Vec<f64>,
+
+    /* Penalty of the excess of load, used to
+     * rank the infeasible individuals. */
+    penalty     : f64,
+
+}
+
+impl Population
+{
+
+    pub fn new(size : usize, generation : usize, elite : usize, close : usize) -> Population
+    {
+
+        Population {
+            individuals : Vec::new(),
+            size,
+            generation,
+            elite,
+            close,
+            distances   : Vec::new(),
+            fitness     : Vec::new(),
+            penalty     : 0.0,
+        }
+
+    }
+
+    /* Compute the biased fitness of every
+     * individual, the lower the better. */
+    fn update_fitness(&mut self)
+    {
+
+        let count : usize = self.individuals.len();
+        self.fitness = vec![0.0; count];
+        if count <= 1
+        {
+            return;
+        }
+
+        /* Diversity contribution, as the average distance
+         * from the closest individuals. */
+        let diversity : Vec<f64> = (0..count)
+            .map(|i| {
+                let mut distances : Vec<f64> = (0..count)
+                    .filter(|&j| j != i)
+                    .map(|j| self.distances[i][j])
+                    .collect();
+                distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let close : usize = self.close.clamp(1, distances.len());
+                distances[..close].iter().sum::<f64>() / close as f64
+            })
+            .collect();
+
+        let mut cost_order : Vec<usize> = (0..count).collect();
+        let costs : Vec<f64> = self.individuals.iter().map(|i| i.penalized_cost(self.penalty)).collect();
+        cost_order.sort_by(|&a, &b| costs[a].partial_cmp(&costs[b]).unwrap());
+
+        let mut diversity_order : Vec<usize> = (0..count).collect();
+        diversity_order.sort_by(|&a, &b| diversity[b].partial_cmp(&diversity[a]).unwrap());
+
+        let diversity_weight : f64 = 1.0 - self.elite as f64 / count as f64;
+
+        for (rank, &i) in cost_order.iter().enumerate()
+        {
+            self.fitness[i] += rank as f64 / (count - 1) as f64;
+        }
+        for (rank, &i) in diversity_order.iter().enumerate()
+        {
+            self.fitness[i] += diversity_weight * rank as f64 / (count - 1) as f64;
+        }
+
+    }
+
+    /* Add an individual, and select the survivors
+     * when the generation is complete. */
+    pub fn add(&mut self, individual : Individual)
+    {
+
+        let distances : Vec<f64> = self.individuals.iter()
+            .map(|other| individual.distance(other))
+            .collect();
+        for (row, &distance) in self.distances.iter_mut().zip(distances.iter())
+        {
+            row.push(distance);
+        }
+        self.distances.push(distances);
+        self.distances.last_mut().unwrap().push(0.0);
+        self.individuals.push(individual);
+
+        if self.individuals.len() >= self.size + self.generation
+        {
+            self.select_survivors();
+        }
+
+        self.update_fitness();
+
+    }
+
+    /* Remove individuals until the population size is
+     * reached, clones first, then the individuals with
+     * the worst biased fitness. */
+    fn select_survivors(&mut self)
+    {
+
+        while self.individuals.len() > self.size
+        {
+
+            self.update_fitness();
+
+            let count : usize = self.individuals.len();
+            let worst : usize = (0..count)
+                .map(|i| ((0..count).any(|j| j != i && self.distances[i][j] == 0.0), self.fitness[i], i))
+                .max_by(|a, b| (a.0, a.1).partial_cmp(&(b.0, b.1)).unwrap())
+                .unwrap()
+                .2;
+
+            self.individuals.swap_remove(worst);
+            self.distances.swap_remove(worst);
+            for row in self.distances.iter_mut()
+            {
+                row.swap_remove(worst);
+            }
+
+        }
+
+    }
+
+    /* Biased fitness of the individual
+     * in the given position. */
+    pub fn biased_fitness(&self, index : usize) -> f64
+    {
+
+        self.fitness[index]
+
+    }
+
+    /* The given number of individuals
+     * with the lowest penalized cost. */
+    pub fn best_individuals(&self, count : usize) -> Vec<Individual>
+    {
+
+        let mut result : Vec<Individual> = self.individuals.clone();
+        result.sort_by(|a, b| a.penalized_cost(self.penalty).partial_cmp(&b.penalized_cost(self.penalty)).unwrap());
+        result.truncate(count);
+
+        return result;
+
+    }
+
+    /* Update the penalty, and the ranking
+     * of the individuals accordingly. */
+    pub fn set_penalty(&mut self, penalty : f64)
+    {
+
+        self.penalty = penalty;
+        self.update_fitness();
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+
+    /* Individual over four customers. */
+    fn individual(routes : Vec< Vec<usize>>, cost : f64, excess : usize) -> Individual
+    {
+
+        Individual::new(routes, cost, excess, 5)
+
+    }
+
+    #[test]
+    fn broken_pairs_distance()
+    {
+
+        let a : Individual = individual(vec![vec![1, 2], vec![3, 4]], 10.0, 0);
+
+        /* Routes travelled in the opposite direction
+         * keep every pair of adjacent customers. */
+        assert_eq!(a.distance(&a), 0.0);
+        assert_eq!(a.distance(&individual(vec![vec![4, 3], vec![2, 1]], 10.0, 0)), 0.0);
+
+        /* Merging the routes only breaks the
+         * pair of 2 with the depot. */
+        assert_eq!(a.distance(&individual(vec![vec![1, 2, 3, 4]], 10.0, 0)), 0.25);
+        assert_eq!(a.distance(&individual(vec![vec![1], vec![2], vec![3], vec![4]], 10.0, 0)), 0.5);
+
+    }
+
+    #[test]
+    fn survivors_are_selected_when_the_generation_is_complete()
+    {
+
+        let mut population : Population = Population::new(3, 2, 1, 1);
+        let routes : Vec< Vec< Vec<usize>>> = vec![
+            vec![vec![1, 2], vec![3, 4]],
+            vec![vec![1, 3], vec![2, 4]],
+            vec![vec![1, 4], vec![2, 3]],
+            vec![vec![1, 2, 3, 4]],
+            vec![vec![1], vec![2], vec![3], vec![4]],
+        ];
+
+        for (k, r) in routes.into_iter().enumerate()
+        {
+            population.add(individual(r, 10.0 + k as f64, 0));
+            assert_eq!(population.individuals.len(), if k < 4 { k + 1 } else { 3 });
+        }
+
+        /* The best individual has the best biased
+         * fitness, and is never removed. */
+        assert!(population.individuals.iter().any(|i| i.cost == 10.0));
+
+    }
+
+    #[test]
+    fn clones_are_removed_first()
+    {
+
+        let mut population : Population = Population::new(2, 2, 1, 1);
+        population.add(individual(vec![vec![1, 2], vec![3, 4]], 10.0, 0));
+        population.add(individual(vec![vec![2, 1], vec![4, 3]], 10.0, 0));
+        population.add(individual(vec![vec![1, 3], vec![2, 4]], 20.0, 0));
+        population.add(individual(vec![vec![1, 4], vec![2, 3]], 30.0, 0));
+
+        assert_eq!(population.individuals.len(), 2);
+        assert!(population.individuals[0].distance(&population.individuals[1]) > 0.0);
+        assert!(population.individuals.iter().any(|i| i.cost == 10.0));
+
+    }
+
+    #[test]
+    fn penalty_ranks_the_infeasible_individuals()
+    {
+
+        let mut population : Population = Population::new(10, 10, 1, 1);
+        population.add(individual(vec![vec![1, 2, 3, 4]], 10.0, 5));
+        population.add(individual(vec![vec![1, 2], vec![3, 4]], 20.0, 1));
+
+        population.set_penalty(1.0);
+        assert_eq!(population.best_individuals(1)[0].cost, 10.0);
+        assert!(population.biased_fitness(0) < population.biased_fitness(1));
+
+        population.set_penalty(10.0);
+        assert_eq!(population.best_individuals(1)[0].cost, 20.0);
+        assert!(population.biased_fitness(1) < population.biased_fitness(0));
+
+    }
+
+}
@@ -0,0 +1,272 @@
+use std::time::Instant;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+use crate::iterated_local_search::perturbation::Perturbation;
+use crate::route_first_algorithm::split::split;
+use crate::algorithm_interface::{CVRPSolver, CVRPImprover, StoppingCriterion, compute_routes_cost, SolveControl};
+
+/* Criterion deciding whether the perturbed and
+ * improved routes replace the current ones. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum AcceptanceCriterion
+{
+
+    /* Accept only routes better than the current. */
+    BetterOnly,
+
+    /* Accept any routes. */
+    RandomWalk,
+
+    /* Accept only better routes, and restart from a
+     * random giant tour after the given number of
+     * iterations without improvement. */
+    RestartAfter(usize),
+
+}
+
+/* Here the iterated local search is implemented.
+ * Starting from the routes of another solver, at
+ * each iteration the current routes are perturbed
+ * and improved by the given local searches, and
+ * the result replaces them as the acceptance
+ * criterion states. */
+pub struct IteratedLocalSearch<'a>
+{
+
+    pub instance           : &'a dyn LocalSearchInstanceTrait,
+
+    /* Solver computing the initial routes. */
+    pub initial_solver     : &'a dyn CVRPSolver,
+
+    /* Improvement procedures applied, in the given
+     * order, after each perturbation. */
+    pub improvers          : Vec<&'a dyn CVRPImprover>,
+
+    pub perturbation       : Perturbation,
+
+    pub acceptance         : AcceptanceCriterion,
+
+    pub stopping_criterion : StoppingCriterion,
+
+    pub seed               : u64,
+
+}
+
+/* Methods of IteratedLocalSearch not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> IteratedLocalSearch<'a>
+{
+
+    /* Iterated local search with double-bridge
+     * perturbation and better-only acceptance. */
+    pub fn new(
+        instance       : &'a dyn LocalSearchInstanceTrait,
+        initial_solver : &'a dyn CVRPSolver,
+        improvers      : Vec<&'a dyn CVRPImprover>,
+        seed           : u64)
+        -> IteratedLocalSearch<'a>
+    {
+
+        IteratedLocalSearch {
+            instance,
+            initial_solver,
+            improvers,
+            perturbation       : Perturbation::DoubleBridge,
+            acceptance         : AcceptanceCriterion::BetterOnly,
+            stopping_criterion : StoppingCriterion::Iterations(100),
+            seed,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn LocalSearchInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    pub fn set_seed(&mut self, seed : u64)
+    {
+
+        self.seed = seed;
+
+    }
+
+    fn improve(&self, routes : Vec< Vec<usize>>, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let mut result : Vec< Vec<usize>> = routes;
+
+        for improver in &self.improvers
+        {
+            result = improver.improve(result, control);
+        }
+
+        return result;
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for IteratedLocalSearch<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let instance        : &dyn LocalSearchInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>                = instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>                    = instance.get_nodes_demand();
+        let capacity        : usize                         = instance.get_capacity();
+        let start           : Instant                       = Instant::now();
+        let mut rng         : StdRng                        = StdRng::seed_from_u64(self.seed);
+
+        let mut routes       : Vec< Vec<usize>> = self.improve(self.initial_solver.solve_with_control(control), control);
+        let mut current_cost : f64              = compute_routes_cost(&distance_matrix, &routes);
+        let mut best_routes  : Vec< Vec<usize>> = routes.clone();
+        let mut best_cost    : f64              = current_cost;
+        control.report(&best_routes);
+
+        let mut iteration     : usize = 0;
+        let mut non_improving : usize = 0;
+        while !self.stopping_criterion.is_met(iteration, start) && !control.should_stop()
+        {
+
+            iteration += 1;
+
+            let candidate : Vec< Vec<usize>> = self.improve(
+                self.perturbation.apply(&distance_matrix, &nodes_demand, capacity, &routes, &mut rng), control);
+            let cost      : f64              = compute_routes_cost(&distance_matrix, &candidate);
+
+            if cost < current_cost - 1e-9
+            {
+                non_improving = 0;
+            }
+            else
+            {
+                non_improving += 1;
+            }
+
+            if cost < current_cost - 1e-9 || self.acceptance == AcceptanceCriterion::RandomWalk
+            {
+                routes       = candidate;
+                current_cost = cost;
+            }
+
+            if current_cost < best_cost - 1e-9
+            {
+                best_cost   = current_cost;
+                best_routes = routes.clone();
+                control.report(&best_routes);
+            }
+
+            if let AcceptanceCriterion::RestartAfter(patience) = self.acceptance
+            {
+                if non_improving >= patience
+                {
+                    let mut giant_tour : Vec<usize> = (1..nodes_demand.len()).collect();
+                    giant_tour.shuffle(&mut rng);
+
+                    if let Some(restart) = split(&distance_matrix, &nodes_demand, capacity, &giant_tour)
+                    {
+                        routes       = self.improve(restart, control);
+                        current_cost = compute_routes_cost(&distance_matrix, &routes);
+                    }
+                    non_improving = 0;
+
+                    if current_cost < best_cost - 1e-9
+                    {
+                        best_cost   = current_cost;
+                        best_routes = routes.clone();
+                        control.report(&best_routes);
+                    }
+                }
+            }
+
+        }
+
+        return best_routes;
+
+    }
+
+    fn get_seed(&self) -> Option<u64>
+    {
+
+        Some(self.seed)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use std::time::Duration;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+    use crate::local_search::inter_route_imp::InterRouteLocalSearch;
+
+    #[test]
+    fn deadline_stops_the_search()
+    {
+
+        let graph   : GraphInstance         = read_test_instance("F-n72-k4");
+        let savings : SavingsSolver         = SavingsSolver::new(&graph);
+        let local   : InterRouteLocalSearch = InterRouteLocalSearch::new(&graph);
+        let solver  : IteratedLocalSearch   = IteratedLocalSearch {
+            stopping_criterion : StoppingCriterion::Iterations(usize::MAX),
+            ..IteratedLocalSearch::new(&graph, &savings, vec![&local], 1)
+        };
+
+        let start   : Instant          = Instant::now();
+        let control : SolveControl     = SolveControl::new(None, Some(start + Duration::from_millis(100)), None, None);
+        let routes  : Vec< Vec<usize>> = solver.solve_with_control(&control);
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_feasible(&graph, &routes);
+
+    }
+
+    #[test]
+    fn every_acceptance_gives_feasible_routes()
+    {
+
+        let graph   : GraphInstance         = read_test_instance("F-n45-k4");
+        let savings : SavingsSolver         = SavingsSolver::new(&graph);
+        let local   : InterRouteLocalSearch = InterRouteLocalSearch::new(&graph);
+
+        for acceptance in [
+            AcceptanceCriterion::BetterOnly,
+            AcceptanceCriterion::RandomWalk,
+            AcceptanceCriterion::RestartAfter(3),
+        ]
+        {
+
+            let solver : IteratedLocalSearch = IteratedLocalSearch {
+                acceptance,
+                stopping_criterion : StoppingCriterion::Iterations(20),
+                ..IteratedLocalSearch::new(&graph, &savings, vec![&local], 1)
+            };
+
+            assert_feasible(&graph, &solver.solve());
+
+        }
+
+    }
+
+}
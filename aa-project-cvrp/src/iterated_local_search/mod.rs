@@ -0,0 +1,2 @@
+pub mod perturbation;
+pub mod iterated_local_search_imp;
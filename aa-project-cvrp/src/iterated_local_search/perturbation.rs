@@ -0,0 +1,230 @@
+use rand::{Rng, RngCore};
+use rand::seq::SliceRandom;
+use rand::seq::index::sample;
+use crate::route_first_algorithm::split::split;
+
+/* Perturbation applied to the routes at each
+ * iteration of the iterated local search. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum Perturbation
+{
+
+    /* The routes are concatenated into a giant tour,
+     * which is cut into four parts A B C D and
+     * reconnected as A C B D, then split again
+     * into routes. */
+    DoubleBridge,
+
+    /* A segment of at most the given length is cut
+     * from each of the given number of random routes,
+     * and the segments are exchanged cyclically
+     * between them. */
+    SegmentShuffle { routes : usize, length : usize },
+
+}
+
+impl Perturbation
+{
+
+    /* Perturb the routes, keeping them
+     * within the capacity. */
+    pub fn apply(
+        &self,
+        distance_matrix : &[Vec<f64>],
+        nodes_demand    : &[usize],
+        capacity        : usize,
+        routes          : &[Vec<usize>],
+        rng             : &mut dyn RngCore)
+        -> Vec< Vec<usize>>
+    {
+
+        match *self
+        {
+            Perturbation::DoubleBridge =>
+                double_bridge(distance_matrix, nodes_demand, capacity, routes, rng),
+            Perturbation::SegmentShuffle { routes : count, length } =>
+                segment_shuffle(nodes_demand, capacity, routes, count, length, rng),
+        }
+
+    }
+
+}
+
+fn double_bridge(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    routes          : &[Vec<usize>],
+    rng             : &mut dyn RngCore)
+    -> Vec< Vec<usize>>
+{
+
+    let giant_tour : Vec<usize> = routes.iter().flatten().cloned().collect();
+    if giant_tour.len() < 4
+    {
+        return routes.to_vec();
+    }
+
+    /* Three distinct cut points, which
+     * leave the four parts non empty. */
+    let mut cuts : Vec<usize> = sample(rng, giant_tour.len() - 1, 3)
+        .into_iter()
+        .map(|c| c + 1)
+        .collect();
+    cuts.sort();
+
+    let mut perturbed : Vec<usize> = Vec::with_capacity(giant_tour.len());
+    perturbed.extend_from_slice(&giant_tour[..cuts[0]]);
+    perturbed.extend_from_slice(&giant_tour[cuts[1]..cuts[2]]);
+    perturbed.extend_from_slice(&giant_tour[cuts[0]..cuts[1]]);
+    perturbed.extend_from_slice(&giant_tour[cuts[2]..]);
+
+    /* The routes are kept when a customer
+     * can not be served by any route. */
+    return match split(distance_matrix, nodes_demand, capacity, &perturbed)
+    {
+        Some(result) => result,
+        _            => routes.to_vec(),
+    };
+
+}
+
+fn segment_shuffle(
+    nodes_demand : &[usize],
+    capacity     : usize,
+    routes       : &[Vec<usize>],
+    count        : usize,
+    length       : usize,
+    rng          : &mut dyn RngCore)
+    -> Vec< Vec<usize>>
+{
+
+    let mut result : Vec< Vec<usize>> = routes.to_vec();
+    result.retain(|r| !r.is_empty());
+
+    let count : usize = count.min(result.len());
+    if count < 2 || length == 0
+    {
+        return result;
+    }
+
+    let mut selected : Vec<usize> = (0..result.len()).collect();
+    selected.shuffle(rng);
+    selected.truncate(count);
+
+    /* Cut a segment from each selected route,
+     * remembering where it was. */
+    let mut cuts : Vec<(usize, Vec<usize>)> = Vec::with_capacity(count);
+    for &r in &selected
+    {
+        let route_len : usize = result[r].len();
+        let size      : usize = rng.gen_range(1..=length.min(route_len));
+        let position  : usize = rng.gen_range(0..=route_len - size);
+        let segment   : Vec<usize> = result[r].drain(position..position + size).collect();
+        cuts.push((position, segment));
+    }
+
+    /* Each route receives the segment of the previous
+     * one, possibly reversed. The customers which
+     * exceed the capacity open a new route. */
+    let mut overflow : Vec<usize> = Vec::new();
+    for (i, &r) in selected.iter().enumerate()
+    {
+
+        let position    : usize      = cuts[i].0;
+        let mut segment : Vec<usize> = cuts[(i + count - 1) % count].1.clone();
+        if rng.gen::<bool>()
+        {
+            segment.reverse();
+        }
+
+        let mut load : usize = result[r].iter().map(|&n| nodes_demand[n]).sum();
+        let mut kept : Vec<usize> = Vec::with_capacity(segment.len());
+        for node in segment
+        {
+            if load + nodes_demand[node] <= capacity
+            {
+                load += nodes_demand[node];
+                kept.push(node);
+            }
+            else
+            {
+                overflow.push(node);
+            }
+        }
+
+        result[r].splice(position..position, kept);
+
+    }
+
+    /* The overflow is served by as few new
+     * routes as a sequential filling allows. */
+    let mut load : usize = capacity;
+    for node in overflow
+    {
+        if load + nodes_demand[node] > capacity
+        {
+            result.push(Vec::new());
+            load = 0;
+        }
+        result.last_mut().unwrap().push(node);
+        load += nodes_demand[node];
+    }
+
+    result.retain(|r| !r.is_empty());
+
+    return result;
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::algorithm_interface::CVRPSolver;
+    use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+
+    /* Perturb the savings routes repeatedly, checking
+     * the routes after each perturbation. */
+    fn check_perturbation(perturbation : Perturbation)
+    {
+
+        for name in ["F-n45-k4", "bays-n29-k5", "ulysses-n16-k3"]
+        {
+
+            let graph           : GraphInstance    = read_test_instance(name);
+            let distance_matrix : Vec< Vec<f64>>   = graph.get_distance_matrix();
+            let nodes_demand    : Vec<usize>       = graph.get_nodes_demand();
+            let capacity        : usize            = graph.get_capacity();
+            let mut routes      : Vec< Vec<usize>> = SavingsSolver::new(&graph).solve();
+            let mut rng         : StdRng           = StdRng::seed_from_u64(11);
+
+            for _ in 0..50
+            {
+                routes = perturbation.apply(&distance_matrix, &nodes_demand, capacity, &routes, &mut rng);
+                assert_feasible(&graph, &routes);
+            }
+
+        }
+
+    }
+
+    #[test]
+    fn double_bridge_keeps_the_routes_feasible()
+    {
+        check_perturbation(Perturbation::DoubleBridge);
+    }
+
+    #[test]
+    fn segment_shuffle_keeps_the_routes_feasible()
+    {
+        check_perturbation(Perturbation::SegmentShuffle { routes : 3, length : 4 });
+    }
+
+}
@@ -0,0 +1,191 @@
+use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+use crate::local_search::moves::{Move, Neighbourhood, EPSILON};
+use crate::algorithm_interface::{CVRPImprover, SolveControl};
+
+/* Strategy used to select the move
+ * applied at each iteration. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum ImprovementStrategy
+{
+
+    /* Apply the first improving move found. */
+    FirstImprovement,
+
+    /* Apply the best move of the neighbourhood. */
+    BestImprovement,
+
+}
+
+/* Here the inter-route local search is
+ * implemented. Customers are moved between
+ * routes with relocate, swap, 2-opt* and
+ * CROSS-exchange moves, as long as the
+ * capacity of the routes is respected. */
+pub struct InterRouteLocalSearch<'a>
+{
+
+    pub instance       : &'a dyn LocalSearchInstanceTrait,
+
+    pub strategy       : ImprovementStrategy,
+
+    /* Neighbourhoods explored, in order. After an
+     * improving move the search restarts from
+     * the first neighbourhood. */
+    pub neighbourhoods : Vec<Neighbourhood>,
+
+}
+
+/* Methods of InterRouteLocalSearch not
+ * defined by the CVRPImprover trait. */
+#[allow(dead_code)]
+impl<'a> InterRouteLocalSearch<'a>
+{
+
+    /* Local search over all the inter-route
+     * neighbourhoods, with first improvement. */
+    pub fn new(instance : &'a dyn LocalSearchInstanceTrait) -> InterRouteLocalSearch<'a>
+    {
+
+        InterRouteLocalSearch {
+            instance,
+            strategy       : ImprovementStrategy::FirstImprovement,
+            neighbourhoods : vec![
+                Neighbourhood::Relocate,
+                Neighbourhood::Swap,
+                Neighbourhood::TwoOptStar,
+                Neighbourhood::CrossExchange,
+            ],
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn LocalSearchInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+}
+
+/* Run the local search over the routes until no
+ * improving and feasible move is left in any of the
+ * neighbourhoods, or the control is stopped. An
+ * empty route is kept during the search, so that
+ * a new route can be opened, and empty routes are
+ * removed at the end. */
+pub fn local_search(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    neighbourhoods  : &Vec<Neighbourhood>,
+    strategy        : ImprovementStrategy,
+    routes          : Vec< Vec<usize>>,
+    control         : &SolveControl)
+    -> Vec< Vec<usize>>
+{
+
+    let mut result : Vec< Vec<usize>> = routes;
+    result.retain(|r| !r.is_empty());
+    result.push(Vec::new());
+
+    let mut improved : bool = true;
+    while improved && !control.should_stop()
+    {
+
+        improved = false;
+
+        let loads : Vec<usize> = result.iter()
+            .map(|r| r.iter().map(|&n| nodes_demand[n]).sum())
+            .collect();
+
+        for &neighbourhood in neighbourhoods
+        {
+
+            let selected_move : Option<Move> = select_move(
+                distance_matrix, nodes_demand, capacity, neighbourhood, strategy, &result, &loads);
+
+            if let Some(m) = selected_move
+            {
+                m.apply(&mut result);
+                improved = true;
+                break;
+            }
+
+        }
+
+        /* Keep exactly one empty route. */
+        if improved
+        {
+            result.retain(|r| !r.is_empty());
+            result.push(Vec::new());
+        }
+
+    }
+
+    result.retain(|r| !r.is_empty());
+
+    return result;
+
+}
+
+/* Select an improving and feasible move of the
+ * neighbourhood, following the strategy, if any. */
+pub fn select_move(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    neighbourhood   : Neighbourhood,
+    strategy        : ImprovementStrategy,
+    routes          : &[Vec<usize>],
+    loads           : &[usize])
+    -> Option<Move>
+{
+
+    let mut selected_move  : Option<Move> = None;
+    let mut selected_delta : f64          = -EPSILON;
+
+    for m in neighbourhood.moves(routes)
+    {
+
+        let delta : f64 = m.delta(distance_matrix, routes);
+        if delta < selected_delta &&
+            m.is_feasible(routes, loads, nodes_demand, capacity)
+        {
+            selected_move  = Some(m);
+            selected_delta = delta;
+
+            if strategy == ImprovementStrategy::FirstImprovement
+            {
+                break;
+            }
+        }
+
+    }
+
+    return selected_move;
+
+}
+
+/* Implementation of the CVRPImprover trait. */
+impl<'a> CVRPImprover for InterRouteLocalSearch<'a>
+{
+
+    fn improve(&self, routes : Vec< Vec<usize>>, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let instance : &dyn LocalSearchInstanceTrait = self.instance;
+
+        local_search(
+            &instance.get_distance_matrix(),
+            &instance.get_nodes_demand(),
+            instance.get_capacity(),
+            &self.neighbourhoods,
+            self.strategy,
+            routes,
+            control)
+
+    }
+
+}
@@ -0,0 +1,167 @@
+use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+use crate::local_search::moves::{Move, Neighbourhood, EPSILON};
+use crate::algorithm_interface::{CVRPImprover, SolveControl};
+
+/* Here the intra-route optimization is
+ * implemented. Each route is improved as
+ * a TSP tour through the depot, applying
+ * 2-opt and Or-opt moves until no move
+ * improves the route. */
+pub struct IntraRouteOptimizer<'a>
+{
+
+    pub instance : &'a dyn LocalSearchInstanceTrait,
+
+}
+
+/* Methods of IntraRouteOptimizer not
+ * defined by the CVRPImprover trait. */
+#[allow(dead_code)]
+impl<'a> IntraRouteOptimizer<'a>
+{
+
+    pub fn add_instance(&'a mut self, i : &'a dyn LocalSearchInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+}
+
+/* Improve a single route, applying the first
+ * improving move found of the given intra-route
+ * neighbourhoods, until a local optimum is
+ * reached or the control is stopped. */
+pub fn optimize_route_with(
+    distance_matrix : &[Vec<f64>],
+    route           : &mut Vec<usize>,
+    neighbourhoods  : &[Neighbourhood],
+    control         : &SolveControl)
+{
+
+    let routes : &mut [Vec<usize>] = std::slice::from_mut(route);
+
+    let mut improved : bool = true;
+    while improved && !control.should_stop()
+    {
+
+        improved = false;
+
+        for neighbourhood in neighbourhoods
+        {
+
+            let improving_move : Option<Move> = neighbourhood.moves(routes)
+                .into_iter()
+                .find(|m| m.delta(distance_matrix, routes) < -EPSILON);
+
+            if let Some(m) = improving_move
+            {
+                m.apply(routes);
+                improved = true;
+                break;
+            }
+
+        }
+
+    }
+
+}
+
+/* Implementation of the CVRPImprover trait. */
+impl<'a> CVRPImprover for IntraRouteOptimizer<'a>
+{
+
+    fn improve(&self, routes : Vec< Vec<usize>>, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let distance_matrix : Vec< Vec<f64>>   = self.instance.get_distance_matrix();
+        let mut result      : Vec< Vec<usize>> = routes;
+
+        for route in result.iter_mut()
+        {
+            optimize_route_with(
+                &distance_matrix, route, &[Neighbourhood::TwoOpt, Neighbourhood::OrOpt], control);
+        }
+
+        return result;
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use crate::algorithm_interface::{CVRPSolver, compute_routes_cost};
+    use crate::model::{GraphInstance, read_test_instance};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+
+    #[test]
+    fn optimization_keeps_the_customers_and_never_worsens()
+    {
+
+        let graph           : GraphInstance  = read_test_instance("F-n45-k4");
+        let distance_matrix : Vec< Vec<f64>> = graph.get_distance_matrix();
+        let mut rng         : StdRng         = StdRng::seed_from_u64(5);
+
+        for neighbourhoods in [
+            vec![Neighbourhood::TwoOpt],
+            vec![Neighbourhood::OrOpt],
+            vec![Neighbourhood::TwoOpt, Neighbourhood::OrOpt],
+        ]
+        {
+            for _ in 0..20
+            {
+
+                let mut route : Vec<usize> = (1..distance_matrix.len()).collect();
+                route.shuffle(&mut rng);
+                route.truncate(15);
+
+                let original : Vec<usize> = route.clone();
+                optimize_route_with(&distance_matrix, &mut route, &neighbourhoods, &SolveControl::unlimited());
+
+                let mut customers : Vec<usize> = route.clone();
+                let mut expected  : Vec<usize> = original.clone();
+                customers.sort();
+                expected.sort();
+
+                assert_eq!(customers, expected);
+                assert!(compute_routes_cost(&distance_matrix, &vec![route])
+                    <= compute_routes_cost(&distance_matrix, &vec![original]) + EPSILON);
+
+            }
+        }
+
+    }
+
+    #[test]
+    fn optimizer_never_worsens_the_routes()
+    {
+
+        let graph     : GraphInstance       = read_test_instance("F-n45-k4");
+        let optimizer : IntraRouteOptimizer = IntraRouteOptimizer { instance : &graph };
+        let routes    : Vec< Vec<usize>>    = SavingsSolver::new(&graph).solve();
+        let improved  : Vec< Vec<usize>>    = optimizer.improve(routes.clone(), &SolveControl::unlimited());
+
+        for (route, optimized) in routes.iter().zip(&improved)
+        {
+            let mut customers : Vec<usize> = optimized.clone();
+            let mut expected  : Vec<usize> = route.clone();
+            customers.sort();
+            expected.sort();
+            assert_eq!(customers, expected);
+        }
+
+        let distance_matrix : Vec< Vec<f64>> = graph.get_distance_matrix();
+        assert!(compute_routes_cost(&distance_matrix, &improved)
+            <= compute_routes_cost(&distance_matrix, &routes) + EPSILON);
+
+    }
+
+}
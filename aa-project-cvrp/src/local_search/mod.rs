@@ -0,0 +1,5 @@
+pub mod local_search_instance_trait;
+pub mod moves;
+pub mod intra_route_imp;
+pub mod inter_route_imp;
+pub mod post_optimization_imp;
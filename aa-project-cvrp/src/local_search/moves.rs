@@ -0,0 +1,878 @@
+/* Moves applied by the local search procedures.
+ * Positions refer to the customers inside
+ * a route, the depot being implicit at both
+ * the ends of every route.
+ * Distances are assumed symmetric, as in the
+ * CVRP instances of the TSPLIB. */
+
+use rand::{Rng, RngCore};
+use rand::seq::SliceRandom;
+
+/* Minimum variation of the cost for
+ * a move to be considered improving. */
+pub const EPSILON : f64 = 1e-9;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Move
+{
+
+    /* Reverse the customers between the
+     * positions i and j (both included). */
+    TwoOpt { route : usize, i : usize, j : usize },
+
+    /* Move the segment of the given length starting at
+     * position from, to the position to of the route
+     * obtained after removing the segment. */
+    OrOpt { route : usize, from : usize, length : usize, to : usize },
+
+    /* Move the customer in position from of the first
+     * route, to position to of the second route. */
+    Relocate { from_route : usize, from : usize, to_route : usize, to : usize },
+
+    /* Exchange two customers of different routes. */
+    Swap { route_1 : usize, position_1 : usize, route_2 : usize, position_2 : usize },
+
+    /* Exchange the tails of two routes, starting
+     * from the given positions. */
+    TwoOptStar { route_1 : usize, position_1 : usize, route_2 : usize, position_2 : usize },
+
+    /* Exchange two segments of two routes, each one
+     * defined by its starting position and its
+     * length, which may be zero. */
+    CrossExchange {
+        route_1    : usize,
+        position_1 : usize,
+        length_1   : usize,
+        route_2    : usize,
+        position_2 : usize,
+        length_2   : usize,
+    },
+
+}
+
+/* The neighbourhoods explored by
+ * the local search procedures. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Neighbourhood
+{
+
+    TwoOpt,
+    OrOpt,
+    Relocate,
+    Swap,
+    TwoOptStar,
+    CrossExchange,
+
+}
+
+/* Maximum length of the segments moved by
+ * Or-opt and exchanged by CROSS-exchange. */
+pub const MAX_SEGMENT_LENGTH : usize = 3;
+
+/* Node before the given position,
+ * which is the depot for the first one. */
+pub(crate) fn node_before(route : &[usize], position : usize) -> usize
+{
+
+    if position == 0 { 0 } else { route[position - 1] }
+
+}
+
+/* Node in the given position, which is
+ * the depot past the end of the route. */
+pub(crate) fn node_at(route : &[usize], position : usize) -> usize
+{
+
+    if position < route.len() { route[position] } else { 0 }
+
+}
+
+/* Variation of the cost of the route if the customers
+ * from position to position + length are replaced
+ * by the given segment. The internal edges of both
+ * the segments are accounted, so that they cancel
+ * out when the segments are exchanged. */
+fn segment_replacement_delta(
+    distance_matrix : &[Vec<f64>],
+    route           : &[usize],
+    position        : usize,
+    length          : usize,
+    segment         : &[usize])
+    -> f64
+{
+
+    let path_cost = |nodes : &[usize]| -> f64
+    {
+        let mut cost     : f64   = 0.0;
+        let mut previous : usize = node_before(route, position);
+        for &n in nodes
+        {
+            cost     += distance_matrix[previous][n];
+            previous  = n;
+        }
+        cost + distance_matrix[previous][node_at(route, position + length)]
+    };
+
+    path_cost(segment) - path_cost(&route[position..position + length])
+
+}
+
+/* Sum of the demands of the given customers. */
+fn segment_load(segment : &[usize], nodes_demand : &[usize]) -> usize
+{
+
+    segment.iter().map(|&n| nodes_demand[n]).sum()
+
+}
+
+impl Move
+{
+
+    /* Variation of the cost of the routes
+     * if the move is applied. */
+    pub fn delta(
+        &self,
+        distance_matrix : &[Vec<f64>],
+        routes          : &[Vec<usize>])
+        -> f64
+    {
+
+        let d = |a : usize, b : usize| distance_matrix[a][b];
+
+        match *self
+        {
+
+            Move::TwoOpt { route, i, j } =>
+                {
+                    let r : &Vec<usize> = &routes[route];
+                    let a : usize       = node_before(r, i);
+                    let b : usize       = node_at(r, j + 1);
+
+                    d(a, r[j]) + d(r[i], b) - d(a, r[i]) - d(r[j], b)
+                }
+
+            Move::OrOpt { route, from, length, to } =>
+                {
+                    let r     : &Vec<usize> = &routes[route];
+                    let first : usize       = r[from];
+                    let last  : usize       = r[from + length - 1];
+                    let a     : usize       = node_before(r, from);
+                    let b     : usize       = node_at(r, from + length);
+
+                    /* Nodes around the insertion point, in
+                     * the route without the segment. */
+                    let reduced = |k : usize| if k < from { r[k] } else { r[k + length] };
+                    let reduced_len : usize = r.len() - length;
+                    let c : usize = if to == 0 { 0 } else { reduced(to - 1) };
+                    let e : usize = if to == reduced_len { 0 } else { reduced(to) };
+
+                    d(a, b) - d(a, first) - d(last, b) + d(c, first) + d(last, e) - d(c, e)
+                }
+
+            Move::Relocate { from_route, from, to_route, to } =>
+                segment_replacement_delta(distance_matrix, &routes[from_route], from, 1, &[])
+                    + segment_replacement_delta(
+                        distance_matrix, &routes[to_route], to, 0, &routes[from_route][from..=from]),
+
+            Move::Swap { route_1, position_1, route_2, position_2 } =>
+                segment_replacement_delta(
+                    distance_matrix, &routes[route_1], position_1, 1,
+                    &routes[route_2][position_2..=position_2])
+                    + segment_replacement_delta(
+                        distance_matrix, &routes[route_2], position_2, 1,
+                        &routes[route_1][position_1..=position_1]),
+
+            Move::TwoOptStar { route_1, position_1, route_2, position_2 } =>
+                {
+                    let r_1 : &Vec<usize> = &routes[route_1];
+                    let r_2 : &Vec<usize> = &routes[route_2];
+                    let a_1 : usize       = node_before(r_1, position_1);
+                    let b_1 : usize       = node_at(r_1, position_1);
+                    let a_2 : usize       = node_before(r_2, position_2);
+                    let b_2 : usize       = node_at(r_2, position_2);
+
+                    d(a_1, b_2) + d(a_2, b_1) - d(a_1, b_1) - d(a_2, b_2)
+                }
+
+            Move::CrossExchange { route_1, position_1, length_1, route_2, position_2, length_2 } =>
+                segment_replacement_delta(
+                    distance_matrix, &routes[route_1], position_1, length_1,
+                    &routes[route_2][position_2..position_2 + length_2])
+                    + segment_replacement_delta(
+                        distance_matrix, &routes[route_2], position_2, length_2,
+                        &routes[route_1][position_1..position_1 + length_1]),
+
+        }
+
+    }
+
+    /* Loads of the routes modified by the move, after
+     * the move is applied, as (route, load) couples.
+     * Intra-route moves do not modify any load. */
+    pub fn loads_after(
+        &self,
+        routes       : &[Vec<usize>],
+        loads        : &[usize],
+        nodes_demand : &[usize])
+        -> Vec<(usize, usize)>
+    {
+
+        match *self
+        {
+
+            Move::TwoOpt { .. } | Move::OrOpt { .. } => Vec::new(),
+
+            Move::Relocate { from_route, from, to_route, .. } =>
+                {
+                    let q : usize = nodes_demand[routes[from_route][from]];
+                    vec![(from_route, loads[from_route] - q), (to_route, loads[to_route] + q)]
+                }
+
+            Move::Swap { route_1, position_1, route_2, position_2 } =>
+                {
+                    let q_1 : usize = nodes_demand[routes[route_1][position_1]];
+                    let q_2 : usize = nodes_demand[routes[route_2][position_2]];
+                    vec![(route_1, loads[route_1] - q_1 + q_2), (route_2, loads[route_2] - q_2 + q_1)]
+                }
+
+            Move::TwoOptStar { route_1, position_1, route_2, position_2 } =>
+                {
+                    let tail_1 : usize = segment_load(&routes[route_1][position_1..], nodes_demand);
+                    let tail_2 : usize = segment_load(&routes[route_2][position_2..], nodes_demand);
+                    vec![(route_1, loads[route_1] - tail_1 + tail_2), (route_2, loads[route_2] - tail_2 + tail_1)]
+                }
+
+            Move::CrossExchange { route_1, position_1, length_1, route_2, position_2, length_2 } =>
+                {
+                    let q_1 : usize = segment_load(
+                        &routes[route_1][position_1..position_1 + length_1], nodes_demand);
+                    let q_2 : usize = segment_load(
+                        &routes[route_2][position_2..position_2 + length_2], nodes_demand);
+                    vec![(route_1, loads[route_1] - q_1 + q_2), (route_2, loads[route_2] - q_2 + q_1)]
+                }
+
+        }
+
+    }
+
+    /* Routes modified by the move. */
+    pub fn routes(&self) -> Vec<usize>
+    {
+
+        match *self
+        {
+            Move::TwoOpt { route, .. } | Move::OrOpt { route, .. } => vec![route],
+            Move::Relocate { from_route, to_route, .. } => vec![from_route, to_route],
+            Move::Swap { route_1, route_2, .. } |
+            Move::TwoOptStar { route_1, route_2, .. } |
+            Move::CrossExchange { route_1, route_2, .. } => vec![route_1, route_2],
+        }
+
+    }
+
+    /* Check if the routes modified by the move
+     * respect the capacity after the move. */
+    pub fn is_feasible(
+        &self,
+        routes       : &[Vec<usize>],
+        loads        : &[usize],
+        nodes_demand : &[usize],
+        capacity     : usize)
+        -> bool
+    {
+
+        self.loads_after(routes, loads, nodes_demand)
+            .iter()
+            .all(|&(_, load)| load <= capacity)
+
+    }
+
+    /* Customers moved to another route by the move,
+     * as (customer, from route, to route) triples.
+     * Intra-route moves do not move any customer. */
+    pub fn moved_customers(&self, routes : &[Vec<usize>]) -> Vec<(usize, usize, usize)>
+    {
+
+        let segment = |route : usize, from : usize, to : usize, other : usize|
+            routes[route][from..to].iter().map(move |&n| (n, route, other));
+
+        match *self
+        {
+
+            Move::TwoOpt { .. } | Move::OrOpt { .. } => Vec::new(),
+
+            Move::Relocate { from_route, from, to_route, .. } =>
+                vec![(routes[from_route][from], from_route, to_route)],
+
+            Move::Swap { route_1, position_1, route_2, position_2 } =>
+                vec![
+                    (routes[route_1][position_1], route_1, route_2),
+                    (routes[route_2][position_2], route_2, route_1),
+                ],
+
+            Move::TwoOptStar { route_1, position_1, route_2, position_2 } =>
+                segment(route_1, position_1, routes[route_1].len(), route_2)
+                    .chain(segment(route_2, position_2, routes[route_2].len(), route_1))
+                    .collect(),
+
+            Move::CrossExchange { route_1, position_1, length_1, route_2, position_2, length_2 } =>
+                segment(route_1, position_1, position_1 + length_1, route_2)
+                    .chain(segment(route_2, position_2, position_2 + length_2, route_1))
+                    .collect(),
+
+        }
+
+    }
+
+    /* Apply the move to the routes. */
+    pub fn apply(&self, routes : &mut [Vec<usize>])
+    {
+
+        match *self
+        {
+
+            Move::TwoOpt { route, i, j } =>
+                routes[route][i..=j].reverse(),
+
+            Move::OrOpt { route, from, length, to } =>
+                {
+                    let segment : Vec<usize> = routes[route].drain(from..from + length).collect();
+                    routes[route].splice(to..to, segment);
+                }
+
+            Move::Relocate { from_route, from, to_route, to } =>
+                {
+                    let node : usize = routes[from_route].remove(from);
+                    routes[to_route].insert(to, node);
+                }
+
+            Move::Swap { route_1, position_1, route_2, position_2 } =>
+                {
+                    let node_1 : usize = routes[route_1][position_1];
+                    routes[route_1][position_1] = routes[route_2][position_2];
+                    routes[route_2][position_2] = node_1;
+                }
+
+            Move::TwoOptStar { route_1, position_1, route_2, position_2 } =>
+                {
+                    let mut tail_1 : Vec<usize> = routes[route_1].split_off(position_1);
+                    let mut tail_2 : Vec<usize> = routes[route_2].split_off(position_2);
+                    routes[route_1].append(&mut tail_2);
+                    routes[route_2].append(&mut tail_1);
+                }
+
+            Move::CrossExchange { route_1, position_1, length_1, route_2, position_2, length_2 } =>
+                {
+                    let segment_1 : Vec<usize> =
+                        routes[route_1].drain(position_1..position_1 + length_1).collect();
+                    let segment_2 : Vec<usize> =
+                        routes[route_2].drain(position_2..position_2 + length_2).collect();
+                    routes[route_1].splice(position_1..position_1, segment_2);
+                    routes[route_2].splice(position_2..position_2, segment_1);
+                }
+
+        }
+
+    }
+
+}
+
+/* Candidate lists of the granular neighbourhoods:
+ * for each customer, the customers closer than the
+ * threshold, sorted by increasing distance. Only
+ * the moves which insert at least one of these
+ * short edges are explored. */
+pub fn granular_neighbours(
+    distance_matrix : &[Vec<f64>],
+    threshold       : f64)
+    -> Vec< Vec<usize>>
+{
+
+    let node_number : usize           = distance_matrix.len();
+    let mut result  : Vec< Vec<usize>> = vec![Vec::new(); node_number];
+
+    for u in 1..node_number
+    {
+        let mut neighbours : Vec<usize> = (1..node_number)
+            .filter(|&v| v != u && distance_matrix[u][v] <= threshold)
+            .collect();
+        neighbours.sort_by(|&a, &b| distance_matrix[u][a].partial_cmp(&distance_matrix[u][b]).unwrap());
+        result[u] = neighbours;
+    }
+
+    return result;
+
+}
+
+/* Candidate lists made of the k nearest
+ * customers of each customer, sorted by
+ * increasing distance. */
+pub fn nearest_neighbours(
+    distance_matrix : &[Vec<f64>],
+    k               : usize)
+    -> Vec< Vec<usize>>
+{
+
+    let node_number : usize           = distance_matrix.len();
+    let mut result  : Vec< Vec<usize>> = vec![Vec::new(); node_number];
+
+    for u in 1..node_number
+    {
+        let mut neighbours : Vec<usize> = (1..node_number).filter(|&v| v != u).collect();
+        neighbours.sort_by(|&a, &b| distance_matrix[u][a].partial_cmp(&distance_matrix[u][b]).unwrap());
+        neighbours.truncate(k);
+        result[u] = neighbours;
+    }
+
+    return result;
+
+}
+
+/* Route and position of each customer
+ * in the routes. */
+pub fn customer_positions(
+    routes      : &[Vec<usize>],
+    node_number : usize)
+    -> Vec<(usize, usize)>
+{
+
+    let mut result : Vec<(usize, usize)> = vec![(0, 0); node_number];
+
+    for (r, route) in routes.iter().enumerate()
+    {
+        for (p, &n) in route.iter().enumerate()
+        {
+            result[n] = (r, p);
+        }
+    }
+
+    return result;
+
+}
+
+impl Neighbourhood
+{
+
+    /* Enumerate the moves of the neighbourhood
+     * over every route, or couple of routes. */
+    pub fn moves(&self, routes : &[Vec<usize>]) -> Vec<Move>
+    {
+
+        let mut result : Vec<Move> = Vec::new();
+
+        match self
+        {
+
+            Neighbourhood::TwoOpt =>
+                for (route, nodes) in routes.iter().enumerate()
+                {
+                    let route_len : usize = nodes.len();
+                    for i in 0..route_len
+                    {
+                        for j in (i + 1)..route_len
+                        {
+                            result.push(Move::TwoOpt { route, i, j });
+                        }
+                    }
+                },
+
+            /* Segments up to three customers
+             * are moved, as in the original
+             * definition of Or-opt. */
+            Neighbourhood::OrOpt =>
+                for (route, nodes) in routes.iter().enumerate()
+                {
+                    let route_len : usize = nodes.len();
+                    for length in 1..=MAX_SEGMENT_LENGTH.min(route_len)
+                    {
+                        for from in 0..=(route_len - length)
+                        {
+                            for to in 0..=(route_len - length)
+                            {
+                                if to != from
+                                {
+                                    result.push(Move::OrOpt { route, from, length, to });
+                                }
+                            }
+                        }
+                    }
+                },
+
+            Neighbourhood::Relocate =>
+                for from_route in 0..routes.len()
+                {
+                    for to_route in 0..routes.len()
+                    {
+                        if from_route == to_route
+                        {
+                            continue;
+                        }
+                        for from in 0..routes[from_route].len()
+                        {
+                            for to in 0..=routes[to_route].len()
+                            {
+                                result.push(Move::Relocate { from_route, from, to_route, to });
+                            }
+                        }
+                    }
+                },
+
+            Neighbourhood::Swap =>
+                for route_1 in 0..routes.len()
+                {
+                    for route_2 in (route_1 + 1)..routes.len()
+                    {
+                        for position_1 in 0..routes[route_1].len()
+                        {
+                            for position_2 in 0..routes[route_2].len()
+                            {
+                                result.push(Move::Swap { route_1, position_1, route_2, position_2 });
+                            }
+                        }
+                    }
+                },
+
+            /* Exchanging the whole routes, or nothing,
+             * leaves the solution unchanged. */
+            Neighbourhood::TwoOptStar =>
+                for route_1 in 0..routes.len()
+                {
+                    for route_2 in (route_1 + 1)..routes.len()
+                    {
+                        let len_1 : usize = routes[route_1].len();
+                        let len_2 : usize = routes[route_2].len();
+                        for position_1 in 0..=len_1
+                        {
+                            for position_2 in 0..=len_2
+                            {
+                                if (position_1 == 0 && position_2 == 0) ||
+                                    (position_1 == len_1 && position_2 == len_2)
+                                {
+                                    continue;
+                                }
+                                result.push(Move::TwoOptStar { route_1, position_1, route_2, position_2 });
+                            }
+                        }
+                    }
+                },
+
+            /* Exchanges of at most one customer per route
+             * are already covered by relocate and swap. */
+            Neighbourhood::CrossExchange =>
+                for route_1 in 0..routes.len()
+                {
+                    for route_2 in (route_1 + 1)..routes.len()
+                    {
+                        let len_1 : usize = routes[route_1].len();
+                        let len_2 : usize = routes[route_2].len();
+                        for length_1 in 0..=MAX_SEGMENT_LENGTH.min(len_1)
+                        {
+                            for length_2 in 0..=MAX_SEGMENT_LENGTH.min(len_2)
+                            {
+                                if length_1 <= 1 && length_2 <= 1
+                                {
+                                    continue;
+                                }
+                                for position_1 in 0..=(len_1 - length_1)
+                                {
+                                    for position_2 in 0..=(len_2 - length_2)
+                                    {
+                                        result.push(Move::CrossExchange {
+                                            route_1, position_1, length_1,
+                                            route_2, position_2, length_2,
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+
+        }
+
+        return result;
+
+    }
+
+
+    /* Enumerate the moves of the neighbourhood which
+     * make each customer u adjacent to one of its
+     * candidate neighbours v. Neighbourhoods with no
+     * granular definition are fully enumerated. */
+    pub fn granular_moves(
+        &self,
+        routes     : &[Vec<usize>],
+        neighbours : &[Vec<usize>])
+        -> Vec<Move>
+    {
+
+        let positions : Vec<(usize, usize)> = customer_positions(routes, neighbours.len());
+
+        match self
+        {
+            Neighbourhood::OrOpt | Neighbourhood::CrossExchange => self.moves(routes),
+            _ => (1..neighbours.len())
+                .flat_map(|u| self.customer_granular_moves(routes, &positions, u, &neighbours[u]))
+                .collect(),
+        }
+
+    }
+
+    /* Granular moves of a single customer u,
+     * given the positions of the customers. */
+    pub fn customer_granular_moves(
+        &self,
+        routes     : &[Vec<usize>],
+        positions  : &[(usize, usize)],
+        u          : usize,
+        neighbours : &[usize])
+        -> Vec<Move>
+    {
+
+        let (route, position) : (usize, usize) = positions[u];
+        let mut result        : Vec<Move>      = Vec::new();
+
+        for &v in neighbours
+        {
+
+            let (v_route, v_position) : (usize, usize) = positions[v];
+
+            match self
+            {
+
+                /* Reverse the path from the successor of u
+                 * to v, or from the successor of v to u,
+                 * in the same route. */
+                Neighbourhood::TwoOpt if v_route == route =>
+                    if position + 1 < v_position
+                    {
+                        result.push(Move::TwoOpt { route, i : position + 1, j : v_position });
+                    }
+                    else if v_position + 1 < position
+                    {
+                        result.push(Move::TwoOpt { route, i : v_position + 1, j : position });
+                    },
+
+                /* Insert u just before or just after v,
+                 * in the same route as an Or-opt move. */
+                Neighbourhood::Relocate =>
+                    if v_route != route
+                    {
+                        for to in [v_position, v_position + 1]
+                        {
+                            result.push(Move::Relocate {
+                                from_route : route, from : position, to_route : v_route, to,
+                            });
+                        }
+                    }
+                    else
+                    {
+                        let reduced_position : usize = if v_position < position { v_position } else { v_position - 1 };
+                        for to in [reduced_position, reduced_position + 1]
+                        {
+                            if to != position
+                            {
+                                result.push(Move::OrOpt { route, from : position, length : 1, to });
+                            }
+                        }
+                    },
+
+                /* Put u in place of the predecessor
+                 * or of the successor of v. */
+                Neighbourhood::Swap if v_route != route =>
+                    {
+                        if v_position > 0
+                        {
+                            result.push(Move::Swap {
+                                route_1 : route, position_1 : position,
+                                route_2 : v_route, position_2 : v_position - 1,
+                            });
+                        }
+                        if v_position + 1 < routes[v_route].len()
+                        {
+                            result.push(Move::Swap {
+                                route_1 : route, position_1 : position,
+                                route_2 : v_route, position_2 : v_position + 1,
+                            });
+                        }
+                    },
+
+                /* Link u to v, exchanging the
+                 * tails of the two routes. */
+                Neighbourhood::TwoOptStar if v_route != route =>
+                    result.push(Move::TwoOptStar {
+                        route_1 : route, position_1 : position + 1,
+                        route_2 : v_route, position_2 : v_position,
+                    }),
+
+                _ => (),
+
+            }
+
+        }
+
+        return result;
+
+    }
+
+
+    /* Draw a random move of the neighbourhood, None
+     * if the routes admit no move of this kind. */
+    pub fn random_move(&self, routes : &[Vec<usize>], rng : &mut dyn RngCore) -> Option<Move>
+    {
+
+        /* Routes with at least the given number of customers. */
+        let routes_with = |length : usize| -> Vec<usize>
+            {
+                (0..routes.len()).filter(|&r| routes[r].len() >= length).collect()
+            };
+
+        /* Two distinct routes, the first with at least
+         * the given number of customers. */
+        let two_routes = |length_1 : usize, length_2 : usize, rng : &mut dyn RngCore| -> Option<(usize, usize)>
+            {
+                let route_1 : usize      = *routes_with(length_1).choose(rng)?;
+                let others  : Vec<usize> = routes_with(length_2).into_iter().filter(|&r| r != route_1).collect();
+                let route_2 : usize      = *others.choose(rng)?;
+                Some((route_1, route_2))
+            };
+
+        let result : Move = match self
+        {
+
+            Neighbourhood::TwoOpt =>
+                {
+                    let route : usize = *routes_with(2).choose(rng)?;
+                    let i     : usize = rng.gen_range(0..routes[route].len() - 1);
+                    let j     : usize = rng.gen_range(i + 1..routes[route].len());
+                    Move::TwoOpt { route, i, j }
+                }
+
+            Neighbourhood::OrOpt =>
+                {
+                    let route  : usize = *routes_with(2).choose(rng)?;
+                    let length : usize = rng.gen_range(1..=MAX_SEGMENT_LENGTH.min(routes[route].len() - 1));
+                    let last   : usize = routes[route].len() - length;
+                    let from   : usize = rng.gen_range(0..=last);
+                    let to     : usize = rng.gen_range(0..=last);
+                    if to == from
+                    {
+                        return None;
+                    }
+                    Move::OrOpt { route, from, length, to }
+                }
+
+            Neighbourhood::Relocate =>
+                {
+                    let (from_route, to_route) = two_routes(1, 0, rng)?;
+                    Move::Relocate {
+                        from_route,
+                        from : rng.gen_range(0..routes[from_route].len()),
+                        to_route,
+                        to   : rng.gen_range(0..=routes[to_route].len()),
+                    }
+                }
+
+            Neighbourhood::Swap =>
+                {
+                    let (route_1, route_2) = two_routes(1, 1, rng)?;
+                    Move::Swap {
+                        route_1,
+                        position_1 : rng.gen_range(0..routes[route_1].len()),
+                        route_2,
+                        position_2 : rng.gen_range(0..routes[route_2].len()),
+                    }
+                }
+
+            Neighbourhood::TwoOptStar =>
+                {
+                    let (route_1, route_2) = two_routes(0, 0, rng)?;
+                    let position_1 : usize = rng.gen_range(0..=routes[route_1].len());
+                    let position_2 : usize = rng.gen_range(0..=routes[route_2].len());
+                    if (position_1 == 0 && position_2 == 0) ||
+                        (position_1 == routes[route_1].len() && position_2 == routes[route_2].len())
+                    {
+                        return None;
+                    }
+                    Move::TwoOptStar { route_1, position_1, route_2, position_2 }
+                }
+
+            Neighbourhood::CrossExchange =>
+                {
+                    let (route_1, route_2) = two_routes(0, 0, rng)?;
+                    let length_1 : usize = rng.gen_range(0..=MAX_SEGMENT_LENGTH.min(routes[route_1].len()));
+                    let length_2 : usize = rng.gen_range(0..=MAX_SEGMENT_LENGTH.min(routes[route_2].len()));
+                    if length_1 <= 1 && length_2 <= 1
+                    {
+                        return None;
+                    }
+                    Move::CrossExchange {
+                        route_1,
+                        position_1 : rng.gen_range(0..=routes[route_1].len() - length_1),
+                        length_1,
+                        route_2,
+                        position_2 : rng.gen_range(0..=routes[route_2].len() - length_2),
+                        length_2,
+                    }
+                }
+
+        };
+
+        return Some(result);
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::algorithm_interface::compute_routes_cost;
+    use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+    use crate::model::{GraphInstance, read_test_instance};
+
+    #[test]
+    fn delta_matches_recomputed_cost()
+    {
+
+        let graph           : GraphInstance   = read_test_instance("gr-n17-k3");
+        let distance_matrix : Vec< Vec<f64>>  = LocalSearchInstanceTrait::get_distance_matrix(&graph);
+        let routes          : Vec< Vec<usize>> = vec![
+            vec![1, 2, 3, 4, 5],
+            vec![6, 7, 8],
+            vec![9],
+            vec![10, 11, 12, 13, 14, 15, 16],
+            vec![],
+        ];
+        let cost            : f64              = compute_routes_cost(&distance_matrix, &routes);
+
+        let neighbourhoods : Vec<Neighbourhood> = vec![
+            Neighbourhood::TwoOpt,
+            Neighbourhood::OrOpt,
+            Neighbourhood::Relocate,
+            Neighbourhood::Swap,
+            Neighbourhood::TwoOptStar,
+            Neighbourhood::CrossExchange,
+        ];
+
+        for neighbourhood in neighbourhoods
+        {
+
+            let moves : Vec<Move> = neighbourhood.moves(&routes);
+            assert!(!moves.is_empty(), "{:?} has no moves", neighbourhood);
+
+            for m in moves
+            {
+                let mut moved : Vec< Vec<usize>> = routes.clone();
+                m.apply(&mut moved);
+                let expected : f64 = compute_routes_cost(&distance_matrix, &moved) - cost;
+                assert!((m.delta(&distance_matrix, &routes) - expected).abs() < 1e-6, "{:?}", m);
+            }
+
+        }
+
+    }
+
+}
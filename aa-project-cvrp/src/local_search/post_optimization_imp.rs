@@ -0,0 +1,50 @@
+use crate::algorithm_interface::{CVRPSolver, CVRPImprover, SolveControl};
+
+/* A CVRPSolver which applies a sequence of
+ * improvement procedures, in the given order,
+ * to the routes computed by another solver. */
+pub struct PostOptimizationSolver<'a>
+{
+
+    pub solver    : &'a dyn CVRPSolver,
+
+    pub improvers : Vec<&'a dyn CVRPImprover>,
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for PostOptimizationSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    /* The improvers end once the
+     * search is stopped. */
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let mut routes : Vec< Vec<usize>> = self.solver.solve_with_control(control);
+
+        for improver in &self.improvers
+        {
+            routes = improver.improve(routes, control);
+            control.report(&routes);
+        }
+
+        return routes;
+
+    }
+
+    fn get_seed(&self) -> Option<u64>
+    {
+
+        self.solver.get_seed()
+
+    }
+
+}
@@ -1,21 +1,82 @@
 use std::fs;
+use std::env;
+use std::process;
 use std::time::{Instant, Duration};
 
 use crate::tsplib_parser::problem_instance::TSPInstance;
 use crate::tsplib_parser::parser::parse;
-use crate::algorithm_interface::CVRPSolver;
-use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+use crate::algorithm_interface::{CVRPSolver, SolveControl, compute_routes_cost};
+use crate::savings_algorithm::savings_algorithm_imp::{SavingsSolver, SavingsParameters, SavingsVariant};
 use crate::sweep_algorithm::sweep_algorithm_imp::SweepSolver;
+use crate::route_first_algorithm::route_first_algorithm_imp::RouteFirstSolver;
+use crate::construction::nearest_neighbour_imp::NearestNeighbourSolver;
+use crate::construction::insertion_imp::{InsertionSolver, InsertionCriterion};
+use crate::fisher_jaikumar_algorithm::fisher_jaikumar_algorithm_imp::FisherJaikumarSolver;
+use crate::local_search::intra_route_imp::IntraRouteOptimizer;
+use crate::local_search::inter_route_imp::InterRouteLocalSearch;
+use crate::local_search::post_optimization_imp::PostOptimizationSolver;
+use crate::tabu_search::tabu_search_imp::GranularTabuSearch;
+use crate::alns::alns_imp::AlnsSolver;
+use crate::hybrid_genetic_search::hgs_imp::HgsSolver;
+use crate::simulated_annealing::simulated_annealing_imp::{SimulatedAnnealingSolver, CoolingSchedule};
+use crate::ant_colony::ant_colony_imp::AntColonySolver;
+use crate::iterated_local_search::iterated_local_search_imp::{IteratedLocalSearch, AcceptanceCriterion};
+use crate::iterated_local_search::perturbation::Perturbation;
+use crate::variable_neighbourhood_search::variable_neighbourhood_descent_imp::VariableNeighbourhoodDescent;
+use crate::variable_neighbourhood_search::variable_neighbourhood_search_imp::VariableNeighbourhoodSearch;
+use crate::variable_neighbourhood_search::config_parser::{VnsConfig, parse_vns_config};
+use crate::record_to_record::record_to_record_imp::RecordToRecordSolver;
+use crate::exact_algorithm::exact_algorithm_imp::ExactSolver;
+use crate::route_pool::route_pool_imp::RoutePoolSolver;
+use crate::milp_export::milp_model::MilpModel;
+use crate::milp_export::vehicle_flow::{two_index_model, CapacityFormulation};
+use crate::milp_export::lp_writer::write_lp;
+use crate::milp_export::mps_writer::write_mps;
+use crate::parallel::multi_start::{MultiStartSolver, MultiStartStatistics};
+use crate::parallel::island_model::IslandModelSolver;
+use crate::bounds::bounds_instance_trait::BoundsInstanceTrait;
+use crate::bounds::vehicle_bound::{vehicles_lower_bound, first_fit_decreasing};
+use crate::bounds::k_tree_bound::k_tree_bound;
 
 mod savings_algorithm;
 mod sweep_algorithm;
 mod algorithm_interface;
+mod route_first_algorithm;
+mod construction;
+mod fisher_jaikumar_algorithm;
+mod local_search;
+mod tabu_search;
+mod alns;
+mod hybrid_genetic_search;
+mod simulated_annealing;
+mod ant_colony;
+mod iterated_local_search;
+mod variable_neighbourhood_search;
+mod record_to_record;
+mod exact_algorithm;
+mod route_pool;
+mod milp_export;
+mod parallel;
+mod bounds;
 mod model;
 mod tsplib_parser;
 
 
 fn main() {
 
+    /* The seed of the randomized solvers can be passed
+     * as first argument, in order to reproduce a
+     * previous run. Otherwise it is drawn at random. */
+    let seed : u64 = match env::args().nth(1)
+    {
+        Some(s) => s.parse::<u64>().expect("The seed should be an unsigned integer"),
+        _ => rand::random(),
+    };
+
+    /* The MILP models of the instances are exported
+     * to the directory passed as second argument. */
+    let export_directory : Option<String> = env::args().nth(2);
+
     let mut contents: Vec<&str> = Vec::new();
     contents.push("./input/att-n48-k4.vrp");
     contents.push("./input/bayg-n29-k4.vrp");
@@ -34,6 +95,19 @@ fn main() {
     contents.push("./input/ulysses-n16-k3.vrp");
     contents.push("./input/ulysses-n22-k4.vrp");
 
+    /* Configuration of the variable
+     * neighbourhood search. */
+    let vns_config : VnsConfig = match parse_vns_config(
+        &fs::read_to_string("./config/vns.cfg").expect("Something went wrong reading the file"))
+    {
+        Ok(config) => config,
+        Err(message) =>
+        {
+            eprintln!("Invalid configuration ./config/vns.cfg: {}", message);
+            process::exit(1);
+        },
+    };
+
     for i in 0..contents.len()
     {
 
@@ -47,41 +121,334 @@ fn main() {
             instance
         };
 
-        /* Initialize the two solvers and compute the routes. */
-        let saving_solver : SavingsSolver = SavingsSolver {
-            instance: &graph,
+        println!();
+        println!("Instance: {}", graph.instance.specification.name);
+
+        /* Initialize the solvers and compute the routes. */
+        let saving_solver : SavingsSolver = SavingsSolver::new(&graph);
+
+        /* Lower bounds on the number of vehicles and on
+         * the cost of the routes, over any number of
+         * routes from the lower bound. The models are
+         * exported with the vehicles of the instance,
+         * or else their lower bound. */
+        let distance_matrix : Vec< Vec<f64>> = BoundsInstanceTrait::get_distance_matrix(&graph);
+        let nodes_demand    : Vec<usize>     = BoundsInstanceTrait::get_nodes_demand(&graph);
+        let capacity        : usize          = BoundsInstanceTrait::get_capacity(&graph);
+        let min_vehicles    : usize          = vehicles_lower_bound(&nodes_demand, capacity);
+        let vehicles        : usize          = BoundsInstanceTrait::get_vehicles_number(&graph).unwrap_or(0).max(min_vehicles);
+        let lower_bound     : f64            = k_tree_bound(
+            &distance_matrix, min_vehicles, compute_routes_cost(&distance_matrix, &saving_solver.solve()));
+        println!("Vehicles lower bound = {}, First-fit decreasing vehicles = {}, K-tree lower bound = {:.1}",
+                 min_vehicles,
+                 first_fit_decreasing(&nodes_demand, capacity).len(),
+                 lower_bound);
+
+        if let Some(directory) = &export_directory
+        {
+            export_models(&graph, vehicles, directory);
+        }
+
+        run_solver("Savings Algorithm", &saving_solver, &graph, lower_bound);
+
+        let parametric_saving_solver : SavingsSolver = SavingsSolver {
+            parameters_grid : SavingsParameters::default_grid(),
+            ..SavingsSolver::new(&graph)
         };
-        let savings_alg_start        : Instant         = Instant::now();
-        let saving_routes            : Vec<Vec<usize>> = saving_solver.solve();
-        let savings_alg_elapsed_time : Duration        = savings_alg_start.elapsed();
+        run_solver("Parametric Savings Algorithm", &parametric_saving_solver, &graph, lower_bound);
+
+        let sequential_saving_solver : SavingsSolver = SavingsSolver {
+            variant : SavingsVariant::Sequential,
+            ..SavingsSolver::new(&graph)
+        };
+        run_solver("Sequential Savings Algorithm", &sequential_saving_solver, &graph, lower_bound);
+
+        let granular_saving_solver : SavingsSolver = SavingsSolver {
+            variant : SavingsVariant::Granular { neighbours : 20 },
+            ..SavingsSolver::new(&graph)
+        };
+        run_solver("Granular Savings Algorithm", &granular_saving_solver, &graph, lower_bound);
 
         let sweep_solver : SweepSolver = SweepSolver {
             instance: &graph,
         };
-        let sweep_alg_start        : Instant         = Instant::now();
-        let sweep_routes           : Vec<Vec<usize>> = sweep_solver.solve();
-        let sweep_alg_elapsed_time : Duration        = sweep_alg_start.elapsed();
+        run_solver("Sweep Algorithm", &sweep_solver, &graph, lower_bound);
+
+        let route_first_solver : RouteFirstSolver = RouteFirstSolver {
+            instance: &graph,
+        };
+        run_solver("Route-first Cluster-second Algorithm", &route_first_solver, &graph, lower_bound);
 
-        /* Compute the cost of the routes. */
-        let savings_routes_cost : f64 =
-            model::compute_cost_of_routes(graph.instance.clone(), saving_routes);
+        let fisher_jaikumar_solver : FisherJaikumarSolver = FisherJaikumarSolver {
+            instance: &graph,
+        };
+        run_solver("Fisher-Jaikumar Algorithm", &fisher_jaikumar_solver, &graph, lower_bound);
 
-        let sweep_routes_cost   : f64 =
-            model::compute_cost_of_routes(graph.instance.clone(), sweep_routes);
+        let nearest_neighbour_solver : NearestNeighbourSolver = NearestNeighbourSolver {
+            instance: &graph,
+        };
+        run_solver("Nearest Neighbour Heuristic", &nearest_neighbour_solver, &graph, lower_bound);
+
+        let insertion_criteria : Vec<(&str, InsertionCriterion)> = vec![
+            ("Cheapest Insertion Heuristic", InsertionCriterion::Cheapest),
+            ("Farthest Insertion Heuristic", InsertionCriterion::Farthest),
+            ("Regret-3 Insertion Heuristic", InsertionCriterion::Regret(3)),
+        ];
+        for (name, criterion) in insertion_criteria
+        {
+            let insertion_solver : InsertionSolver = InsertionSolver {
+                instance: &graph,
+                criterion,
+            };
+            run_solver(name, &insertion_solver, &graph, lower_bound);
+        }
+
+        /* Improve the routes of both the solvers with
+         * the inter-route local search, followed by
+         * the intra-route optimization. */
+        let inter_route_local_search : InterRouteLocalSearch = InterRouteLocalSearch::new(&graph);
+        let intra_route_optimizer    : IntraRouteOptimizer   = IntraRouteOptimizer {
+            instance: &graph,
+        };
+
+        let optimized_savings_solver : PostOptimizationSolver = PostOptimizationSolver {
+            solver    : &saving_solver,
+            improvers : vec![&inter_route_local_search, &intra_route_optimizer],
+        };
+        run_solver("Savings Algorithm + Local search", &optimized_savings_solver, &graph, lower_bound);
+
+        let optimized_sweep_solver : PostOptimizationSolver = PostOptimizationSolver {
+            solver    : &sweep_solver,
+            improvers : vec![&inter_route_local_search, &intra_route_optimizer],
+        };
+        run_solver("Sweep Algorithm + Local search", &optimized_sweep_solver, &graph, lower_bound);
+
+        /* Escape the local optima of both the
+         * solvers with the granular tabu search. */
+        let savings_tabu_search : GranularTabuSearch = GranularTabuSearch::new(&graph, &saving_solver, seed);
+        run_solver("Savings Algorithm + Tabu search", &savings_tabu_search, &graph, lower_bound);
+
+        let sweep_tabu_search : GranularTabuSearch = GranularTabuSearch::new(&graph, &sweep_solver, seed);
+        run_solver("Sweep Algorithm + Tabu search", &sweep_tabu_search, &graph, lower_bound);
+
+        let alns_solver : AlnsSolver = AlnsSolver::new(&graph, &saving_solver, seed);
+        run_solver("Savings Algorithm + ALNS", &alns_solver, &graph, lower_bound);
+
+        let hgs_solver : HgsSolver = HgsSolver::new(&graph, Duration::from_secs(1), seed);
+        run_solver("Hybrid Genetic Search", &hgs_solver, &graph, lower_bound);
+
+        /* The hybrid genetic search as an anytime solver,
+         * stopped at a deadline of half a second, printing
+         * each new best solution when it is found. */
+        let routes_cost    = |routes : &Vec< Vec<usize>>| compute_routes_cost(&distance_matrix, routes);
+        let print_new_best = |_ : &Vec< Vec<usize>>, cost : f64, elapsed : Duration|
+            println!("New best solution: cost = {}, elapsed = {}", cost, elapsed.as_micros());
+        let anytime_control    : SolveControl = SolveControl::new(
+            Some(&routes_cost),
+            Some(Instant::now() + Duration::from_millis(500)),
+            None,
+            Some(&print_new_best));
+        let anytime_hgs_solver : HgsSolver    = HgsSolver::new(&graph, Duration::from_secs(10), seed);
+        run_solver_with_control(
+            "Hybrid Genetic Search, anytime", &anytime_hgs_solver, &anytime_control, &graph, lower_bound);
+
+        let island_model_solver : IslandModelSolver = IslandModelSolver {
+            islands : 4,
+            ..IslandModelSolver::new(&graph, Duration::from_secs(1), seed)
+        };
+        run_solver("Hybrid Genetic Search, island model", &island_model_solver, &graph, lower_bound);
+
+        let cooling_schedules : Vec<(&str, CoolingSchedule)> = vec![
+            ("Simulated Annealing, geometric cooling", CoolingSchedule::Geometric { alpha : 0.99997 }),
+            ("Simulated Annealing, linear cooling", CoolingSchedule::Linear),
+            ("Simulated Annealing, reheating", CoolingSchedule::Reheating { alpha : 0.99995, patience : 50000, fraction : 0.1 }),
+        ];
+        for (name, cooling_schedule) in cooling_schedules
+        {
+            let annealing_solver : SimulatedAnnealingSolver = SimulatedAnnealingSolver {
+                cooling_schedule,
+                ..SimulatedAnnealingSolver::new(&graph, &saving_solver, seed)
+            };
+            run_solver(name, &annealing_solver, &graph, lower_bound);
+        }
+
+        let ant_colony_solver : AntColonySolver = AntColonySolver::new(&graph, seed);
+        run_solver("Ant Colony Optimization", &ant_colony_solver, &graph, lower_bound);
+
+        /* Iterated local search from both the savings and
+         * the sweep algorithms, with the local searches
+         * used above. */
+        let iterated_local_searches : Vec<(&str, &dyn CVRPSolver, Perturbation, AcceptanceCriterion)> = vec![
+            ("Savings Algorithm + ILS, double-bridge", &saving_solver,
+                Perturbation::DoubleBridge, AcceptanceCriterion::BetterOnly),
+            ("Savings Algorithm + ILS, segment shuffle", &saving_solver,
+                Perturbation::SegmentShuffle { routes : 3, length : 3 }, AcceptanceCriterion::RestartAfter(30)),
+            ("Sweep Algorithm + ILS, random walk", &sweep_solver,
+                Perturbation::SegmentShuffle { routes : 2, length : 2 }, AcceptanceCriterion::RandomWalk),
+        ];
+        for (name, initial_solver, perturbation, acceptance) in iterated_local_searches
+        {
+            let iterated_local_search : IteratedLocalSearch = IteratedLocalSearch {
+                perturbation,
+                acceptance,
+                ..IteratedLocalSearch::new(
+                    &graph, initial_solver, vec![&inter_route_local_search, &intra_route_optimizer], seed)
+            };
+            run_solver(name, &iterated_local_search, &graph, lower_bound);
+        }
+
+        /* The iterated local search from the savings
+         * algorithm with eight seeds, run in parallel,
+         * with the statistics of the runs. */
+        let seeded_searches    : Vec<IteratedLocalSearch> = (0..8)
+            .map(|i| IteratedLocalSearch::new(
+                &graph, &saving_solver, vec![&inter_route_local_search, &intra_route_optimizer], seed.wrapping_add(i)))
+            .collect();
+        let multi_start_solver : MultiStartSolver         = MultiStartSolver::new(
+            &graph, seeded_searches.iter().map(|s| s as &dyn CVRPSolver).collect());
+        let multi_start_start  : Instant                  = Instant::now();
+        let (_, statistics)    : (Vec< Vec<usize>>, MultiStartStatistics) =
+            multi_start_solver.run(&SolveControl::unlimited()).expect("The multi-start has no solvers");
+        println!("Savings Algorithm + ILS, parallel multi-start results: ");
+        println!("Routes cost = {best}, Time required = {time}, Sequential time = {total}, Runs = {runs}, \
+                  Mean cost = {mean:.1}, Standard deviation = {deviation:.1}, Worst cost = {worst}",
+                 best      = statistics.best_cost(),
+                 time      = multi_start_start.elapsed().as_micros(),
+                 total     = statistics.total_time().as_micros(),
+                 runs      = statistics.costs.len(),
+                 mean      = statistics.mean_cost(),
+                 deviation = statistics.standard_deviation(),
+                 worst     = statistics.worst_cost());
+
+        let variable_neighbourhood_descent : VariableNeighbourhoodDescent = VariableNeighbourhoodDescent::new(&graph);
+        let descent_savings_solver         : PostOptimizationSolver       = PostOptimizationSolver {
+            solver    : &saving_solver,
+            improvers : vec![&variable_neighbourhood_descent],
+        };
+        run_solver("Savings Algorithm + VND", &descent_savings_solver, &graph, lower_bound);
+
+        let savings_vns : VariableNeighbourhoodSearch = VariableNeighbourhoodSearch::new(&graph, &saving_solver, seed);
+        run_solver("Savings Algorithm + VNS", &savings_vns, &graph, lower_bound);
+
+        let sweep_vns : VariableNeighbourhoodSearch =
+            VariableNeighbourhoodSearch::from_config(&graph, &sweep_solver, &vns_config, seed);
+        run_solver("Sweep Algorithm + VNS, configured", &sweep_vns, &graph, lower_bound);
+
+        let record_to_record_solver : RecordToRecordSolver = RecordToRecordSolver::new(&graph, &saving_solver);
+        run_solver("Savings Algorithm + Record-to-record travel", &record_to_record_solver, &graph, lower_bound);
+
+        let threshold_accepting_solver : RecordToRecordSolver =
+            RecordToRecordSolver::threshold_accepting(&graph, &saving_solver, 0.02);
+        run_solver("Savings Algorithm + Threshold accepting", &threshold_accepting_solver, &graph, lower_bound);
+
+        /* Set partitioning over the routes of the
+         * construction heuristics, improved by the
+         * variable neighbourhood descent, and of the
+         * record-to-record travel. */
+        let route_pool_solver : RoutePoolSolver = RoutePoolSolver::new(
+            &graph,
+            vec![&saving_solver, &parametric_saving_solver, &sequential_saving_solver, &granular_saving_solver,
+                 &sweep_solver, &route_first_solver, &fisher_jaikumar_solver, &nearest_neighbour_solver,
+                 &record_to_record_solver],
+            vec![&variable_neighbourhood_descent]);
+        run_solver("Route pool + Set partitioning", &route_pool_solver, &graph, lower_bound);
+
+        /* The optimal routes, for the
+         * smallest instances only. */
+        if graph.instance.specification.dimension <= 24
+        {
+            let exact_solver : ExactSolver = ExactSolver::new(&graph, &saving_solver);
+            run_solver("Exact Algorithm", &exact_solver, &graph, lower_bound);
+        }
 
-        /* Print the result. */
-        println!();
-        println!("Instance: {}", graph.instance.specification.name.clone());
-        println!("Savings Algorithm results: ");
-        println!("Routes cost = {cost}, Time required = {time}",
-                 cost = savings_routes_cost,
-                 time = savings_alg_elapsed_time.as_micros());
-        println!("Sweep Algorithm results: ");
-        println!("Routes cost = {cost}, Time required = {time}",
-                 cost = sweep_routes_cost,
-                 time = sweep_alg_elapsed_time.as_micros());
         println!(" - - - - - - - - - - - - - - - ");
 
     }
 
 }
+
+/* Run the solver over the instance graph, without
+ * limits other than its own stopping criterion. */
+fn run_solver(
+    name        : &str,
+    solver      : &dyn CVRPSolver,
+    graph       : &model::GraphInstance,
+    lower_bound : f64)
+{
+
+    let control : SolveControl = SolveControl::unlimited();
+
+    run_solver_with_control(name, solver, &control, graph, lower_bound);
+
+}
+
+/* Run the solver over the instance graph within the
+ * limits of the control, then print the cost of the
+ * routes, the time required, the seed, for randomized
+ * solvers, and the gap from the lower bound on the cost. */
+fn run_solver_with_control(
+    name        : &str,
+    solver      : &dyn CVRPSolver,
+    control     : &SolveControl,
+    graph       : &model::GraphInstance,
+    lower_bound : f64)
+{
+
+    let alg_start        : Instant         = Instant::now();
+    let routes           : Vec<Vec<usize>> = solver.solve_with_control(control);
+    let alg_elapsed_time : Duration        = alg_start.elapsed();
+
+    /* Compute the cost of the routes. */
+    let routes_cost : f64 =
+        model::compute_cost_of_routes(graph.instance.clone(), routes);
+
+    /* Gap from the lower bound, in percentage. */
+    let gap : f64 = 100.0 * (routes_cost - lower_bound) / lower_bound;
+
+    /* Print the result. */
+    println!("{} results: ", name);
+    match solver.get_seed()
+    {
+        Some(seed) =>
+            println!("Routes cost = {cost}, Time required = {time}, Seed = {seed}, Gap = {gap:.2}%",
+                     cost = routes_cost,
+                     time = alg_elapsed_time.as_micros(),
+                     seed = seed,
+                     gap  = gap),
+        None =>
+            println!("Routes cost = {cost}, Time required = {time}, Gap = {gap:.2}%",
+                     cost = routes_cost,
+                     time = alg_elapsed_time.as_micros(),
+                     gap  = gap),
+    }
+
+}
+
+/* Write the two-index vehicle flow models of the
+ * instance, with both capacity formulations, in
+ * both the LP and the MPS formats. */
+fn export_models(graph : &model::GraphInstance, vehicles : usize, directory : &str)
+{
+
+    let formulations : Vec<(&str, CapacityFormulation)> = vec![
+        ("mtz", CapacityFormulation::MillerTuckerZemlin),
+        ("scf", CapacityFormulation::SingleCommodityFlow),
+    ];
+
+    for (suffix, formulation) in formulations
+    {
+
+        let model : MilpModel = two_index_model(graph, vehicles, formulation);
+        let path  : String    = format!("{}/{}-{}", directory, graph.instance.specification.name, suffix);
+
+        let mut lp_file : fs::File = fs::File::create(format!("{}.lp", path))
+            .expect("Something went wrong creating the file");
+        write_lp(&model, &mut lp_file).expect("Something went wrong writing the file");
+
+        let mut mps_file : fs::File = fs::File::create(format!("{}.mps", path))
+            .expect("Something went wrong creating the file");
+        write_mps(&model, &mut mps_file).expect("Something went wrong writing the file");
+
+    }
+
+}
@@ -0,0 +1,182 @@
+use std::io::{self, Write};
+use crate::milp_export::milp_model::{MilpModel, VariableType, Sense};
+
+/* Terms written on each line, keeping the lines
+ * well below the length accepted by the readers
+ * of the LP format. */
+const TERMS_PER_LINE : usize = 8;
+
+/* Write the model in the CPLEX LP format. */
+pub fn write_lp(model : &MilpModel, out : &mut dyn Write) -> io::Result<()>
+{
+
+    writeln!(out, "\\ Problem name: {}", model.name)?;
+    writeln!(out)?;
+
+    writeln!(out, "Minimize")?;
+    let objective : Vec<(usize, f64)> = model.objective.iter()
+        .enumerate()
+        .filter(|&(_, &c)| c != 0.0)
+        .map(|(v, &c)| (v, c))
+        .collect();
+    write_expression(model, out, " obj:", &objective)?;
+    writeln!(out)?;
+
+    writeln!(out, "Subject To")?;
+    for constraint in &model.constraints
+    {
+        write_expression(model, out, &format!(" {}:", constraint.name), &constraint.terms)?;
+        let sense : &str = match constraint.sense
+        {
+            Sense::LessEqual    => "<=",
+            Sense::GreaterEqual => ">=",
+            Sense::Equal        => "=",
+        };
+        writeln!(out, "   {} {}", sense, constraint.rhs)?;
+    }
+
+    /* The variables are non negative by default, and
+     * the bounds of the binaries are implied. */
+    writeln!(out, "Bounds")?;
+    for variable in model.variables.iter().filter(|v| v.variable_type != VariableType::Binary)
+    {
+        match variable.upper
+        {
+            Some(upper) => writeln!(out, " {} <= {} <= {}", variable.lower, variable.name, upper)?,
+            None if variable.lower != 0.0 => writeln!(out, " {} >= {}", variable.name, variable.lower)?,
+            None => (),
+        }
+    }
+
+    let sections : Vec<(&str, VariableType)> = vec![
+        ("Binaries", VariableType::Binary),
+        ("Generals", VariableType::Integer),
+    ];
+    for (section, variable_type) in sections
+    {
+        let names : Vec<&str> = model.variables.iter()
+            .filter(|v| v.variable_type == variable_type)
+            .map(|v| v.name.as_str())
+            .collect();
+        if !names.is_empty()
+        {
+            writeln!(out, "{}", section)?;
+            for line in names.chunks(TERMS_PER_LINE)
+            {
+                writeln!(out, " {}", line.join(" "))?;
+            }
+        }
+    }
+
+    writeln!(out, "End")?;
+
+    return Ok(());
+
+}
+
+/* Write the label and the linear expression,
+ * splitting it over several lines. */
+fn write_expression(
+    model : &MilpModel,
+    out   : &mut dyn Write,
+    label : &str,
+    terms : &[(usize, f64)])
+    -> io::Result<()>
+{
+
+    write!(out, "{}", label)?;
+
+    if terms.is_empty()
+    {
+        /* An empty expression is not allowed. */
+        write!(out, " 0 {}", model.variables[0].name)?;
+    }
+
+    for (t, &(v, coefficient)) in terms.iter().enumerate()
+    {
+        if t > 0 && t % TERMS_PER_LINE == 0
+        {
+            write!(out, "\n  ")?;
+        }
+        let sign : &str = if coefficient < 0.0 { "-" } else { "+" };
+        write!(out, " {} {} {}", sign, coefficient.abs(), model.variables[v].name)?;
+    }
+
+    writeln!(out)?;
+
+    return Ok(());
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::milp_export::vehicle_flow::CapacityFormulation;
+    use crate::milp_export::vehicle_flow::tests::tiny_model;
+
+    fn lp_lines(formulation : CapacityFormulation) -> Vec<String>
+    {
+
+        let mut out : Vec<u8> = Vec::new();
+        write_lp(&tiny_model(formulation), &mut out).unwrap();
+
+        return String::from_utf8(out).unwrap().lines().map(|l| l.to_string()).collect();
+
+    }
+
+    fn position(lines : &[String], line : &str) -> usize
+    {
+
+        return lines.iter().position(|l| l == line).unwrap();
+
+    }
+
+    #[test]
+    fn lp_sections_in_order()
+    {
+
+        for formulation in [CapacityFormulation::MillerTuckerZemlin, CapacityFormulation::SingleCommodityFlow]
+        {
+
+            let lines : Vec<String> = lp_lines(formulation);
+
+            let sections : Vec<usize> = ["Minimize", "Subject To", "Bounds", "Binaries", "End"].iter()
+                .map(|s| position(&lines, s))
+                .collect();
+            assert!(sections.windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(lines.last().unwrap(), "End");
+            assert!(!lines.iter().any(|l| l == "Generals"));
+
+            /* The 12 arcs, 8 names per line. */
+            let binaries : usize = position(&lines, "Binaries");
+            assert_eq!(lines[binaries + 1].split_whitespace().count(), 8);
+            assert_eq!(lines[binaries + 2].split_whitespace().count(), 4);
+
+        }
+
+    }
+
+    #[test]
+    fn lp_constraints()
+    {
+
+        let lines : Vec<String> = lp_lines(CapacityFormulation::MillerTuckerZemlin);
+
+        let mtz : usize = position(&lines, " mtz_1_2: + 1 u_1 - 1 u_2 + 10 x_1_2");
+        assert_eq!(lines[mtz + 1], "   <= 6");
+        position(&lines, " 4 <= u_2 <= 10");
+
+        let lines : Vec<String> = lp_lines(CapacityFormulation::SingleCommodityFlow);
+
+        let lower : usize = position(&lines, " flow_lower_1_2: + 1 f_1_2 - 4 x_1_2");
+        let upper : usize = position(&lines, " flow_upper_1_2: + 1 f_1_2 - 7 x_1_2");
+        assert_eq!(lines[lower + 1], "   >= 0");
+        assert_eq!(lines[upper + 1], "   <= 0");
+        position(&lines, " flow_upper_0_1: + 1 f_0_1 - 10 x_0_1");
+        position(&lines, " 0 <= f_1_2 <= 10");
+
+    }
+
+}
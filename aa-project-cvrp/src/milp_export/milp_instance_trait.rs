@@ -0,0 +1,16 @@
+/* This trait define the methods required
+ * by the MILP model export, available only
+ * for GraphInstance when explicitly
+ * implemented. */
+pub trait MilpInstanceTrait : Sync
+{
+
+    fn get_name(&self) -> String;
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>>;
+
+    fn get_capacity(&self) -> usize;
+
+    fn get_nodes_demand(&self) -> Vec<usize>;
+
+}
@@ -0,0 +1,118 @@
+/* A mixed integer linear program, minimizing a
+ * linear objective, kept in a form which can be
+ * written in any of the file formats of the
+ * external solvers. */
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum VariableType
+{
+
+    Binary,
+
+    Integer,
+
+    Continuous,
+
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum Sense
+{
+
+    LessEqual,
+
+    GreaterEqual,
+
+    Equal,
+
+}
+
+pub struct Variable
+{
+
+    pub name          : String,
+
+    pub variable_type : VariableType,
+
+    pub lower         : f64,
+
+    /* None if the variable is unbounded above. */
+    pub upper         : Option<f64>,
+
+}
+
+/* A linear constraint, given by its terms as
+ * pairs of variable index and coefficient. */
+pub struct Constraint
+{
+
+    pub name  : String,
+
+    pub terms : Vec<(usize, f64)>,
+
+    pub sense : Sense,
+
+    pub rhs   : f64,
+
+}
+
+pub struct MilpModel
+{
+
+    pub name        : String,
+
+    pub variables   : Vec<Variable>,
+
+    /* Objective coefficient of each variable. */
+    pub objective   : Vec<f64>,
+
+    pub constraints : Vec<Constraint>,
+
+}
+
+/* Methods building the model. */
+#[allow(dead_code)]
+impl MilpModel
+{
+
+    pub fn new(name : &str) -> MilpModel
+    {
+
+        MilpModel {
+            name        : name.to_string(),
+            variables   : Vec::new(),
+            objective   : Vec::new(),
+            constraints : Vec::new(),
+        }
+
+    }
+
+    /* Add a variable with its objective
+     * coefficient, returning its index. */
+    pub fn add_variable(
+        &mut self,
+        name          : String,
+        variable_type : VariableType,
+        lower         : f64,
+        upper         : Option<f64>,
+        cost          : f64)
+        -> usize
+    {
+
+        self.variables.push(Variable { name, variable_type, lower, upper });
+        self.objective.push(cost);
+
+        return self.variables.len() - 1;
+
+    }
+
+    pub fn add_constraint(&mut self, name : String, terms : Vec<(usize, f64)>, sense : Sense, rhs : f64)
+    {
+
+        self.constraints.push(Constraint { name, terms, sense, rhs });
+
+    }
+
+}
@@ -0,0 +1,5 @@
+pub mod milp_instance_trait;
+pub mod milp_model;
+pub mod vehicle_flow;
+pub mod lp_writer;
+pub mod mps_writer;
@@ -0,0 +1,148 @@
+use std::io::{self, Write};
+use crate::milp_export::milp_model::{MilpModel, VariableType, Sense};
+
+/* Write the model in the free MPS format, where
+ * the fields are separated by spaces, so that
+ * the names are not limited to eight characters. */
+pub fn write_mps(model : &MilpModel, out : &mut dyn Write) -> io::Result<()>
+{
+
+    writeln!(out, "NAME {}", model.name)?;
+
+    writeln!(out, "ROWS")?;
+    writeln!(out, " N obj")?;
+    for constraint in &model.constraints
+    {
+        let sense : &str = match constraint.sense
+        {
+            Sense::LessEqual    => "L",
+            Sense::GreaterEqual => "G",
+            Sense::Equal        => "E",
+        };
+        writeln!(out, " {} {}", sense, constraint.name)?;
+    }
+
+    /* The coefficients are written by column,
+     * so they are first grouped by variable. */
+    let mut columns : Vec< Vec<(usize, f64)>> = vec![Vec::new(); model.variables.len()];
+    for (c, constraint) in model.constraints.iter().enumerate()
+    {
+        for &(v, coefficient) in &constraint.terms
+        {
+            columns[v].push((c, coefficient));
+        }
+    }
+
+    /* The integer variables are enclosed
+     * between the markers. */
+    writeln!(out, "COLUMNS")?;
+    let mut integer : bool = false;
+    for (v, variable) in model.variables.iter().enumerate()
+    {
+
+        let is_integer : bool = variable.variable_type != VariableType::Continuous;
+        if is_integer != integer
+        {
+            let marker : &str = if is_integer { "'INTORG'" } else { "'INTEND'" };
+            writeln!(out, " MARKER 'MARKER' {}", marker)?;
+            integer = is_integer;
+        }
+
+        if model.objective[v] != 0.0
+        {
+            writeln!(out, " {} obj {}", variable.name, model.objective[v])?;
+        }
+        for &(c, coefficient) in &columns[v]
+        {
+            writeln!(out, " {} {} {}", variable.name, model.constraints[c].name, coefficient)?;
+        }
+
+    }
+    if integer
+    {
+        writeln!(out, " MARKER 'MARKER' 'INTEND'")?;
+    }
+
+    writeln!(out, "RHS")?;
+    for constraint in model.constraints.iter().filter(|c| c.rhs != 0.0)
+    {
+        writeln!(out, " rhs {} {}", constraint.name, constraint.rhs)?;
+    }
+
+    writeln!(out, "BOUNDS")?;
+    for variable in &model.variables
+    {
+        match (variable.variable_type, variable.upper)
+        {
+            (VariableType::Binary, _) => writeln!(out, " BV bnd {}", variable.name)?,
+            (_, Some(upper)) =>
+            {
+                writeln!(out, " LO bnd {} {}", variable.name, variable.lower)?;
+                writeln!(out, " UP bnd {} {}", variable.name, upper)?;
+            },
+            /* The integer variables between the markers
+             * may otherwise be read as binaries. */
+            (_, None) =>
+            {
+                writeln!(out, " LO bnd {} {}", variable.name, variable.lower)?;
+                writeln!(out, " PL bnd {}", variable.name)?;
+            },
+        }
+    }
+
+    writeln!(out, "ENDATA")?;
+
+    return Ok(());
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::milp_export::vehicle_flow::CapacityFormulation;
+    use crate::milp_export::vehicle_flow::tests::tiny_model;
+
+    #[test]
+    fn mps_markers_and_bounds()
+    {
+
+        for (formulation, rows) in [(CapacityFormulation::MillerTuckerZemlin, 13), (CapacityFormulation::SingleCommodityFlow, 34)]
+        {
+
+            let mut out : Vec<u8> = Vec::new();
+            write_mps(&tiny_model(formulation), &mut out).unwrap();
+            let text  : String    = String::from_utf8(out).unwrap();
+            let lines : Vec<&str> = text.lines().collect();
+
+            /* The objective and the constraints. */
+            let start : usize = lines.iter().position(|&l| l == "ROWS").unwrap();
+            let end   : usize = lines.iter().position(|&l| l == "COLUMNS").unwrap();
+            assert_eq!(end - start - 1, rows + 1);
+
+            /* The markers open and close in turn, around
+             * the arcs, the first of the variables. */
+            let markers : Vec<&str> = lines.iter()
+                .filter(|l| l.starts_with(" MARKER"))
+                .cloned()
+                .collect();
+            assert_eq!(markers, vec![" MARKER 'MARKER' 'INTORG'", " MARKER 'MARKER' 'INTEND'"]);
+            assert_eq!(lines[end + 1], " MARKER 'MARKER' 'INTORG'");
+            assert!(lines[end + 2].starts_with(" x_0_1 "));
+
+            assert_eq!(lines.iter().filter(|l| l.starts_with(" BV bnd x_")).count(), 12);
+            assert!(lines.contains(&" rhs vehicles 2"));
+            if formulation == CapacityFormulation::MillerTuckerZemlin
+            {
+                assert!(lines.contains(&" x_1_2 mtz_1_2 10"));
+                assert!(lines.contains(&" rhs mtz_1_2 6"));
+                assert!(lines.contains(&" LO bnd u_2 4") && lines.contains(&" UP bnd u_2 10"));
+            }
+            assert_eq!(*lines.last().unwrap(), "ENDATA");
+
+        }
+
+    }
+
+}
@@ -0,0 +1,271 @@
+use crate::milp_export::milp_instance_trait::MilpInstanceTrait;
+use crate::milp_export::milp_model::{MilpModel, VariableType, Sense};
+
+/* Constraints ensuring that the routes are connected
+ * to the depot and within the capacity. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum CapacityFormulation
+{
+
+    /* Miller-Tucker-Zemlin constraints: the load u_i
+     * on arrival at the customer i, after serving it,
+     * lies between its demand and the capacity, and
+     * grows by the demand of the next customer along
+     * each arc between customers. */
+    MillerTuckerZemlin,
+
+    /* Single-commodity flow: the flow f_i_j on each arc
+     * is the load carried along it, which decreases
+     * by the demand of each customer visited, and is
+     * within the capacity on the arcs used only. */
+    SingleCommodityFlow,
+
+}
+
+/* Two-index vehicle flow formulation of the instance,
+ * over the binary variables x_i_j, one for each arc,
+ * equal to one if a vehicle goes from i to j. Each
+ * customer is entered and left exactly once, and at
+ * most the given number of vehicles leave the depot,
+ * which is the node 0. */
+pub fn two_index_model(
+    instance    : &dyn MilpInstanceTrait,
+    vehicles    : usize,
+    formulation : CapacityFormulation)
+    -> MilpModel
+{
+
+    let distance_matrix : Vec< Vec<f64>> = instance.get_distance_matrix();
+    let nodes_demand    : Vec<usize>     = instance.get_nodes_demand();
+    let capacity        : f64            = instance.get_capacity() as f64;
+    let node_number     : usize          = nodes_demand.len();
+
+    let mut model : MilpModel = MilpModel::new(&instance.get_name());
+
+    /* Index of the variable of each arc. */
+    let mut arcs : Vec< Vec<usize>> = vec![vec![usize::MAX; node_number]; node_number];
+    for i in 0..node_number
+    {
+        for j in (0..node_number).filter(|&j| j != i)
+        {
+            arcs[i][j] = model.add_variable(
+                format!("x_{}_{}", i, j), VariableType::Binary, 0.0, Some(1.0), distance_matrix[i][j]);
+        }
+    }
+
+    /* Degree constraints. */
+    for (i, outgoing) in arcs.iter().enumerate().skip(1)
+    {
+        model.add_constraint(
+            format!("out_{}", i),
+            (0..node_number).filter(|&j| j != i).map(|j| (outgoing[j], 1.0)).collect(),
+            Sense::Equal,
+            1.0);
+        model.add_constraint(
+            format!("in_{}", i),
+            (0..node_number).filter(|&j| j != i).map(|j| (arcs[j][i], 1.0)).collect(),
+            Sense::Equal,
+            1.0);
+    }
+    model.add_constraint(
+        "vehicles".to_string(),
+        (1..node_number).map(|j| (arcs[0][j], 1.0)).collect(),
+        Sense::LessEqual,
+        vehicles as f64);
+
+    match formulation
+    {
+
+        CapacityFormulation::MillerTuckerZemlin =>
+        {
+
+            let mut loads : Vec<usize> = vec![usize::MAX; node_number];
+            for i in 1..node_number
+            {
+                loads[i] = model.add_variable(
+                    format!("u_{}", i), VariableType::Continuous, nodes_demand[i] as f64, Some(capacity), 0.0);
+            }
+
+            /* u_i - u_j + Q x_i_j <= Q - q_j */
+            for i in 1..node_number
+            {
+                for j in (1..node_number).filter(|&j| j != i)
+                {
+                    model.add_constraint(
+                        format!("mtz_{}_{}", i, j),
+                        vec![(loads[i], 1.0), (loads[j], -1.0), (arcs[i][j], capacity)],
+                        Sense::LessEqual,
+                        capacity - nodes_demand[j] as f64);
+                }
+            }
+
+        },
+
+        CapacityFormulation::SingleCommodityFlow =>
+        {
+
+            let mut flows : Vec< Vec<usize>> = vec![vec![usize::MAX; node_number]; node_number];
+            for (i, outgoing) in flows.iter_mut().enumerate()
+            {
+                for j in (0..node_number).filter(|&j| j != i)
+                {
+                    outgoing[j] = model.add_variable(
+                        format!("f_{}_{}", i, j), VariableType::Continuous, 0.0, Some(capacity), 0.0);
+                }
+            }
+
+            /* The flow entering each customer exceeds
+             * the flow leaving it by its demand. */
+            for i in 1..node_number
+            {
+                let mut terms : Vec<(usize, f64)> = Vec::new();
+                for j in (0..node_number).filter(|&j| j != i)
+                {
+                    terms.push((flows[j][i], 1.0));
+                    terms.push((flows[i][j], -1.0));
+                }
+                model.add_constraint(format!("flow_{}", i), terms, Sense::Equal, nodes_demand[i] as f64);
+            }
+
+            /* q_j x_i_j <= f_i_j <= (Q - q_i) x_i_j */
+            for i in 0..node_number
+            {
+                for j in (0..node_number).filter(|&j| j != i)
+                {
+                    model.add_constraint(
+                        format!("flow_lower_{}_{}", i, j),
+                        vec![(flows[i][j], 1.0), (arcs[i][j], -(nodes_demand[j] as f64))],
+                        Sense::GreaterEqual,
+                        0.0);
+                    model.add_constraint(
+                        format!("flow_upper_{}_{}", i, j),
+                        vec![(flows[i][j], 1.0), (arcs[i][j], -(capacity - nodes_demand[i] as f64))],
+                        Sense::LessEqual,
+                        0.0);
+                }
+            }
+
+        },
+
+    }
+
+    return model;
+
+}
+
+#[cfg(test)]
+pub(crate) mod tests
+{
+
+    use super::*;
+    use crate::milp_export::milp_model::{Variable, Constraint};
+
+    /* Three customers, of demands 3, 4 and 5,
+     * and vehicles of capacity 10. */
+    struct TinyInstance;
+
+    impl MilpInstanceTrait for TinyInstance
+    {
+
+        fn get_name(&self) -> String
+        {
+            return "tiny".to_string();
+        }
+
+        fn get_distance_matrix(&self) -> Vec< Vec<f64>>
+        {
+            return vec![
+                vec![0.0, 2.0, 3.0, 4.0],
+                vec![2.0, 0.0, 1.0, 5.0],
+                vec![3.0, 1.0, 0.0, 2.0],
+                vec![4.0, 5.0, 2.0, 0.0],
+            ];
+        }
+
+        fn get_capacity(&self) -> usize
+        {
+            return 10;
+        }
+
+        fn get_nodes_demand(&self) -> Vec<usize>
+        {
+            return vec![0, 3, 4, 5];
+        }
+
+    }
+
+    /* Model of the tiny instance with two vehicles. */
+    pub(crate) fn tiny_model(formulation : CapacityFormulation) -> MilpModel
+    {
+
+        return two_index_model(&TinyInstance, 2, formulation);
+
+    }
+
+    fn constraint<'m>(model : &'m MilpModel, name : &str) -> &'m Constraint
+    {
+
+        return model.constraints.iter().find(|c| c.name == name).unwrap();
+
+    }
+
+    /* Terms of the constraint, by variable name. */
+    fn terms<'m>(model : &'m MilpModel, constraint : &Constraint) -> Vec<(&'m str, f64)>
+    {
+
+        return constraint.terms.iter().map(|&(v, c)| (model.variables[v].name.as_str(), c)).collect();
+
+    }
+
+    #[test]
+    fn miller_tucker_zemlin_model()
+    {
+
+        let model : MilpModel = tiny_model(CapacityFormulation::MillerTuckerZemlin);
+
+        /* 12 arcs and 3 loads; 6 degree constraints,
+         * the vehicles and 6 MTZ constraints. */
+        assert_eq!(model.variables.len(), 15);
+        assert_eq!(model.constraints.len(), 13);
+        assert_eq!(model.variables.iter().filter(|v| v.variable_type == VariableType::Binary).count(), 12);
+
+        let load : &Variable = model.variables.iter().find(|v| v.name == "u_2").unwrap();
+        assert_eq!((load.lower, load.upper), (4.0, Some(10.0)));
+
+        let mtz : &Constraint = constraint(&model, "mtz_1_2");
+        assert_eq!(mtz.sense, Sense::LessEqual);
+        assert_eq!(mtz.rhs, 6.0);
+        assert_eq!(terms(&model, mtz), vec![("u_1", 1.0), ("u_2", -1.0), ("x_1_2", 10.0)]);
+
+        let vehicles : &Constraint = constraint(&model, "vehicles");
+        assert_eq!((vehicles.terms.len(), vehicles.rhs), (3, 2.0));
+
+    }
+
+    #[test]
+    fn single_commodity_flow_model()
+    {
+
+        let model : MilpModel = tiny_model(CapacityFormulation::SingleCommodityFlow);
+
+        /* 12 arcs and 12 flows; 6 degree constraints,
+         * the vehicles, 3 flow conservations and the
+         * lower and upper bounds of the 12 flows. */
+        assert_eq!(model.variables.len(), 24);
+        assert_eq!(model.constraints.len(), 34);
+
+        let conservation : &Constraint = constraint(&model, "flow_2");
+        assert_eq!((conservation.terms.len(), conservation.sense, conservation.rhs), (6, Sense::Equal, 4.0));
+
+        /* q_2 x_1_2 <= f_1_2 <= (Q - q_1) x_1_2 */
+        let lower : &Constraint = constraint(&model, "flow_lower_1_2");
+        let upper : &Constraint = constraint(&model, "flow_upper_1_2");
+        assert_eq!(terms(&model, lower), vec![("f_1_2", 1.0), ("x_1_2", -4.0)]);
+        assert_eq!(terms(&model, upper), vec![("f_1_2", 1.0), ("x_1_2", -7.0)]);
+        assert_eq!((lower.sense, lower.rhs), (Sense::GreaterEqual, 0.0));
+        assert_eq!((upper.sense, upper.rhs), (Sense::LessEqual, 0.0));
+
+    }
+
+}
@@ -9,11 +9,22 @@ mod utils;
 use crate::tsplib_parser::problem_instance::TSPInstance;
 use crate::sweep_algorithm::sweep_instance_trait::SweepInstanceTrait;
 use crate::savings_algorithm::savings_instance_trait::SavingsInstanceTrait;
-use crate::model::utils::{compute_savings_coord, compute_savings_fmatrix, from_hmatrix_to_fmatrix, compute_savings_geo, compute_distance_geo, compute_distance_euc};
+use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+use crate::route_first_algorithm::route_first_instance_trait::RouteFirstInstanceTrait;
+use crate::construction::construction_instance_trait::ConstructionInstanceTrait;
+use crate::fisher_jaikumar_algorithm::fisher_jaikumar_instance_trait::FisherJaikumarInstanceTrait;
+use crate::alns::alns_instance_trait::AlnsInstanceTrait;
+use crate::hybrid_genetic_search::hgs_instance_trait::HgsInstanceTrait;
+use crate::ant_colony::ant_colony_instance_trait::AntColonyInstanceTrait;
+use crate::exact_algorithm::exact_instance_trait::ExactInstanceTrait;
+use crate::bounds::bounds_instance_trait::BoundsInstanceTrait;
+use crate::route_pool::route_pool_instance_trait::RoutePoolInstanceTrait;
+use crate::milp_export::milp_instance_trait::MilpInstanceTrait;
+use crate::parallel::parallel_instance_trait::ParallelInstanceTrait;
+use crate::record_to_record::record_to_record_instance_trait::RecordToRecordInstanceTrait;
+use crate::model::utils::{compute_savings_coord, compute_savings_fmatrix, from_hmatrix_to_fmatrix, compute_savings_geo, compute_distance_geo, compute_distance_euc, compute_distance_matrix, compute_mds_embedding, compute_savings_parametric, compute_savings_neighbours, parse_vehicles_number};
 use crate::tsplib_parser::keyword_values::{EDGE_WEIGHT_TYPE, EDGE_WEIGHT_FORMAT};
 use crate::tsplib_parser::custom_types::{Node, Coord};
-use rand::Rng;
-use rand::rngs::ThreadRng;
 
 /* The GraphInstance object encapsulates
  * the tsplib dependence, which is then
@@ -27,6 +38,107 @@ pub struct GraphInstance<'a>
 
 }
 
+/* Methods of GraphInstance shared by the
+ * implementations of the instance traits. */
+impl GraphInstance<'_>
+{
+
+    /* Full matrix of the distances between nodes,
+     * regardless of the instance type. */
+    fn distance_matrix(&self) -> Vec< Vec<f64>>
+    {
+
+        compute_distance_matrix(
+            &self.instance.data.edge_weight_section,
+            &self.instance.specification.edge_weight_format,
+            &self.instance.specification.edge_weight_type,
+            &self.instance.data.node_coord_section,
+            self.instance.specification.dimension)
+            .expect("The edge weight format of the instance is not supported")
+
+    }
+
+    /* Distance between two nodes, computed on demand
+     * from the coordinates when the instance has them,
+     * so that large instances do not require the full
+     * distance matrix, which is built only for the
+     * instances with explicit distances. */
+    fn distance_function(&self) -> Box<dyn Fn(usize, usize) -> f64 + '_>
+    {
+
+        match (&self.instance.data.edge_weight_section, &self.instance.data.node_coord_section)
+        {
+
+            (None, Some(n_coord)) =>
+                {
+                    let distance_function : fn(&Coord, &Coord) -> f64 =
+                        match self.instance.specification.edge_weight_type
+                        {
+                            EDGE_WEIGHT_TYPE::GEO => compute_distance_geo,
+                            _ => compute_distance_euc,
+                        };
+
+                    Box::new(move |i, j| distance_function(&n_coord[i], &n_coord[j]))
+                }
+
+            _ =>
+                {
+                    let distance_matrix : Vec< Vec<f64>> = self.distance_matrix();
+
+                    Box::new(move |i, j| distance_matrix[i][j])
+                }
+
+        }
+
+    }
+
+    /* Demand of each node, the depot included. */
+    fn nodes_demand(&self) -> Vec<usize>
+    {
+
+        match &self.instance.data.demand_section
+        {
+            Some(d_s) => d_s.iter().map(|(_, d)| *d).collect(),
+            _ => Vec::new(),
+        }
+
+    }
+
+}
+
+/* Instance read from the input folder, for the
+ * tests. The content of the file is leaked, since
+ * the instance borrows it. */
+#[cfg(test)]
+pub(crate) fn read_test_instance(name : &str) -> GraphInstance<'static>
+{
+
+    let content : String = std::fs::read_to_string(format!("./input/{}.vrp", name))
+        .expect("Something went wrong reading the file");
+
+    GraphInstance {
+        instance : crate::tsplib_parser::parser::parse(Box::leak(content.into_boxed_str())),
+    }
+
+}
+
+/* Check that the routes serve every customer
+ * of the instance exactly once, within the
+ * capacity, for the tests. */
+#[cfg(test)]
+pub(crate) fn assert_feasible(graph : &GraphInstance, routes : &[Vec<usize>])
+{
+
+    let nodes_demand : Vec<usize> = graph.nodes_demand();
+    let capacity     : usize      = graph.instance.specification.capacity;
+
+    let mut served : Vec<usize> = routes.concat();
+    served.sort();
+    assert_eq!(served, (1..nodes_demand.len()).collect::<Vec<usize>>());
+    assert!(routes.iter().all(|r| r.iter().map(|&i| nodes_demand[i]).sum::<usize>() <= capacity));
+
+}
+
 /* Implementation of the SavingsInstanceTrait
  * for GraphInstance. */
 impl SavingsInstanceTrait for GraphInstance<'_>
@@ -138,6 +250,51 @@ impl SavingsInstanceTrait for GraphInstance<'_>
 
     }
 
+    fn compute_parametric_savings(
+        &self,
+        lambda : f64,
+        mu     : f64,
+        nu     : f64)
+        -> Vec<(usize, usize, f64)> {
+
+        let mut savings : Vec<(usize, usize, f64)> = Vec::new();
+
+        compute_savings_parametric(
+            &self.distance_matrix(),
+            &self.nodes_demand(),
+            lambda,
+            mu,
+            nu,
+            &mut savings);
+
+        return savings;
+
+    }
+
+    fn compute_neighbour_savings(
+        &self,
+        neighbours : usize,
+        lambda     : f64,
+        mu         : f64,
+        nu         : f64)
+        -> Vec<(usize, usize, f64)> {
+
+        let mut savings      : Vec<(usize, usize, f64)> = Vec::new();
+        let nodes_demand     : Vec<usize>               = self.nodes_demand();
+
+        compute_savings_neighbours(
+            &*self.distance_function(),
+            &nodes_demand,
+            neighbours,
+            lambda,
+            mu,
+            nu,
+            &mut savings);
+
+        return savings;
+
+    }
+
     fn get_capacity(&self) -> usize {
 
         self.instance.specification.capacity
@@ -179,6 +336,27 @@ impl SavingsInstanceTrait for GraphInstance<'_>
         return result;
 
     }
+
+    fn compute_routes_cost(&self, routes : &[Vec<usize>]) -> f64
+    {
+
+        let distance   : Box<dyn Fn(usize, usize) -> f64 + '_> = self.distance_function();
+        let mut result : f64                                  = 0.0;
+
+        for route in routes
+        {
+            let mut previous_node : usize = 0;
+            for &current_node in route
+            {
+                result        += distance(previous_node, current_node);
+                previous_node  = current_node;
+            }
+            result += distance(previous_node, 0);
+        }
+
+        return result;
+
+    }
 }
 
 /* Implementation of the SweepInstanceTrait
@@ -191,19 +369,11 @@ impl SweepInstanceTrait for GraphInstance<'_>
 
         let dimension          : usize                      = self.instance.specification.dimension;
         let edge_weight_type   : &EDGE_WEIGHT_TYPE          = &self.instance.specification.edge_weight_type;
-        let edge_weights       : &Option< Vec< Vec<usize>>> = &self.instance.data.edge_weight_section;
         let node_coord         : &Option< Vec< Coord>>      = &self.instance.data.node_coord_section;
-        let result             : Vec<Node>;
-
-        /* Select a node randomly. */
-        let mut rng            : ThreadRng         = rand::thread_rng();
-        let n1                 : Node              = rng.gen_range(1..dimension);
-        let n2                 : Node;
-        let n3                 : Node;
 
         /* Check if the edge weight are expressed
          * as coord distances. */
-        if  *edge_weight_type == EDGE_WEIGHT_TYPE::GEO    ||
+        let points : Vec<(f64, f64)> = if *edge_weight_type == EDGE_WEIGHT_TYPE::GEO    ||
             *edge_weight_type == EDGE_WEIGHT_TYPE::EUC_2D ||
             *edge_weight_type == EDGE_WEIGHT_TYPE::EUC_3D
         {
@@ -217,190 +387,149 @@ impl SweepInstanceTrait for GraphInstance<'_>
                 _ => Vec::new(),
             };
 
-            /* The format is (Node, Angle, Radius). */
-            let mut node_polar_coord : Vec<(Node, f64, f64)>
-                = Vec::with_capacity(dimension - 1);
+            n_coord.iter()
+                .map(|c| match c
+                {
+                    Coord::Coord2d((_, x, y)) => (*x, *y),
+                    Coord::Coord3d((_, x, y, _)) => (*x, *y),
+                })
+                .collect()
 
-            let x_0 : f64 = match n_coord[0]
-            {
-                Coord::Coord2d((_, x, _)) => x,
-                Coord::Coord3d((_, x, _, _)) => x,
-            };
+        }
+        /* Otherwise we have to handle
+         * a matrix, which is embedded into
+         * the plane to obtain pseudo-coordinates. */
+        else
+        {
 
-            let y_0 : f64 = match n_coord[0]
-            {
-                Coord::Coord2d((_, _, y)) => y,
-                Coord::Coord3d((_, _, y, _)) => y,
-            };
+            compute_mds_embedding(&self.distance_matrix())
 
-            /* Compute the polar coordinates. */
-            for i in 1..dimension
-            {
+        };
 
-                let x_i : f64 = match n_coord[i]
-                {
-                    Coord::Coord2d((_, x, _)) => x,
-                    Coord::Coord3d((_, x, _, _)) => x,
-                };
+        let (x_0, y_0) : (f64, f64) = points[0];
 
-                let y_i : f64 = match n_coord[i]
-                {
-                    Coord::Coord2d((_, _, y)) => y,
-                    Coord::Coord3d((_, _, y, _)) => y,
-                };
+        /* The format is (Node, Angle, Radius). */
+        let mut node_polar_coord : Vec<(Node, f64, f64)>
+            = Vec::with_capacity(dimension - 1);
 
-                let angle_i : f64
-                    = ( (y_i - y_0) / (x_i - x_0) ).atan();
+        /* Compute the polar coordinates. */
+        for (i, &(x_i, y_i)) in points.iter().enumerate().skip(1)
+        {
 
-                let radius_i : f64
-                    = ((y_i - y_0).powf(2.0) + (x_i - x_0).powf(2.0)).sqrt();
+            /* The angle is measured counter-clockwise
+             * from the x axis, in the range [0, 2pi),
+             * so that nodes in opposite quadrants are
+             * not folded together. */
+            let angle_i : f64
+                = (y_i - y_0).atan2(x_i - x_0).rem_euclid(2.0 * std::f64::consts::PI);
 
-                node_polar_coord.push((i, angle_i, radius_i));
+            let radius_i : f64
+                = ((y_i - y_0).powf(2.0) + (x_i - x_0).powf(2.0)).sqrt();
 
-            }
+            node_polar_coord.push((i, angle_i, radius_i));
 
-            node_polar_coord.sort_by(|(_, a1, _), (_, a2, _)| a1.partial_cmp(a2).unwrap());
-            node_polar_coord.sort_by(|&(_, a1, r1), &(_, a2, r2)|
+        }
+
+        node_polar_coord.sort_by(|&(_, a1, r1), &(_, a2, r2)|
+            {
+                match a1 == a2
                 {
-                    match a1 == a2
-                    {
-                        true  => r1.partial_cmp(&r2).unwrap(),
-                        false => a1.partial_cmp(&a2).unwrap(),
-                    }
-                });
+                    true  => r1.partial_cmp(&r2).unwrap(),
+                    false => a1.partial_cmp(&a2).unwrap(),
+                }
+            });
 
-            result = node_polar_coord.iter().map(|(n, _, _)| n.clone()).collect();
+        let result : Vec<Node> = node_polar_coord.iter().map(|(n, _, _)| *n).collect();
 
-        }
-        /* Otherwise we have to handle
-         * a matrix. */
-        else
+        return result;
+
+    }
+
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
+
+    }
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
+
+    }
+
+    fn get_nodes_demand(&self) -> Vec<usize> {
+
+        let d_section : Vec<(Node, usize)> =
+            match self.instance.data.demand_section.clone()
         {
+            Some(d_s) => d_s,
+            _ => Vec::new(),
+        };
 
-            /* Move the edge weight information. */
-            let e_weights_hmatrix : Option<Vec< Vec<usize>>> = edge_weights.clone();
+        let result : Vec<usize> =
+            d_section.iter().map(|(_, d)| *d).collect();
 
-            let edge_weight_format : &Option<EDGE_WEIGHT_FORMAT> = &self.instance.specification.edge_weight_format;
-            let e_w_f : EDGE_WEIGHT_FORMAT = match edge_weight_format
-            {
-                Some(w_f) => w_f.clone(),
-                _ => EDGE_WEIGHT_FORMAT::FULL_MATRIX,
-            };
+        return result;
 
-            let mut op_e_weights : Option< Vec< Vec<usize>>> = e_weights_hmatrix.clone();
+    }
+}
 
-            /* Convert edge weight into full matrix. */
-            if e_w_f != EDGE_WEIGHT_FORMAT::FULL_MATRIX
-            {
-                op_e_weights =
-                    from_hmatrix_to_fmatrix(&e_weights_hmatrix, dimension, e_w_f);
-            }
+/* Implementation of the LocalSearchInstanceTrait
+ * for GraphInstance. */
+impl LocalSearchInstanceTrait for GraphInstance<'_>
+{
 
-            let e_weights : Vec< Vec<usize>> = match op_e_weights
-            {
-                Some(ew) => ew,
-                _ => Vec::new()
-            };
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
 
-            /* Compute a list of nodes sorted by
-             * distance from n1. */
-            let nodes_from_n1: Vec<(Node, f64)> =
-                compute_node_list_sort_by_distance(&e_weights, n1, dimension);
+        self.distance_matrix()
 
-            n2 = match nodes_from_n1.last()
-            {
-                Some((n, _)) => *n,
-                _ => 0,
-            };
+    }
 
-            /* Compute the node which maximise the
-             * value of d_n1_n3 + d_n2_n3. */
-            let mut dist_n1_n2_n3 : f64  = 0.0;
-            let mut tmp_n3        : Node = 0;
-            for i in 1..dimension
-            {
+    fn get_capacity(&self) -> usize {
 
-                /* Skip if the current node is n1 or n2. */
-                if i == n1 || i == n2
-                {
-                    continue;
-                }
+        self.instance.specification.capacity
+
+    }
 
-                let dist_n1_n3 : f64 = compute_distance_from_nodes(&e_weights, n1, i);
-                let dist_n2_n3 : f64 = compute_distance_from_nodes(&e_weights, n2, i);
+    fn get_nodes_demand(&self) -> Vec<usize> {
 
-                if dist_n1_n3 + dist_n2_n3 > dist_n1_n2_n3
-                {
-                    tmp_n3        = i;
-                    dist_n1_n2_n3 = dist_n1_n3 + dist_n2_n3;
-                }
+        self.nodes_demand()
 
-            }
+    }
 
-            n3 = tmp_n3;
+}
 
-            /* Finally, partition the nodes. */
-            let mut cluster_n1_n2: Vec<(Node, f64)> = Vec::new();
-            let mut cluster_n2_n3: Vec<(Node, f64)> = Vec::new();
-            let mut cluster_n3_n1: Vec<(Node, f64)> = Vec::new();
+/* Implementation of the RouteFirstInstanceTrait
+ * for GraphInstance. */
+impl RouteFirstInstanceTrait for GraphInstance<'_>
+{
 
-            /* Insert n1, n2, n3, one for each cluster. */
-            cluster_n1_n2.push((n1, 0.0));
-            cluster_n2_n3.push((n2, 0.0));
-            cluster_n3_n1.push((n3, 0.0));
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
 
-            for i in 1..dimension
-            {
+        self.distance_matrix()
 
-                /* Skip if i is one of the cluster centers. */
-                if i == n1 || i == n2 || i == n3
-                {
-                    continue;
-                }
+    }
 
-                let dist_n1_i : f64 = compute_distance_from_nodes(&e_weights, n1, i);
-                let dist_n2_i : f64 = compute_distance_from_nodes(&e_weights, n2, i);
-                let dist_n3_i : f64 = compute_distance_from_nodes(&e_weights, n3, i);
+    fn get_capacity(&self) -> usize {
 
-                if dist_n1_i < dist_n3_i
-                {
-                    if dist_n2_i < dist_n3_i
-                    {
-                        cluster_n1_n2.push((i, dist_n1_i));
-                    }
-                    else
-                    {
-                        cluster_n3_n1.push((i, dist_n3_i));
-                    }
-                }
-                else
-                {
-                    if dist_n1_i < dist_n2_i
-                    {
-                        cluster_n3_n1.push((i, dist_n3_i));
-                    }
-                    else
-                    {
-                        cluster_n2_n3.push((i, dist_n2_i));
-                    }
-                }
+        self.instance.specification.capacity
 
-            }
+    }
 
-            cluster_n1_n2.sort_by_key(|n| n.1 as usize);
-            cluster_n2_n3.sort_by_key(|n| n.1 as usize);
-            cluster_n3_n1.sort_by_key(|n| n.1 as usize);
+    fn get_nodes_demand(&self) -> Vec<usize> {
 
-            /* Produce a single list from
-             * the three clusters. */
-            cluster_n1_n2.append(cluster_n2_n3.as_mut());
-            cluster_n1_n2.append(cluster_n3_n1.as_mut());
+        self.nodes_demand()
 
-            result = cluster_n1_n2.iter().map(|&n| n.0).collect();
+    }
 
-        }
+}
 
-        return result;
+impl ConstructionInstanceTrait for GraphInstance<'_>
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
 
     }
 
@@ -412,62 +541,280 @@ impl SweepInstanceTrait for GraphInstance<'_>
 
     fn get_nodes_demand(&self) -> Vec<usize> {
 
-        let d_section : Vec<(Node, usize)> =
-            match self.instance.data.demand_section.clone()
-        {
-            Some(d_s) => d_s,
-            _ => Vec::new(),
-        };
+        self.nodes_demand()
 
-        let result : Vec<usize> =
-            d_section.iter().map(|(_, d)| *d).collect();
+    }
 
-        return result;
+}
+
+impl FisherJaikumarInstanceTrait for GraphInstance<'_>
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
+
+    }
+
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
+
+    }
+
+    fn get_nodes_demand(&self) -> Vec<usize> {
+
+        self.nodes_demand()
 
     }
+
+    fn get_vehicles_number(&self) -> Option<usize> {
+
+        parse_vehicles_number(
+            self.instance.specification.name,
+            &self.instance.specification.comment)
+
+    }
+
 }
 
-fn compute_distance_from_nodes(
-    e_weights : &Vec< Vec<usize>>,
-    n1        : Node,
-    n2        : Node)
-    -> f64
+impl AlnsInstanceTrait for GraphInstance<'_>
 {
 
-    let distance_n1_n2 : f64 = e_weights[n1][n2] as f64;
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
 
-    return distance_n1_n2;
+    }
+
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
+
+    }
+
+    fn get_nodes_demand(&self) -> Vec<usize> {
+
+        self.nodes_demand()
+
+    }
 
 }
 
-fn compute_node_list_sort_by_distance(
-    e_weights : &Vec< Vec<usize>>,
-    n1        : Node,
-    dimension : usize)
-    -> Vec<(Node, f64)>
+impl HgsInstanceTrait for GraphInstance<'_>
 {
 
-    let mut nodes_from_n1: Vec<(Node, f64)> = Vec::with_capacity(dimension - 2);
-    for i in 1..(dimension - 1)
-    {
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
+
+    }
+
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
+
+    }
+
+    fn get_nodes_demand(&self) -> Vec<usize> {
+
+        self.nodes_demand()
+
+    }
+
+}
+
+impl AntColonyInstanceTrait for GraphInstance<'_>
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
+
+    }
+
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
+
+    }
+
+    fn get_nodes_demand(&self) -> Vec<usize> {
+
+        self.nodes_demand()
+
+    }
+
+    fn compute_savings(&self) -> Vec<(usize, usize, usize)> {
+
+        <Self as SavingsInstanceTrait>::compute_savings(self)
+
+    }
+
+}
+
+impl ExactInstanceTrait for GraphInstance<'_>
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
+
+    }
+
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
+
+    }
+
+    fn get_nodes_demand(&self) -> Vec<usize> {
+
+        self.nodes_demand()
+
+    }
+
+}
+
+impl BoundsInstanceTrait for GraphInstance<'_>
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
+
+    }
+
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
+
+    }
+
+    fn get_nodes_demand(&self) -> Vec<usize> {
+
+        self.nodes_demand()
+
+    }
+
+    fn get_vehicles_number(&self) -> Option<usize> {
+
+        parse_vehicles_number(
+            self.instance.specification.name,
+            &self.instance.specification.comment)
+
+    }
+
+}
+
+impl RoutePoolInstanceTrait for GraphInstance<'_>
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
+
+    }
+
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
+
+    }
+
+    fn get_nodes_demand(&self) -> Vec<usize> {
+
+        self.nodes_demand()
+
+    }
+
+}
+
+impl MilpInstanceTrait for GraphInstance<'_>
+{
+
+    fn get_name(&self) -> String {
+
+        self.instance.specification.name.to_string()
 
-        if i == n1
+    }
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
+
+    }
+
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
+
+    }
+
+    fn get_nodes_demand(&self) -> Vec<usize> {
+
+        self.nodes_demand()
+
+    }
+
+}
+
+impl ParallelInstanceTrait for GraphInstance<'_>
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>> {
+
+        self.distance_matrix()
+
+    }
+
+}
+
+impl RecordToRecordInstanceTrait for GraphInstance<'_>
+{
+
+    fn get_distance_function(&self) -> Box<dyn Fn(usize, usize) -> f64 + Sync + '_> {
+
+        match (&self.instance.data.edge_weight_section, &self.instance.data.node_coord_section)
         {
-            continue;
+
+            /* Distances are computed from the coordinates,
+             * so that large instances do not require
+             * the full distance matrix. */
+            (None, Some(n_coord)) =>
+                {
+                    let distance_function : fn(&Coord, &Coord) -> f64 =
+                        match self.instance.specification.edge_weight_type
+                        {
+                            EDGE_WEIGHT_TYPE::GEO => compute_distance_geo,
+                            _ => compute_distance_euc,
+                        };
+
+                    Box::new(move |i, j| distance_function(&n_coord[i], &n_coord[j]))
+                }
+
+            _ =>
+                {
+                    let distance_matrix : Vec< Vec<f64>> = self.distance_matrix();
+
+                    Box::new(move |i, j| distance_matrix[i][j])
+                }
+
         }
 
-        let n2             : Node = i;
-        let distance_n1_n2 : f64  = compute_distance_from_nodes(e_weights, n1, n2);
+    }
 
-        nodes_from_n1.push((n2, distance_n1_n2));
+    fn get_capacity(&self) -> usize {
+
+        self.instance.specification.capacity
 
     }
 
-    /* Sort the node according to their distances
-     * from n1. */
-    nodes_from_n1.sort_by(|(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap());
+    fn get_nodes_demand(&self) -> Vec<usize> {
 
-    return nodes_from_n1;
+        self.nodes_demand()
+
+    }
 
 }
 
@@ -2,7 +2,7 @@
  * tsplib instances. */
 
 use crate::tsplib_parser::custom_types::{Coord};
-use crate::tsplib_parser::keyword_values::{EDGE_WEIGHT_FORMAT};
+use crate::tsplib_parser::keyword_values::{EDGE_WEIGHT_FORMAT, EDGE_WEIGHT_TYPE};
 
 /* Computes savings for instance
  * where the weight of each edge
@@ -242,6 +242,119 @@ pub(crate) fn compute_savings_fmatrix(
 
 }
 
+/* Computes the generalized savings
+ *   s_ij = d_0i + d_0j - lambda d_ij
+ *          + mu |d_0i - d_0j| + nu (q_i + q_j) / q_avg
+ * from the full matrix of the distances,
+ * regardless of the instance type. */
+pub(crate) fn compute_savings_parametric(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    lambda          : f64,
+    mu              : f64,
+    nu              : f64,
+    savings         : &mut Vec<(usize, usize, f64)>)
+{
+
+    let node_number  : usize = distance_matrix.len();
+
+    /* Average demand of the customers. */
+    let total_demand : usize = nodes_demand.iter().skip(1).sum();
+    let avg_demand   : f64   = match total_demand
+    {
+        0 => 1.0,
+        _ => total_demand as f64 / (node_number - 1) as f64,
+    };
+
+    for i in 1..node_number
+    {
+
+        for j in (i + 1)..node_number
+        {
+
+            let d_0_i : f64 = distance_matrix[0][i];
+            let d_0_j : f64 = distance_matrix[0][j];
+            let d_i_j : f64 = distance_matrix[i][j];
+
+            /* Compute the saving for the edge between i, j. */
+            let s : f64 = d_0_i + d_0_j - lambda * d_i_j
+                + mu * (d_0_i - d_0_j).abs()
+                + nu * (nodes_demand[i] + nodes_demand[j]) as f64 / avg_demand;
+
+            savings.push((i, j, s));
+
+        }
+
+    }
+
+}
+
+/* Computes the generalized savings only between
+ * each node and its nearest neighbours. Distances
+ * are computed on demand, therefore memory grows
+ * linearly with the number of nodes. */
+pub(crate) fn compute_savings_neighbours(
+    distance     : &dyn Fn(usize, usize) -> f64,
+    nodes_demand : &[usize],
+    neighbours   : usize,
+    lambda       : f64,
+    mu           : f64,
+    nu           : f64,
+    savings      : &mut Vec<(usize, usize, f64)>)
+{
+
+    let node_number  : usize = nodes_demand.len();
+    let k            : usize = neighbours.min(node_number.saturating_sub(2));
+
+    /* Average demand of the customers. */
+    let total_demand : usize = nodes_demand.iter().skip(1).sum();
+    let avg_demand   : f64   = match total_demand
+    {
+        0 => 1.0,
+        _ => total_demand as f64 / (node_number - 1) as f64,
+    };
+
+    let depot_distance : Vec<f64> = (0..node_number).map(|i| distance(0, i)).collect();
+
+    if k == 0
+    {
+        return;
+    }
+
+    for i in 1..node_number
+    {
+
+        /* Select the k nearest customers of i. */
+        let mut distances : Vec<(usize, f64)> = (1..node_number)
+            .filter(|&j| j != i)
+            .map(|j| (j, distance(i, j)))
+            .collect();
+        distances.select_nth_unstable_by(k - 1, |a, b| a.1.partial_cmp(&b.1).unwrap());
+
+        for &(j, d_i_j) in &distances[..k]
+        {
+
+            let d_0_i : f64 = depot_distance[i];
+            let d_0_j : f64 = depot_distance[j];
+
+            /* Compute the saving for the edge between i, j. */
+            let s : f64 = d_0_i + d_0_j - lambda * d_i_j
+                + mu * (d_0_i - d_0_j).abs()
+                + nu * (nodes_demand[i] + nodes_demand[j]) as f64 / avg_demand;
+
+            savings.push((i.min(j), i.max(j), s));
+
+        }
+
+    }
+
+    /* A couple appears twice when each node is
+     * among the neighbours of the other. */
+    savings.sort_unstable_by_key(|&(i, j, _)| (i, j));
+    savings.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+
+}
+
 /* Convert half matrix into full matrix. */
 pub(crate) fn from_hmatrix_to_fmatrix(
     edge_weight     : &Option< Vec< Vec<usize>>>,
@@ -344,4 +457,345 @@ pub(crate) fn from_hmatrix_to_fmatrix(
         _ => None
     }
 
-}
\ No newline at end of file
+}
+
+/* Compute the full matrix of the distances
+ * between each couple of nodes. The distances
+ * are consistent with the ones used to
+ * compute the cost of the routes. None if the
+ * format of the edge weight is not supported. */
+pub(crate) fn compute_distance_matrix(
+    edge_weight        : &Option< Vec< Vec<usize>>>,
+    edge_weight_format : &Option<EDGE_WEIGHT_FORMAT>,
+    edge_weight_type   : &EDGE_WEIGHT_TYPE,
+    node_coord         : &Option< Vec<Coord>>,
+    node_number        : usize)
+    -> Option< Vec< Vec<f64>>>
+{
+
+    let mut result : Vec< Vec<f64>> = vec![vec![0.0; node_number]; node_number];
+
+    match (edge_weight, node_coord)
+    {
+
+        (Some(e_weight), _) =>
+            {
+
+                /* Convert the edge weight into
+                 * a full matrix, if required. */
+                let e_w_matrix : Vec< Vec<usize>> = match edge_weight_format
+                {
+                    Some(EDGE_WEIGHT_FORMAT::FULL_MATRIX) => e_weight.clone(),
+                    Some(e_w_f) =>
+                        from_hmatrix_to_fmatrix(edge_weight, node_number, e_w_f.clone())?,
+                    _ => e_weight.clone(),
+                };
+
+                for i in 0..node_number
+                {
+                    for j in 0..node_number
+                    {
+                        result[i][j] = e_w_matrix[i][j] as f64;
+                    }
+                }
+
+            }
+
+        (None, Some(n_coord)) =>
+            {
+
+                /* Geographical distances are used only
+                 * for GEO instances, euclidean otherwise. */
+                let distance_function : fn(&Coord, &Coord) -> f64 =
+                    match edge_weight_type
+                    {
+                        EDGE_WEIGHT_TYPE::GEO => compute_distance_geo,
+                        _ => compute_distance_euc,
+                    };
+
+                for i in 0..node_number
+                {
+                    for j in (i + 1)..node_number
+                    {
+                        let d_i_j : f64 = distance_function(&n_coord[i], &n_coord[j]);
+                        result[i][j] = d_i_j;
+                        result[j][i] = d_i_j;
+                    }
+                }
+
+            }
+
+        _ => ()
+
+    }
+
+    return Some(result);
+
+}
+
+/* Embed the nodes into the plane with classical
+ * multidimensional scaling, so that instances
+ * with explicit distances can be handled as
+ * instances with coordinates.
+ * The coordinates are the two eigenvectors of
+ * the double centered matrix of squared distances
+ * with the largest eigenvalues, scaled by the
+ * square roots of the eigenvalues. */
+pub(crate) fn compute_mds_embedding(distance_matrix : &[Vec<f64>]) -> Vec<(f64, f64)>
+{
+
+    let node_number : usize = distance_matrix.len();
+
+    /* Compute the squared distances and
+     * their row and total means. */
+    let squared     : Vec< Vec<f64>> = distance_matrix.iter()
+        .map(|row| row.iter().map(|d| d * d).collect())
+        .collect();
+    let row_mean    : Vec<f64> = squared.iter()
+        .map(|row| row.iter().sum::<f64>() / node_number as f64)
+        .collect();
+    let total_mean  : f64 = row_mean.iter().sum::<f64>() / node_number as f64;
+
+    /* Double centering: B = -1/2 J D^2 J. */
+    let mut b_matrix : Vec< Vec<f64>> = vec![vec![0.0; node_number]; node_number];
+    for i in 0..node_number
+    {
+        for j in 0..node_number
+        {
+            b_matrix[i][j] =
+                -0.5 * (squared[i][j] - row_mean[i] - row_mean[j] + total_mean);
+        }
+    }
+
+    let (eigenvalues, eigenvectors) : (Vec<f64>, Vec< Vec<f64>>) = compute_symmetric_eigen(&b_matrix);
+
+    /* Eigenvalues sorted in decreasing order. */
+    let mut order : Vec<usize> = (0..node_number).collect();
+    order.sort_by(|&a, &b| eigenvalues[b].partial_cmp(&eigenvalues[a]).unwrap());
+
+    let mut points : Vec<(f64, f64)> = vec![(0.0, 0.0); node_number];
+
+    for (component, &k) in order.iter().take(2).enumerate()
+    {
+
+        /* B may have negative eigenvalues when the distances
+         * are not euclidean. A non positive eigenvalue gives
+         * no information on the node positions. */
+        let scale : f64 = eigenvalues[k].max(0.0).sqrt();
+        for i in 0..node_number
+        {
+            let coordinate : f64 = eigenvectors[i][k] * scale;
+            match component
+            {
+                0 => points[i].0 = coordinate,
+                _ => points[i].1 = coordinate,
+            }
+        }
+
+    }
+
+    return points;
+
+}
+
+/* Maximum number of sweeps of the Jacobi method,
+ * which in practice converges in less than ten. */
+const JACOBI_SWEEPS    : usize = 100;
+
+/* Relative size of the off-diagonal entries
+ * at which the Jacobi method stops. */
+const JACOBI_TOLERANCE : f64   = 1e-12;
+
+/* Eigenvalues and eigenvectors of a symmetric matrix,
+ * by the cyclic Jacobi method: each rotation sets an
+ * off-diagonal entry to zero, and sweeps over all of
+ * them are repeated until they are negligible with
+ * respect to the whole matrix. The eigenvectors are
+ * the columns of the returned matrix. */
+fn compute_symmetric_eigen(matrix : &[Vec<f64>]) -> (Vec<f64>, Vec< Vec<f64>>)
+{
+
+    let n            : usize          = matrix.len();
+    let mut a        : Vec< Vec<f64>> = matrix.to_vec();
+    let mut vectors  : Vec< Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    let norm : f64 = a.iter().flatten().map(|v| v * v).sum::<f64>().sqrt();
+
+    for _ in 0..JACOBI_SWEEPS
+    {
+
+        let off_diagonal : f64 = (0..n)
+            .map(|p| (0..n).filter(|&q| q != p).map(|q| a[p][q] * a[p][q]).sum::<f64>())
+            .sum::<f64>()
+            .sqrt();
+        if off_diagonal <= JACOBI_TOLERANCE * norm
+        {
+            break;
+        }
+
+        for p in 0..n
+        {
+            for q in (p + 1)..n
+            {
+
+                if a[p][q] == 0.0
+                {
+                    continue;
+                }
+
+                /* Rotation zeroing a[p][q], through the
+                 * smaller of the two possible angles. */
+                let theta : f64 = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t     : f64 = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c     : f64 = 1.0 / (t * t + 1.0).sqrt();
+                let s     : f64 = t * c;
+
+                for row in a.iter_mut()
+                {
+                    let (a_kp, a_kq) : (f64, f64) = (row[p], row[q]);
+                    row[p] = c * a_kp - s * a_kq;
+                    row[q] = s * a_kp + c * a_kq;
+                }
+                let (upper, lower) = a.split_at_mut(q);
+                for (a_pk, a_qk) in upper[p].iter_mut().zip(lower[0].iter_mut())
+                {
+                    let (pk, qk) : (f64, f64) = (*a_pk, *a_qk);
+                    *a_pk = c * pk - s * qk;
+                    *a_qk = s * pk + c * qk;
+                }
+                for row in vectors.iter_mut()
+                {
+                    let (v_kp, v_kq) : (f64, f64) = (row[p], row[q]);
+                    row[p] = c * v_kp - s * v_kq;
+                    row[q] = s * v_kp + c * v_kq;
+                }
+
+            }
+        }
+
+    }
+
+    let eigenvalues : Vec<f64> = (0..n).map(|i| a[i][i]).collect();
+
+    return (eigenvalues, vectors);
+
+}
+
+/* Number of vehicles of the instance, as reported in
+ * the comments ("No of trucks: 4") or in the name
+ * ("F-n45-k4"), if any. */
+pub(crate) fn parse_vehicles_number(
+    name     : &str,
+    comments : &Vec<&str>)
+    -> Option<usize>
+{
+
+    let leading_number = |s : &str| -> Option<usize>
+        {
+            let digits : String = s.trim_start()
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            digits.parse::<usize>().ok()
+        };
+
+    for comment in comments
+    {
+        if let Some(position) = comment.find("trucks:")
+        {
+            if let Some(vehicles) = leading_number(&comment[position + "trucks:".len()..])
+            {
+                return Some(vehicles);
+            }
+        }
+    }
+
+    let result : Option<usize> = name.rsplit('-')
+        .next()
+        .filter(|s| s.starts_with('k'))
+        .and_then(|s| leading_number(&s[1..]));
+
+    return result;
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+
+    fn euclidean(a : (f64, f64), b : (f64, f64)) -> f64
+    {
+
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+
+    }
+
+    #[test]
+    fn symmetric_eigen_decomposition()
+    {
+
+        let matrix : Vec< Vec<f64>> = vec![
+            vec![4.0, 1.0, -2.0],
+            vec![1.0, 2.0, 0.0],
+            vec![-2.0, 0.0, 3.0],
+        ];
+        let (values, vectors) = compute_symmetric_eigen(&matrix);
+
+        for k in 0..3
+        {
+            for i in 0..3
+            {
+                let product : f64 = (0..3).map(|j| matrix[i][j] * vectors[j][k]).sum();
+                assert!((product - values[k] * vectors[i][k]).abs() < 1e-9);
+            }
+        }
+        assert!((values.iter().sum::<f64>() - 9.0).abs() < 1e-9);
+
+    }
+
+    #[test]
+    fn mds_embedding_recovers_planar_distances()
+    {
+
+        let points : Vec<(f64, f64)> = vec![
+            (0.0, 0.0), (3.0, 0.0), (0.0, 4.0), (5.0, 5.0),
+            (-2.0, 1.0), (1.0, -3.0), (7.0, -1.0), (-4.0, -4.0),
+        ];
+        let distance_matrix : Vec< Vec<f64>> = points.iter()
+            .map(|&a| points.iter().map(|&b| euclidean(a, b)).collect())
+            .collect();
+
+        let embedding : Vec<(f64, f64)> = compute_mds_embedding(&distance_matrix);
+
+        for i in 0..points.len()
+        {
+            for j in 0..points.len()
+            {
+                assert!((euclidean(embedding[i], embedding[j]) - distance_matrix[i][j]).abs() < 1e-6);
+            }
+        }
+
+    }
+
+    #[test]
+    fn distance_matrix_of_an_unsupported_format()
+    {
+
+        let edge_weight : Option< Vec< Vec<usize>>> = Some(vec![vec![1, 2], vec![3]]);
+
+        assert_eq!(
+            compute_distance_matrix(
+                &edge_weight, &Some(EDGE_WEIGHT_FORMAT::FUNCTION), &EDGE_WEIGHT_TYPE::EXPLICIT, &None, 3),
+            None);
+        assert_eq!(
+            compute_distance_matrix(
+                &edge_weight, &Some(EDGE_WEIGHT_FORMAT::UPPER_ROW), &EDGE_WEIGHT_TYPE::EXPLICIT, &None, 3),
+            Some(vec![vec![0.0, 1.0, 2.0], vec![1.0, 0.0, 3.0], vec![2.0, 3.0, 0.0]]));
+
+    }
+
+}
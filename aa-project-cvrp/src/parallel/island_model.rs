@@ -0,0 +1,154 @@
+use std::thread;
+use std::time::Duration;
+use crate::hybrid_genetic_search::hgs_instance_trait::HgsInstanceTrait;
+use crate::hybrid_genetic_search::hgs_imp::HgsSolver;
+use crate::hybrid_genetic_search::archipelago::Archipelago;
+use crate::parallel::multi_start::run_cost;
+use crate::algorithm_interface::{CVRPSolver, SolveControl};
+
+/* Here the island model of the hybrid genetic
+ * search is implemented. A population evolves on
+ * each island, in its own thread and from its own
+ * seed, and the islands periodically exchange
+ * their best individuals along a ring. */
+pub struct IslandModelSolver<'a>
+{
+
+    pub instance           : &'a dyn HgsInstanceTrait,
+
+    pub islands            : usize,
+
+    /* Number of iterations of each island
+     * between two migrations. */
+    pub migration_interval : usize,
+
+    /* Number of individuals sent at each migration. */
+    pub migrants           : usize,
+
+    pub time_limit         : Duration,
+
+    /* Seed of the first island, the following
+     * ones using the next seeds. */
+    pub seed               : u64,
+
+}
+
+/* Methods of IslandModelSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> IslandModelSolver<'a>
+{
+
+    /* Island model with an island per available
+     * core, running for the given amount of time. */
+    pub fn new(
+        instance   : &'a dyn HgsInstanceTrait,
+        time_limit : Duration,
+        seed       : u64)
+        -> IslandModelSolver<'a>
+    {
+
+        IslandModelSolver {
+            instance,
+            islands            : thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            migration_interval : 500,
+            migrants           : 2,
+            time_limit,
+            seed,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn HgsInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    pub fn set_seed(&mut self, seed : u64)
+    {
+
+        self.seed = seed;
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for IslandModelSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let distance_matrix : Vec< Vec<f64>> = self.instance.get_distance_matrix();
+        let islands         : usize          = self.islands.max(1);
+        let archipelago     : Archipelago    = Archipelago::new(islands, self.migration_interval, self.migrants);
+
+        let solvers : Vec<HgsSolver> = (0..islands)
+            .map(|i| HgsSolver::new(self.instance, self.time_limit, self.seed.wrapping_add(i as u64)))
+            .collect();
+
+        let solutions : Vec< Vec< Vec<usize>>> = thread::scope(|scope| {
+            let handles : Vec<thread::ScopedJoinHandle<Vec< Vec<usize>>>> = solvers.iter()
+                .enumerate()
+                .map(|(island, solver)| {
+                    let archipelago : &Archipelago = &archipelago;
+                    scope.spawn(move || solver.solve_on_island(control, archipelago, island))
+                })
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+
+        /* As in the multi-start, the islands
+         * without routes are the worst. */
+        let result : Vec< Vec<usize>> = solutions.into_iter()
+            .min_by(|a, b| run_cost(&distance_matrix, a)
+                .partial_cmp(&run_cost(&distance_matrix, b))
+                .unwrap())
+            .unwrap();
+
+        return result;
+
+    }
+
+    fn get_seed(&self) -> Option<u64>
+    {
+
+        Some(self.seed)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+
+    #[test]
+    fn islands_give_feasible_routes()
+    {
+
+        let graph  : GraphInstance     = read_test_instance("F-n45-k4");
+        let solver : IslandModelSolver = IslandModelSolver {
+            islands            : 3,
+            migration_interval : 20,
+            ..IslandModelSolver::new(&graph, Duration::from_millis(300), 1)
+        };
+
+        assert_feasible(&graph, &solver.solve());
+
+    }
+
+}
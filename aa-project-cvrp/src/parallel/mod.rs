@@ -0,0 +1,3 @@
+pub mod parallel_instance_trait;
+pub mod multi_start;
+pub mod island_model;
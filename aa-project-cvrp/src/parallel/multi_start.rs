@@ -0,0 +1,275 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::parallel::parallel_instance_trait::ParallelInstanceTrait;
+use crate::algorithm_interface::{CVRPSolver, SolveControl, compute_routes_cost};
+
+/* Here the parallel multi-start is implemented.
+ * The given solvers, usually the same algorithm
+ * with different seeds or parameters, are run
+ * concurrently by a pool of threads, each taking
+ * the next solver to run when it is free, and the
+ * best of their solutions is returned. */
+pub struct MultiStartSolver<'a>
+{
+
+    pub instance : &'a dyn ParallelInstanceTrait,
+
+    pub solvers  : Vec<&'a dyn CVRPSolver>,
+
+    /* Number of threads of the pool. */
+    pub threads  : usize,
+
+}
+
+/* Cost and time required by each run
+ * of the multi-start, in the order of
+ * the solvers. */
+pub struct MultiStartStatistics
+{
+
+    pub costs    : Vec<f64>,
+
+    pub times    : Vec<Duration>,
+
+    /* Index of the run with the best cost. */
+    pub best_run : usize,
+
+}
+
+/* Routes found by a run with the time it took,
+ * None until the run is finished. */
+type RunResult = Option<(Vec< Vec<usize>>, Duration)>;
+
+/* Methods of MultiStartStatistics. */
+#[allow(dead_code)]
+impl MultiStartStatistics
+{
+
+    pub fn best_cost(&self) -> f64
+    {
+
+        self.costs[self.best_run]
+
+    }
+
+    pub fn worst_cost(&self) -> f64
+    {
+
+        self.costs.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+
+    }
+
+    pub fn mean_cost(&self) -> f64
+    {
+
+        self.costs.iter().sum::<f64>() / self.costs.len() as f64
+
+    }
+
+    /* Sum of the times of the runs, which
+     * is the time a single thread requires. */
+    pub fn total_time(&self) -> Duration
+    {
+
+        self.times.iter().sum()
+
+    }
+
+    pub fn standard_deviation(&self) -> f64
+    {
+
+        let mean     : f64 = self.mean_cost();
+        let variance : f64 = self.costs.iter().map(|c| (c - mean) * (c - mean)).sum::<f64>()
+            / self.costs.len() as f64;
+
+        return variance.sqrt();
+
+    }
+
+}
+
+/* Cost of the routes of a run. The runs stopped
+ * before starting return no routes, and are the
+ * worst. */
+pub(crate) fn run_cost(distance_matrix : &[Vec<f64>], routes : &Vec< Vec<usize>>) -> f64
+{
+
+    if routes.is_empty() { f64::INFINITY } else { compute_routes_cost(distance_matrix, routes) }
+
+}
+
+/* Methods of MultiStartSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> MultiStartSolver<'a>
+{
+
+    /* Multi-start over the solvers, with
+     * a thread per available core. */
+    pub fn new(instance : &'a dyn ParallelInstanceTrait, solvers : Vec<&'a dyn CVRPSolver>) -> MultiStartSolver<'a>
+    {
+
+        MultiStartSolver {
+            instance,
+            solvers,
+            threads : thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn ParallelInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    /* Run every solver within the limits of the
+     * control, which is shared by all the runs.
+     * Return the best routes and the statistics,
+     * None if there are no solvers to run. */
+    pub fn run(&self, control : &SolveControl) -> Option<(Vec< Vec<usize>>, MultiStartStatistics)>
+    {
+
+        if self.solvers.is_empty()
+        {
+            return None;
+        }
+
+        let distance_matrix : Vec< Vec<f64>> = self.instance.get_distance_matrix();
+
+        let next_run : AtomicUsize            = AtomicUsize::new(0);
+        let results  : Mutex< Vec<RunResult>> = Mutex::new(vec![None; self.solvers.len()]);
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads.max(1).min(self.solvers.len())
+            {
+                scope.spawn(|| {
+                    loop
+                    {
+                        let run : usize = next_run.fetch_add(1, Ordering::Relaxed);
+                        if run >= self.solvers.len()
+                        {
+                            break;
+                        }
+                        let start  : Instant          = Instant::now();
+                        let routes : Vec< Vec<usize>> = self.solvers[run].solve_with_control(control);
+                        results.lock().unwrap()[run] = Some((routes, start.elapsed()));
+                    }
+                });
+            }
+        });
+
+        let (routes, times) : (Vec< Vec< Vec<usize>>>, Vec<Duration>) = results.into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|result| result.unwrap())
+            .unzip();
+        let costs    : Vec<f64> = routes.iter().map(|r| run_cost(&distance_matrix, r)).collect();
+        let best_run : usize    = (0..costs.len())
+            .min_by(|&a, &b| costs[a].partial_cmp(&costs[b]).unwrap())
+            .unwrap();
+
+        let result : Vec< Vec<usize>> = routes.into_iter().nth(best_run).unwrap();
+
+        return Some((result, MultiStartStatistics { costs, times, best_run }));
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for MultiStartSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        return match self.run(control)
+        {
+            Some((routes, _)) => routes,
+            None              => Vec::new(),
+        };
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::model::{GraphInstance, read_test_instance};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+    use crate::local_search::inter_route_imp::InterRouteLocalSearch;
+    use crate::iterated_local_search::iterated_local_search_imp::IteratedLocalSearch;
+    use crate::algorithm_interface::StoppingCriterion;
+
+    #[test]
+    fn best_of_the_runs()
+    {
+
+        /* Iterated local searches from different
+         * seeds, which reach different routes. */
+        let graph    : GraphInstance            = read_test_instance("att-n48-k4");
+        let savings  : SavingsSolver            = SavingsSolver::new(&graph);
+        let local    : InterRouteLocalSearch    = InterRouteLocalSearch::new(&graph);
+        let searches : Vec<IteratedLocalSearch> = (0..4)
+            .map(|seed| IteratedLocalSearch {
+                stopping_criterion : StoppingCriterion::Iterations(10),
+                ..IteratedLocalSearch::new(&graph, &savings, vec![&local], seed)
+            })
+            .collect();
+        let solver   : MultiStartSolver         = MultiStartSolver::new(
+            &graph, searches.iter().map(|s| s as &dyn CVRPSolver).collect());
+
+        let (routes, statistics) : (Vec< Vec<usize>>, MultiStartStatistics) =
+            solver.run(&SolveControl::unlimited()).unwrap();
+
+        let distance_matrix : Vec< Vec<f64>> = ParallelInstanceTrait::get_distance_matrix(&graph);
+        assert_eq!(statistics.costs.len(), 4);
+        assert!(statistics.costs.iter().any(|&c| c != statistics.best_cost()));
+        assert_eq!(compute_routes_cost(&distance_matrix, &routes), statistics.best_cost());
+        assert!(statistics.costs.iter().all(|&c| c >= statistics.best_cost()));
+        for (seed, search) in searches.iter().enumerate()
+        {
+            assert_eq!(statistics.costs[seed], compute_routes_cost(&distance_matrix, &search.solve()));
+        }
+
+    }
+
+    #[test]
+    fn empty_routes_are_the_worst()
+    {
+
+        let graph           : GraphInstance  = read_test_instance("gr-n17-k3");
+        let distance_matrix : Vec< Vec<f64>> = ParallelInstanceTrait::get_distance_matrix(&graph);
+
+        assert_eq!(run_cost(&distance_matrix, &Vec::new()), f64::INFINITY);
+        assert_eq!(run_cost(&distance_matrix, &vec![vec![1, 2]]), compute_routes_cost(&distance_matrix, &vec![vec![1, 2]]));
+
+    }
+
+    #[test]
+    fn no_solvers_to_run()
+    {
+
+        let graph  : GraphInstance    = read_test_instance("gr-n17-k3");
+        let solver : MultiStartSolver = MultiStartSolver::new(&graph, Vec::new());
+
+        assert!(solver.run(&SolveControl::unlimited()).is_none());
+        assert!(solver.solve().is_empty());
+
+    }
+
+}
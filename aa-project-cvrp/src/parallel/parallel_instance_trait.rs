@@ -0,0 +1,10 @@
+/* This trait define the methods required
+ * by the parallel solvers, available only
+ * for GraphInstance when explicitly
+ * implemented. */
+pub trait ParallelInstanceTrait : Sync
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>>;
+
+}
@@ -0,0 +1,2 @@
+pub mod record_to_record_instance_trait;
+pub mod record_to_record_imp;
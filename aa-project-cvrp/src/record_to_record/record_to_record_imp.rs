@@ -0,0 +1,665 @@
+use std::time::Instant;
+use crate::record_to_record::record_to_record_instance_trait::RecordToRecordInstanceTrait;
+use crate::local_search::moves::{Move, Neighbourhood, EPSILON, customer_positions, node_before, node_at};
+use crate::algorithm_interface::{CVRPSolver, StoppingCriterion, SolveControl};
+
+/* Threshold on the cost of the routes
+ * obtained by accepting a move. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum AcceptanceThreshold
+{
+
+    /* Accept the routes costing less than the
+     * record, the best cost found, increased
+     * by the given fraction. */
+    RecordToRecord { deviation : f64 },
+
+    /* Accept the routes costing less than the current
+     * ones increased by the given fraction, which is
+     * multiplied by decay after every iteration. */
+    ThresholdAccepting { threshold : f64, decay : f64 },
+
+}
+
+/* Here the record-to-record travel of Li, Golden
+ * and Wasil is implemented, together with the more
+ * general threshold accepting. Starting from the
+ * routes of another solver, each iteration is made
+ * of an uphill phase, in which the best move of
+ * each customer is applied if the resulting cost is
+ * below the threshold, followed by a downhill phase,
+ * which applies improving moves only. The moves are
+ * relocate, swap and 2-opt*, restricted to the
+ * granular lists of nearest neighbours, so that the
+ * time of an iteration grows linearly with the
+ * number of customers. The distances are computed
+ * on demand, without the full distance matrix. */
+pub struct RecordToRecordSolver<'a>
+{
+
+    pub instance           : &'a dyn RecordToRecordInstanceTrait,
+
+    /* Solver computing the initial routes. */
+    pub initial_solver     : &'a dyn CVRPSolver,
+
+    pub threshold          : AcceptanceThreshold,
+
+    pub neighbourhoods     : Vec<Neighbourhood>,
+
+    /* Number of nearest neighbours of each customer
+     * considered by the moves. */
+    pub granularity        : usize,
+
+    /* Number of passes over the customers
+     * of each uphill phase. */
+    pub uphill_passes      : usize,
+
+    /* Number of iterations without a new record
+     * after which the search stops. */
+    pub patience           : usize,
+
+    pub stopping_criterion : StoppingCriterion,
+
+}
+
+/* Methods of RecordToRecordSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> RecordToRecordSolver<'a>
+{
+
+    /* Record-to-record travel with the
+     * parameters of Li, Golden and Wasil. */
+    pub fn new(
+        instance       : &'a dyn RecordToRecordInstanceTrait,
+        initial_solver : &'a dyn CVRPSolver)
+        -> RecordToRecordSolver<'a>
+    {
+
+        RecordToRecordSolver {
+            instance,
+            initial_solver,
+            threshold          : AcceptanceThreshold::RecordToRecord { deviation : 0.01 },
+            neighbourhoods     : vec![
+                Neighbourhood::Relocate,
+                Neighbourhood::Swap,
+                Neighbourhood::TwoOptStar,
+            ],
+            granularity        : 30,
+            uphill_passes      : 30,
+            patience           : 5,
+            stopping_criterion : StoppingCriterion::Iterations(100),
+        }
+
+    }
+
+    /* Threshold accepting, starting from a
+     * threshold of the given fraction. */
+    pub fn threshold_accepting(
+        instance       : &'a dyn RecordToRecordInstanceTrait,
+        initial_solver : &'a dyn CVRPSolver,
+        threshold      : f64)
+        -> RecordToRecordSolver<'a>
+    {
+
+        RecordToRecordSolver {
+            threshold : AcceptanceThreshold::ThresholdAccepting { threshold, decay : 0.8 },
+            ..RecordToRecordSolver::new(instance, initial_solver)
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn RecordToRecordInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+}
+
+/* Distance between two nodes, computed on demand. */
+type Distance<'d> = dyn Fn(usize, usize) -> f64 + 'd;
+
+/* Cost of the routes, as compute_routes_cost,
+ * with the distances computed on demand. */
+fn routes_cost(distance : &Distance, routes : &[Vec<usize>]) -> f64
+{
+
+    let mut result : f64 = 0.0;
+
+    for route in routes
+    {
+        let mut previous_node : usize = 0;
+        for &current_node in route
+        {
+            result        += distance(previous_node, current_node);
+            previous_node  = current_node;
+        }
+        result += distance(previous_node, 0);
+    }
+
+    return result;
+
+}
+
+/* The k nearest customers of each customer, sorted
+ * by increasing distance, ties broken by index as
+ * in nearest_neighbours of the local search. Only
+ * the k nearest are sorted, after selecting them
+ * in linear time. */
+fn nearest_neighbours(distance : &Distance, node_number : usize, k : usize) -> Vec< Vec<usize>>
+{
+
+    let k          : usize            = k.min(node_number.saturating_sub(2));
+    let mut result : Vec< Vec<usize>> = vec![Vec::new(); node_number];
+
+    if k == 0
+    {
+        return result;
+    }
+
+    let closer = |a : &(usize, f64), b : &(usize, f64)| a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0));
+
+    for (u, nearest) in result.iter_mut().enumerate().skip(1)
+    {
+        let mut neighbours : Vec<(usize, f64)> = (1..node_number)
+            .filter(|&v| v != u)
+            .map(|v| (v, distance(u, v)))
+            .collect();
+        neighbours.select_nth_unstable_by(k - 1, closer);
+        neighbours.truncate(k);
+        neighbours.sort_by(closer);
+        *nearest = neighbours.into_iter().map(|(v, _)| v).collect();
+    }
+
+    return result;
+
+}
+
+/* Variation of the cost of the route if the customers
+ * from position to position + length are replaced
+ * by the given segment. */
+fn segment_replacement_delta(
+    distance : &Distance,
+    route    : &[usize],
+    position : usize,
+    length   : usize,
+    segment  : &[usize])
+    -> f64
+{
+
+    let path_cost = |nodes : &[usize]| -> f64
+    {
+        let mut cost     : f64   = 0.0;
+        let mut previous : usize = node_before(route, position);
+        for &n in nodes
+        {
+            cost     += distance(previous, n);
+            previous  = n;
+        }
+        cost + distance(previous, node_at(route, position + length))
+    };
+
+    path_cost(segment) - path_cost(&route[position..position + length])
+
+}
+
+/* Variation of the cost of the routes if the
+ * move is applied, as Move::delta, with the
+ * distances computed on demand. */
+fn move_delta(distance : &Distance, m : &Move, routes : &[Vec<usize>]) -> f64
+{
+
+    let d = distance;
+
+    match *m
+    {
+
+        Move::TwoOpt { route, i, j } =>
+            {
+                let r : &Vec<usize> = &routes[route];
+                let a : usize       = node_before(r, i);
+                let b : usize       = node_at(r, j + 1);
+
+                d(a, r[j]) + d(r[i], b) - d(a, r[i]) - d(r[j], b)
+            }
+
+        Move::OrOpt { route, from, length, to } =>
+            {
+                let r     : &Vec<usize> = &routes[route];
+                let first : usize       = r[from];
+                let last  : usize       = r[from + length - 1];
+                let a     : usize       = node_before(r, from);
+                let b     : usize       = node_at(r, from + length);
+
+                let reduced = |k : usize| if k < from { r[k] } else { r[k + length] };
+                let reduced_len : usize = r.len() - length;
+                let c : usize = if to == 0 { 0 } else { reduced(to - 1) };
+                let e : usize = if to == reduced_len { 0 } else { reduced(to) };
+
+                d(a, b) - d(a, first) - d(last, b) + d(c, first) + d(last, e) - d(c, e)
+            }
+
+        Move::Relocate { from_route, from, to_route, to } =>
+            segment_replacement_delta(d, &routes[from_route], from, 1, &[])
+                + segment_replacement_delta(
+                    d, &routes[to_route], to, 0, &routes[from_route][from..=from]),
+
+        Move::Swap { route_1, position_1, route_2, position_2 } =>
+            segment_replacement_delta(
+                d, &routes[route_1], position_1, 1, &routes[route_2][position_2..=position_2])
+                + segment_replacement_delta(
+                    d, &routes[route_2], position_2, 1, &routes[route_1][position_1..=position_1]),
+
+        Move::TwoOptStar { route_1, position_1, route_2, position_2 } =>
+            {
+                let a_1 : usize = node_before(&routes[route_1], position_1);
+                let b_1 : usize = node_at(&routes[route_1], position_1);
+                let a_2 : usize = node_before(&routes[route_2], position_2);
+                let b_2 : usize = node_at(&routes[route_2], position_2);
+
+                d(a_1, b_2) + d(a_2, b_1) - d(a_1, b_1) - d(a_2, b_2)
+            }
+
+        Move::CrossExchange { route_1, position_1, length_1, route_2, position_2, length_2 } =>
+            segment_replacement_delta(
+                d, &routes[route_1], position_1, length_1,
+                &routes[route_2][position_2..position_2 + length_2])
+                + segment_replacement_delta(
+                    d, &routes[route_2], position_2, length_2,
+                    &routes[route_1][position_1..position_1 + length_1]),
+
+    }
+
+}
+
+/* Improve a single route with 2-opt and Or-opt, as
+ * optimize_route_with of the local search, with
+ * the distances computed on demand. */
+fn optimize_route(distance : &Distance, route : &mut Vec<usize>, control : &SolveControl)
+{
+
+    let routes : &mut [Vec<usize>] = std::slice::from_mut(route);
+
+    let mut improved : bool = true;
+    while improved && !control.should_stop()
+    {
+
+        improved = false;
+
+        for neighbourhood in [Neighbourhood::TwoOpt, Neighbourhood::OrOpt]
+        {
+
+            let improving_move : Option<Move> = neighbourhood.moves(routes)
+                .into_iter()
+                .find(|m| move_delta(distance, m, routes) < -EPSILON);
+
+            if let Some(m) = improving_move
+            {
+                m.apply(routes);
+                improved = true;
+                break;
+            }
+
+        }
+
+    }
+
+}
+
+/* The routes and the data kept up to date
+ * while the moves are applied. */
+struct SearchState
+{
+
+    routes      : Vec< Vec<usize>>,
+
+    loads       : Vec<usize>,
+
+    positions   : Vec<(usize, usize)>,
+
+    cost        : f64,
+
+    /* Index of an empty route, kept
+     * among the routes at all times. */
+    empty_route : usize,
+
+}
+
+impl SearchState
+{
+
+    fn new(routes : Vec< Vec<usize>>, nodes_demand : &[usize], distance : &Distance) -> SearchState
+    {
+
+        let mut routes : Vec< Vec<usize>> = routes;
+        routes.retain(|r| !r.is_empty());
+        routes.push(Vec::new());
+
+        SearchState {
+            loads       : routes.iter().map(|r| r.iter().map(|&n| nodes_demand[n]).sum()).collect(),
+            positions   : customer_positions(&routes, nodes_demand.len()),
+            cost        : routes_cost(distance, &routes),
+            empty_route : routes.len() - 1,
+            routes,
+        }
+
+    }
+
+    /* Apply the move, updating the loads and the positions
+     * of the modified routes, and keep an empty route. */
+    fn apply(&mut self, m : Move, delta : f64, nodes_demand : &[usize])
+    {
+
+        for (r, load) in m.loads_after(&self.routes, &self.loads, nodes_demand)
+        {
+            self.loads[r] = load;
+        }
+        m.apply(&mut self.routes);
+        self.cost += delta;
+
+        for r in m.routes()
+        {
+            for (p, &n) in self.routes[r].iter().enumerate()
+            {
+                self.positions[n] = (r, p);
+            }
+        }
+
+        if !self.routes[self.empty_route].is_empty()
+        {
+            self.routes.push(Vec::new());
+            self.loads.push(0);
+            self.empty_route = self.routes.len() - 1;
+        }
+
+    }
+
+}
+
+impl<'a> RecordToRecordSolver<'a>
+{
+
+    /* Best feasible move of the customer over the
+     * neighbourhoods, with its delta, if any. A
+     * customer can be moved alone into an empty
+     * route. */
+    fn best_move(
+        &self,
+        distance     : &Distance,
+        nodes_demand : &[usize],
+        capacity     : usize,
+        neighbours   : &[Vec<usize>],
+        state        : &SearchState,
+        u            : usize)
+        -> Option<(Move, f64)>
+    {
+
+        let (route, position) : (usize, usize) = state.positions[u];
+
+        let mut result : Option<(Move, f64)> = None;
+
+        for neighbourhood in &self.neighbourhoods
+        {
+
+            let mut moves : Vec<Move> = neighbourhood.customer_granular_moves(
+                &state.routes, &state.positions, u, &neighbours[u]);
+            if *neighbourhood == Neighbourhood::Relocate
+            {
+                moves.push(Move::Relocate { from_route : route, from : position, to_route : state.empty_route, to : 0 });
+            }
+
+            for m in moves
+            {
+                let delta : f64 = move_delta(distance, &m, &state.routes);
+                if delta.abs() > EPSILON &&
+                    result.is_none_or(|(_, best)| delta < best) &&
+                    m.is_feasible(&state.routes, &state.loads, nodes_demand, capacity)
+                {
+                    result = Some((m, delta));
+                }
+            }
+
+        }
+
+        return result;
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for RecordToRecordSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let instance     : &dyn RecordToRecordInstanceTrait           = self.instance;
+        let distance     : Box<dyn Fn(usize, usize) -> f64 + Sync + '_> = instance.get_distance_function();
+        let nodes_demand : Vec<usize>                                 = instance.get_nodes_demand();
+        let capacity     : usize                                      = instance.get_capacity();
+        let node_number  : usize                                      = nodes_demand.len();
+        let neighbours   : Vec< Vec<usize>>                           = nearest_neighbours(&*distance, node_number, self.granularity);
+        let start        : Instant                                    = Instant::now();
+
+        let mut state       : SearchState      = SearchState::new(
+            self.initial_solver.solve_with_control(control), &nodes_demand, &*distance);
+        let mut best_routes : Vec< Vec<usize>> = state.routes.clone();
+        let mut record      : f64              = state.cost;
+        control.report(&best_routes);
+
+        let mut threshold     : f64   = match self.threshold
+        {
+            AcceptanceThreshold::RecordToRecord { deviation }         => deviation,
+            AcceptanceThreshold::ThresholdAccepting { threshold, .. } => threshold,
+        };
+        let mut iteration     : usize = 0;
+        let mut non_improving : usize = 0;
+        while non_improving < self.patience && !self.stopping_criterion.is_met(iteration, start) && !control.should_stop()
+        {
+
+            iteration += 1;
+
+            /* Uphill phase. */
+            for _ in 0..self.uphill_passes
+            {
+                for u in 1..node_number
+                {
+                    let limit : f64 = match self.threshold
+                    {
+                        AcceptanceThreshold::RecordToRecord { .. }     => record * (1.0 + threshold),
+                        AcceptanceThreshold::ThresholdAccepting { .. } => state.cost * (1.0 + threshold),
+                    };
+
+                    if let Some((m, delta)) = self.best_move(
+                        &*distance, &nodes_demand, capacity, &neighbours, &state, u)
+                    {
+                        if state.cost + delta < limit
+                        {
+                            state.apply(m, delta, &nodes_demand);
+                        }
+                    }
+                }
+            }
+
+            /* Downhill phase. */
+            let mut improved : bool = true;
+            while improved
+            {
+                improved = false;
+                for u in 1..node_number
+                {
+                    if let Some((m, delta)) = self.best_move(
+                        &*distance, &nodes_demand, capacity, &neighbours, &state, u)
+                    {
+                        if delta < -EPSILON
+                        {
+                            state.apply(m, delta, &nodes_demand);
+                            improved = true;
+                        }
+                    }
+                }
+            }
+
+            if state.cost < record - EPSILON
+            {
+                record        = state.cost;
+                best_routes   = state.routes.clone();
+                non_improving = 0;
+                control.report(&best_routes.iter().filter(|r| !r.is_empty()).cloned().collect());
+            }
+            else
+            {
+                non_improving += 1;
+            }
+
+            if let AcceptanceThreshold::ThresholdAccepting { decay, .. } = self.threshold
+            {
+                threshold *= decay;
+            }
+
+        }
+
+        best_routes.retain(|r| !r.is_empty());
+        for route in best_routes.iter_mut()
+        {
+            optimize_route(&*distance, route, control);
+        }
+        control.report(&best_routes);
+
+        return best_routes;
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::model::{GraphInstance, read_test_instance};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+    use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+    use crate::model::assert_feasible;
+    use std::time::Duration;
+
+    #[test]
+    fn improves_the_initial_routes()
+    {
+
+        for name in ["F-n45-k4", "gr-n24-k4"]
+        {
+
+            let graph    : GraphInstance        = read_test_instance(name);
+            let savings  : SavingsSolver        = SavingsSolver::new(&graph);
+            let solver   : RecordToRecordSolver = RecordToRecordSolver::new(&graph, &savings);
+            let distance : Box<dyn Fn(usize, usize) -> f64 + Sync + '_> =
+                RecordToRecordInstanceTrait::get_distance_function(&graph);
+
+            let routes       : Vec< Vec<usize>> = solver.solve();
+            let nodes_demand : Vec<usize>       = RecordToRecordInstanceTrait::get_nodes_demand(&graph);
+            let capacity     : usize            = RecordToRecordInstanceTrait::get_capacity(&graph);
+
+            assert!(routes_cost(&*distance, &routes) <= routes_cost(&*distance, &savings.solve()));
+
+            /* Every customer is served exactly once,
+             * within the capacity, and no route is
+             * left empty. */
+            let mut served : Vec<usize> = routes.concat();
+            served.sort();
+            assert_eq!(served, (1..nodes_demand.len()).collect::<Vec<usize>>());
+            assert!(routes.iter().all(|r| !r.is_empty()));
+            assert!(routes.iter().all(|r| r.iter().map(|&i| nodes_demand[i]).sum::<usize>() <= capacity));
+
+        }
+
+    }
+
+    #[test]
+    fn nearest_neighbours_match_the_sorted_distances()
+    {
+
+        /* Points of a grid, with many ties among
+         * the rounded distances. */
+        let points   : Vec<(f64, f64)> = (0..30).map(|i| ((i % 6) as f64 * 3.0, (i / 6) as f64 * 4.0)).collect();
+        let distance = |i : usize, j : usize|
+            ((points[i].0 - points[j].0).powi(2) + (points[i].1 - points[j].1).powi(2)).sqrt().round();
+
+        for k in [0, 1, 5, 28, 40]
+        {
+
+            let nearest : Vec< Vec<usize>> = nearest_neighbours(&distance, points.len(), k);
+            assert!(nearest[0].is_empty());
+
+            for i in 1..points.len()
+            {
+                let mut sorted : Vec<usize> = (1..points.len()).filter(|&j| j != i).collect();
+                sorted.sort_by(|&a, &b| distance(i, a).partial_cmp(&distance(i, b)).unwrap());
+                sorted.truncate(k);
+                assert_eq!(nearest[i], sorted);
+            }
+
+        }
+
+    }
+
+    #[test]
+    fn move_delta_matches_the_local_search()
+    {
+
+        let graph           : GraphInstance    = read_test_instance("F-n45-k4");
+        let distance        : Box<dyn Fn(usize, usize) -> f64 + Sync + '_> =
+            RecordToRecordInstanceTrait::get_distance_function(&graph);
+        let distance_matrix : Vec< Vec<f64>>   = LocalSearchInstanceTrait::get_distance_matrix(&graph);
+        let routes          : Vec< Vec<usize>> = SavingsSolver::new(&graph).solve();
+
+        for neighbourhood in [
+            Neighbourhood::TwoOpt,
+            Neighbourhood::OrOpt,
+            Neighbourhood::Relocate,
+            Neighbourhood::Swap,
+            Neighbourhood::TwoOptStar,
+            Neighbourhood::CrossExchange,
+        ]
+        {
+            for m in neighbourhood.moves(&routes)
+            {
+                assert!((move_delta(&*distance, &m, &routes) - m.delta(&distance_matrix, &routes)).abs() < 1e-6);
+            }
+        }
+
+    }
+
+    #[test]
+    fn deadline_stops_the_search()
+    {
+
+        let graph   : GraphInstance        = read_test_instance("F-n72-k4");
+        let savings : SavingsSolver        = SavingsSolver::new(&graph);
+        let solver  : RecordToRecordSolver = RecordToRecordSolver {
+            patience           : usize::MAX,
+            stopping_criterion : StoppingCriterion::Iterations(usize::MAX),
+            ..RecordToRecordSolver::threshold_accepting(&graph, &savings, 0.1)
+        };
+
+        let start   : Instant          = Instant::now();
+        let control : SolveControl     = SolveControl::new(None, Some(start + Duration::from_millis(100)), None, None);
+        let routes  : Vec< Vec<usize>> = solver.solve_with_control(&control);
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_feasible(&graph, &routes);
+
+    }
+
+}
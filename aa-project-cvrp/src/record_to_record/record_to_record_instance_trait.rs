@@ -0,0 +1,17 @@
+/* This trait define the methods required
+ * by the record-to-record travel, available
+ * only for GraphInstance when explicitly
+ * implemented. The full distance matrix is
+ * not required. */
+pub trait RecordToRecordInstanceTrait : Sync
+{
+
+    /* Distance between two nodes, computed on
+     * demand where the instance allows it. */
+    fn get_distance_function(&self) -> Box<dyn Fn(usize, usize) -> f64 + Sync + '_>;
+
+    fn get_capacity(&self) -> usize;
+
+    fn get_nodes_demand(&self) -> Vec<usize>;
+
+}
@@ -0,0 +1,3 @@
+pub mod route_first_instance_trait;
+pub mod route_first_algorithm_imp;
+pub mod split;
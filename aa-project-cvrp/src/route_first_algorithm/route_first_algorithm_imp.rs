@@ -0,0 +1,147 @@
+use crate::route_first_algorithm::route_first_instance_trait::RouteFirstInstanceTrait;
+use crate::route_first_algorithm::split::split;
+use crate::local_search::intra_route_imp::optimize_route_with;
+use crate::local_search::moves::Neighbourhood;
+use crate::algorithm_interface::{CVRPSolver, SolveControl};
+
+/* Here the route-first cluster-second algorithm
+ * is implemented. A giant tour over all the
+ * customers is built with the nearest neighbour
+ * heuristic and improved by 2-opt and Or-opt,
+ * then it is cut into routes by Split. */
+pub struct RouteFirstSolver<'a>
+{
+
+    pub instance : &'a dyn RouteFirstInstanceTrait,
+
+}
+
+/* Methods of RouteFirstSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> RouteFirstSolver<'a>
+{
+
+    pub fn add_instance(&'a mut self, i : &'a dyn RouteFirstInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+}
+
+/* Build a tour over all the customers, starting
+ * from the depot and moving each time to the
+ * nearest customer not yet visited. */
+pub fn nearest_neighbour_tour(distance_matrix : &[Vec<f64>]) -> Vec<usize>
+{
+
+    let node_number : usize      = distance_matrix.len();
+    let mut visited : Vec<bool>  = vec![false; node_number];
+    let mut tour    : Vec<usize> = Vec::with_capacity(node_number - 1);
+    let mut current : usize      = 0;
+
+    visited[0] = true;
+
+    for _ in 1..node_number
+    {
+
+        let next : usize = (1..node_number)
+            .filter(|&n| !visited[n])
+            .min_by(|&a, &b| distance_matrix[current][a].partial_cmp(&distance_matrix[current][b]).unwrap())
+            .unwrap();
+
+        tour.push(next);
+        visited[next] = true;
+        current       = next;
+
+    }
+
+    return tour;
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for RouteFirstSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    /* The improvement of the giant tour ends when
+     * the search is stopped, then the tour is split. */
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        if control.should_stop()
+        {
+            return Vec::new();
+        }
+
+        let instance        : &dyn RouteFirstInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>               = instance.get_distance_matrix();
+
+        /* Route first: a TSP tour through the depot. */
+        let mut giant_tour : Vec<usize> = nearest_neighbour_tour(&distance_matrix);
+        optimize_route_with(
+            &distance_matrix, &mut giant_tour, &[Neighbourhood::TwoOpt, Neighbourhood::OrOpt], control);
+
+        /* Cluster second. No routes are given when
+         * a customer's demand exceeds the capacity. */
+        let routes : Vec< Vec<usize>> = split(
+            &distance_matrix,
+            &instance.get_nodes_demand(),
+            instance.get_capacity(),
+            &giant_tour)
+            .unwrap_or_default();
+        control.report(&routes);
+
+        return routes;
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::route_first_algorithm::route_first_instance_trait::RouteFirstInstanceTrait;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+
+    #[test]
+    fn serves_every_customer()
+    {
+
+        for name in ["F-n45-k4", "gr-n24-k4", "bays-n29-k5"]
+        {
+
+            let graph  : GraphInstance    = read_test_instance(name);
+            let solver : RouteFirstSolver = RouteFirstSolver { instance : &graph };
+
+            assert_feasible(&graph, &solver.solve());
+
+        }
+
+    }
+
+    #[test]
+    fn demand_above_the_capacity_gives_no_routes()
+    {
+
+        let mut graph : GraphInstance = read_test_instance("gr-n17-k3");
+        graph.instance.specification.capacity = graph.get_nodes_demand().into_iter().max().unwrap() - 1;
+
+        let solver : RouteFirstSolver = RouteFirstSolver { instance : &graph };
+
+        assert!(solver.solve().is_empty());
+
+    }
+
+}
@@ -0,0 +1,15 @@
+/* This trait define the methods required
+ * by the route-first cluster-second
+ * algorithm, available only for
+ * GraphInstance when explicitly
+ * implemented. */
+pub trait RouteFirstInstanceTrait : Sync
+{
+
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>>;
+
+    fn get_capacity(&self) -> usize;
+
+    fn get_nodes_demand(&self) -> Vec<usize>;
+
+}
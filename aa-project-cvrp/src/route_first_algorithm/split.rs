@@ -0,0 +1,261 @@
+/* Prins' Split procedure, which cuts a giant tour
+ * over all the customers into capacity-feasible
+ * routes, optimally with respect to the order of
+ * the tour. It is computed as a shortest path over
+ * the auxiliary graph where an arc (i, j) is the
+ * route serving the customers from i + 1 to j.
+ * None if a customer's demand exceeds the
+ * capacity, since no route can serve it. */
+pub fn split(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    giant_tour      : &[usize])
+    -> Option< Vec< Vec<usize>>>
+{
+
+    shortest_path_split(distance_matrix, nodes_demand, capacity, capacity, 0.0, giant_tour)
+
+}
+
+/* Split allowing routes overloaded up to half the
+ * capacity, whose cost is increased by the penalty
+ * times the excess of load. */
+pub fn split_penalized(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    penalty         : f64,
+    giant_tour      : &[usize])
+    -> Option< Vec< Vec<usize>>>
+{
+
+    shortest_path_split(
+        distance_matrix, nodes_demand, capacity, capacity + capacity / 2, penalty, giant_tour)
+
+}
+
+/* Shortest path over the auxiliary graph, with
+ * routes up to the maximum load. The end of the
+ * tour is unreachable when a demand exceeds the
+ * maximum load. */
+fn shortest_path_split(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    max_load        : usize,
+    penalty         : f64,
+    giant_tour      : &[usize])
+    -> Option< Vec< Vec<usize>>>
+{
+
+    let tour_len        : usize      = giant_tour.len();
+    let mut potential   : Vec<f64>   = vec![f64::INFINITY; tour_len + 1];
+    let mut predecessor : Vec<usize> = vec![0; tour_len + 1];
+
+    potential[0] = 0.0;
+
+    for i in 0..tour_len
+    {
+
+        if potential[i] == f64::INFINITY
+        {
+            continue;
+        }
+
+        let mut load : usize = 0;
+        let mut cost : f64   = 0.0;
+
+        for j in i..tour_len
+        {
+
+            load += nodes_demand[giant_tour[j]];
+            if load > max_load
+            {
+                break;
+            }
+
+            /* Cost of the route from the depot to the
+             * j-th customer, without the return. */
+            if j == i
+            {
+                cost = distance_matrix[0][giant_tour[j]];
+            }
+            else
+            {
+                cost += distance_matrix[giant_tour[j - 1]][giant_tour[j]];
+            }
+
+            let total : f64 = potential[i] + cost + distance_matrix[giant_tour[j]][0]
+                + penalty * load.saturating_sub(capacity) as f64;
+            if total < potential[j + 1]
+            {
+                potential[j + 1]   = total;
+                predecessor[j + 1] = i;
+            }
+
+        }
+
+    }
+
+    if potential[tour_len] == f64::INFINITY
+    {
+        return None;
+    }
+
+    /* Extract the routes following
+     * the shortest path backward. */
+    let mut routes : Vec< Vec<usize>> = Vec::new();
+    let mut j      : usize            = tour_len;
+
+    while j > 0
+    {
+        let i : usize = predecessor[j];
+        routes.push(giant_tour[i..j].to_vec());
+        j = i;
+    }
+
+    routes.reverse();
+
+    return Some(routes);
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use super::*;
+    use crate::algorithm_interface::compute_routes_cost;
+
+    /* Cost of the routes, with the penalty on
+     * the excess of load, or None if a route
+     * exceeds the maximum load. */
+    fn penalized_cost(
+        distance_matrix : &[Vec<f64>],
+        nodes_demand    : &[usize],
+        capacity        : usize,
+        max_load        : usize,
+        penalty         : f64,
+        routes          : &Vec< Vec<usize>>)
+        -> Option<f64>
+    {
+
+        let mut result : f64 = compute_routes_cost(distance_matrix, routes);
+        for route in routes
+        {
+            let load : usize = route.iter().map(|&i| nodes_demand[i]).sum();
+            if load > max_load
+            {
+                return None;
+            }
+            result += penalty * load.saturating_sub(capacity) as f64;
+        }
+
+        return Some(result);
+
+    }
+
+    /* Cheapest split among all the ways of
+     * cutting the tour into routes. */
+    fn brute_force_split(
+        distance_matrix : &[Vec<f64>],
+        nodes_demand    : &[usize],
+        capacity        : usize,
+        max_load        : usize,
+        penalty         : f64,
+        giant_tour      : &[usize])
+        -> f64
+    {
+
+        let mut best : f64 = f64::INFINITY;
+
+        for cuts in 0..(1usize << (giant_tour.len() - 1))
+        {
+            let mut routes : Vec< Vec<usize>> = vec![vec![giant_tour[0]]];
+            for k in 1..giant_tour.len()
+            {
+                if cuts & 1 << (k - 1) != 0
+                {
+                    routes.push(Vec::new());
+                }
+                routes.last_mut().unwrap().push(giant_tour[k]);
+            }
+            if let Some(cost) = penalized_cost(distance_matrix, nodes_demand, capacity, max_load, penalty, &routes)
+            {
+                best = best.min(cost);
+            }
+        }
+
+        return best;
+
+    }
+
+    #[test]
+    fn split_matches_brute_force()
+    {
+
+        let mut rng  : StdRng = StdRng::seed_from_u64(3);
+        let capacity : usize  = 10;
+
+        for _ in 0..20
+        {
+
+            let node_number     : usize             = 10;
+            let points          : Vec<(f64, f64)>   = (0..node_number)
+                .map(|_| (rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0)))
+                .collect();
+            let distance_matrix : Vec< Vec<f64>>    = points.iter()
+                .map(|a| points.iter().map(|b| ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()).collect())
+                .collect();
+            let mut nodes_demand : Vec<usize>       = (0..node_number).map(|_| rng.gen_range(1..=6)).collect();
+            nodes_demand[0] = 0;
+            let mut giant_tour  : Vec<usize>        = (1..node_number).collect();
+            giant_tour.shuffle(&mut rng);
+
+            let routes   : Vec< Vec<usize>> = split(&distance_matrix, &nodes_demand, capacity, &giant_tour).unwrap();
+            let expected : f64              =
+                brute_force_split(&distance_matrix, &nodes_demand, capacity, capacity, 0.0, &giant_tour);
+            assert_eq!(routes.concat(), giant_tour);
+            assert!((compute_routes_cost(&distance_matrix, &routes) - expected).abs() < 1e-6);
+            assert!(routes.iter().all(|r| r.iter().map(|&i| nodes_demand[i]).sum::<usize>() <= capacity));
+
+            let penalty  : f64              = 5.0;
+            let routes   : Vec< Vec<usize>> =
+                split_penalized(&distance_matrix, &nodes_demand, capacity, penalty, &giant_tour).unwrap();
+            let expected : f64              = brute_force_split(
+                &distance_matrix, &nodes_demand, capacity, capacity + capacity / 2, penalty, &giant_tour);
+            let cost     : Option<f64>      = penalized_cost(
+                &distance_matrix, &nodes_demand, capacity, capacity + capacity / 2, penalty, &routes);
+            assert_eq!(routes.concat(), giant_tour);
+            assert!((cost.unwrap() - expected).abs() < 1e-6);
+
+        }
+
+    }
+
+    #[test]
+    fn demand_above_the_maximum_load_gives_no_routes()
+    {
+
+        let distance_matrix : Vec< Vec<f64>> = vec![
+            vec![0.0, 1.0, 2.0, 2.0],
+            vec![1.0, 0.0, 1.0, 2.0],
+            vec![2.0, 1.0, 0.0, 1.0],
+            vec![2.0, 2.0, 1.0, 0.0],
+        ];
+        let nodes_demand    : Vec<usize>     = vec![0, 2, 12, 2];
+        let giant_tour      : Vec<usize>     = vec![1, 2, 3];
+
+        assert_eq!(split(&distance_matrix, &nodes_demand, 10, &giant_tour), None);
+        assert_eq!(split_penalized(&distance_matrix, &nodes_demand, 7, 1.0, &giant_tour), None);
+        assert_eq!(
+            split_penalized(&distance_matrix, &nodes_demand, 10, 1.0, &giant_tour),
+            Some(vec![vec![1], vec![2, 3]]));
+
+    }
+
+}
@@ -0,0 +1,4 @@
+pub mod route_pool_instance_trait;
+pub mod pool;
+pub mod set_partitioning;
+pub mod route_pool_imp;
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use crate::algorithm_interface::compute_routes_cost;
+
+/* A pool of distinct routes within the capacity,
+ * collected from the solutions of many solvers.
+ * Routes serving the same customers are kept
+ * once, in their cheapest known order. */
+pub struct RoutePool
+{
+
+    distance_matrix : Vec< Vec<f64>>,
+
+    nodes_demand    : Vec<usize>,
+
+    capacity        : usize,
+
+    /* Cost and order of the cheapest route
+     * serving each set of customers, given
+     * as the sorted customers. */
+    routes          : HashMap<Vec<usize>, (f64, Vec<usize>)>,
+
+}
+
+#[allow(dead_code)]
+impl RoutePool
+{
+
+    /* An empty pool for the instance. */
+    pub fn new(distance_matrix : Vec< Vec<f64>>, nodes_demand : Vec<usize>, capacity : usize) -> RoutePool
+    {
+
+        RoutePool {
+            distance_matrix,
+            nodes_demand,
+            capacity,
+            routes : HashMap::new(),
+        }
+
+    }
+
+    /* Add the route, if it is within the capacity and
+     * it is the cheapest for its customers. Return
+     * whether the pool has changed. */
+    pub fn add(&mut self, route : &[usize]) -> bool
+    {
+
+        if route.is_empty() ||
+            route.iter().map(|&n| self.nodes_demand[n]).sum::<usize>() > self.capacity
+        {
+            return false;
+        }
+
+        let cost    : f64        = compute_routes_cost(&self.distance_matrix, &vec![route.to_vec()]);
+        let mut key : Vec<usize> = route.to_vec();
+        key.sort();
+
+        match self.routes.get(&key)
+        {
+            Some(&(known_cost, _)) if known_cost <= cost => false,
+            _ =>
+            {
+                self.routes.insert(key, (cost, route.to_vec()));
+                true
+            },
+        }
+
+    }
+
+    /* Add every route of a solution. */
+    pub fn add_routes(&mut self, routes : &Vec< Vec<usize>>)
+    {
+
+        for route in routes
+        {
+            self.add(route);
+        }
+
+    }
+
+    pub fn len(&self) -> usize
+    {
+
+        self.routes.len()
+
+    }
+
+    /* The routes of the pool with their
+     * costs, in no particular order. */
+    pub fn routes(&self) -> Vec<(f64, Vec<usize>)>
+    {
+
+        self.routes.values().cloned().collect()
+
+    }
+
+}
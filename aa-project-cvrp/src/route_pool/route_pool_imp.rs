@@ -0,0 +1,212 @@
+use crate::route_pool::route_pool_instance_trait::RoutePoolInstanceTrait;
+use crate::route_pool::pool::RoutePool;
+use crate::route_pool::set_partitioning::{solve_set_partitioning, SetPartitioningResult};
+use crate::algorithm_interface::{CVRPSolver, CVRPImprover, SolveControl, compute_routes_cost};
+
+/* Here the set partitioning matheuristic is
+ * implemented. The routes computed by the given
+ * solvers, and by the improvers applied to each
+ * of them, are collected in a route pool, then the
+ * cheapest routes serving each customer exactly
+ * once are selected among them. */
+pub struct RoutePoolSolver<'a>
+{
+
+    pub instance   : &'a dyn RoutePoolInstanceTrait,
+
+    /* Solvers whose routes fill the pool. */
+    pub solvers    : Vec<&'a dyn CVRPSolver>,
+
+    /* Improvement procedures applied, in the given
+     * order, to the routes of each solver, adding
+     * the routes after each of them to the pool. */
+    pub improvers  : Vec<&'a dyn CVRPImprover>,
+
+    /* Bound on the nodes of the branch and bound. */
+    pub node_limit : usize,
+
+}
+
+/* Methods of RoutePoolSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> RoutePoolSolver<'a>
+{
+
+    pub fn new(
+        instance  : &'a dyn RoutePoolInstanceTrait,
+        solvers   : Vec<&'a dyn CVRPSolver>,
+        improvers : Vec<&'a dyn CVRPImprover>)
+        -> RoutePoolSolver<'a>
+    {
+
+        RoutePoolSolver {
+            instance,
+            solvers,
+            improvers,
+            node_limit : 1_000_000,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn RoutePoolInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    /* Run every solver and improver, collecting
+     * their routes, together with the route of
+     * each customer alone, so that a partition
+     * always exists, until the search is stopped.
+     * Return the pool and the best of the
+     * solutions computed. */
+    pub fn collect_routes(&self, control : &SolveControl) -> (RoutePool, Vec< Vec<usize>>)
+    {
+
+        let distance_matrix : Vec< Vec<f64>> = self.instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>     = self.instance.get_nodes_demand();
+        let capacity        : usize          = self.instance.get_capacity();
+
+        let mut pool      : RoutePool        = RoutePool::new(distance_matrix.clone(), nodes_demand.clone(), capacity);
+        let mut best      : Vec< Vec<usize>> = (1..nodes_demand.len()).map(|i| vec![i]).collect();
+        let mut best_cost : f64              = compute_routes_cost(&distance_matrix, &best);
+        pool.add_routes(&best);
+
+        for solver in &self.solvers
+        {
+
+            if control.should_stop()
+            {
+                break;
+            }
+
+            /* A solver stopped before starting
+             * returns no routes. */
+            let mut routes : Vec< Vec<usize>> = solver.solve_with_control(control);
+            if routes.is_empty()
+            {
+                break;
+            }
+            pool.add_routes(&routes);
+
+            for improver in &self.improvers
+            {
+                routes = improver.improve(routes, control);
+                pool.add_routes(&routes);
+                control.report(&routes);
+            }
+
+            let cost : f64 = compute_routes_cost(&distance_matrix, &routes);
+            if cost < best_cost
+            {
+                best      = routes;
+                best_cost = cost;
+            }
+
+        }
+
+        return (pool, best);
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for RoutePoolSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    /* The set partitioning is skipped once the
+     * search is stopped, and otherwise ends with
+     * the best partition found when it is. */
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let distance_matrix : Vec< Vec<f64>> = self.instance.get_distance_matrix();
+
+        let (pool, best) : (RoutePool, Vec< Vec<usize>>) = self.collect_routes(control);
+        if control.should_stop()
+        {
+            return best;
+        }
+        let routes       : Vec<(f64, Vec<usize>)>         = pool.routes();
+
+        /* Only partitions cheaper than the best
+         * solution computed are searched. */
+        let upper_bound : f64 = compute_routes_cost(&distance_matrix, &best);
+
+        let partition : SetPartitioningResult = solve_set_partitioning(
+            distance_matrix.len(),
+            &routes,
+            upper_bound,
+            self.node_limit,
+            control);
+
+        let result : Vec< Vec<usize>> = match partition.best
+        {
+            Some((_, selected)) => selected.iter().map(|&r| routes[r].1.clone()).collect(),
+            None                => best,
+        };
+        control.report(&result);
+
+        return result;
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::model::{GraphInstance, read_test_instance};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+    use crate::sweep_algorithm::sweep_algorithm_imp::SweepSolver;
+    use crate::route_first_algorithm::route_first_algorithm_imp::RouteFirstSolver;
+    use crate::local_search::inter_route_imp::InterRouteLocalSearch;
+
+    #[test]
+    fn partition_is_no_worse_than_the_solutions_pooled()
+    {
+
+        for name in ["gr-n21-k3", "F-n45-k4"]
+        {
+
+            let graph       : GraphInstance         = read_test_instance(name);
+            let savings     : SavingsSolver         = SavingsSolver::new(&graph);
+            let sweep       : SweepSolver           = SweepSolver { instance : &graph };
+            let route_first : RouteFirstSolver      = RouteFirstSolver { instance : &graph };
+            let local       : InterRouteLocalSearch = InterRouteLocalSearch::new(&graph);
+            let solver      : RoutePoolSolver       =
+                RoutePoolSolver::new(&graph, vec![&savings, &sweep, &route_first], vec![&local]);
+
+            let distance_matrix : Vec< Vec<f64>> = RoutePoolInstanceTrait::get_distance_matrix(&graph);
+            let nodes_demand    : Vec<usize>     = RoutePoolInstanceTrait::get_nodes_demand(&graph);
+            let capacity        : usize          = RoutePoolInstanceTrait::get_capacity(&graph);
+
+            let (_, best) : (RoutePool, Vec< Vec<usize>>) = solver.collect_routes(&SolveControl::unlimited());
+            let routes    : Vec< Vec<usize>>             = solver.solve();
+
+            assert!(compute_routes_cost(&distance_matrix, &routes) <= compute_routes_cost(&distance_matrix, &best));
+
+            /* Every customer is served exactly once,
+             * within the capacity. */
+            let mut served : Vec<usize> = routes.concat();
+            served.sort();
+            assert_eq!(served, (1..nodes_demand.len()).collect::<Vec<usize>>());
+            assert!(routes.iter().all(|r| r.iter().map(|&i| nodes_demand[i]).sum::<usize>() <= capacity));
+
+        }
+
+    }
+
+}
@@ -0,0 +1,453 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use crate::algorithm_interface::SolveControl;
+
+/* A route of the set partitioning problem, with
+ * its reduced cost: the cost minus the shares of
+ * its customers. The signature folds the bit set
+ * of its customers into a single word. */
+struct Column
+{
+
+    index        : usize,
+
+    cost         : f64,
+
+    reduced_cost : f64,
+
+    signature    : u64,
+
+}
+
+/* Result of the set partitioning: the cheapest
+ * partition found, with its cost and the indices of
+ * the routes selected, and whether the search was
+ * completed. A completed search proves the partition
+ * optimal, or that none is cheaper than the upper
+ * bound when no partition is found. */
+pub struct SetPartitioningResult
+{
+
+    pub best      : Option<(f64, Vec<usize>)>,
+
+    pub completed : bool,
+
+}
+
+const EPSILON : f64 = 1e-9;
+
+/* Hasher of the sets of customers left, looked up
+ * at every node of the branch and bound: a
+ * multiplicative hash of their words, much cheaper
+ * than the default one. */
+#[derive(Default)]
+struct BitSetHasher
+{
+
+    hash : u64,
+
+}
+
+impl Hasher for BitSetHasher
+{
+
+    fn finish(&self) -> u64
+    {
+        return self.hash;
+    }
+
+    fn write(&mut self, bytes : &[u8])
+    {
+        for chunk in bytes.chunks(8)
+        {
+            let mut word : [u8; 8] = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u64(&mut self, word : u64)
+    {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+
+    fn write_u128(&mut self, word : u128)
+    {
+        self.write_u64(word as u64);
+        self.write_u64((word >> 64) as u64);
+    }
+
+    fn write_usize(&mut self, word : usize)
+    {
+        self.write_u64(word as u64);
+    }
+
+}
+
+/* Key of a set of customers left in the table of
+ * the branch and bound, built from its bit set:
+ * held inline up to 128 nodes, so that the lookups
+ * of the small instances touch no further memory. */
+trait ReachedKey : Hash + Eq
+{
+
+    fn from_words(words : &[u64]) -> Self;
+
+}
+
+impl ReachedKey for u64
+{
+
+    fn from_words(words : &[u64]) -> Self
+    {
+        return words[0];
+    }
+
+}
+
+impl ReachedKey for u128
+{
+
+    fn from_words(words : &[u64]) -> Self
+    {
+        return words[0] as u128 | (words[1] as u128) << 64;
+    }
+
+}
+
+impl ReachedKey for Vec<u64>
+{
+
+    fn from_words(words : &[u64]) -> Self
+    {
+        return words.to_vec();
+    }
+
+}
+
+/* Number of nodes of the branch and bound
+ * between two checks of the control. */
+const CONTROL_INTERVAL : usize = 1024;
+
+/* Select among the routes, given with their costs,
+ * the cheapest ones serving each customer exactly
+ * once, by branch and bound. Only the partitions
+ * cheaper than the upper bound are searched, and
+ * the search ends after the given number of nodes,
+ * or when the control is stopped. */
+pub fn solve_set_partitioning(
+    node_number : usize,
+    routes      : &[(f64, Vec<usize>)],
+    upper_bound : f64,
+    node_limit  : usize,
+    control     : &SolveControl)
+    -> SetPartitioningResult
+{
+
+    let customers : usize = node_number - 1;
+    if customers == 0
+    {
+        return SetPartitioningResult { best : Some((0.0, Vec::new())), completed : true };
+    }
+
+    let shares : Vec<f64> = dual_ascent(node_number, routes);
+    if shares.iter().any(|s| s.is_infinite())
+    {
+        return SetPartitioningResult { best : None, completed : true };
+    }
+
+    /* Columns grouped by their first customer,
+     * sorted by reduced cost, each group with the
+     * bit sets of its columns laid out in a row. */
+    let words       : usize                         = node_number.div_ceil(64);
+    let mut columns : Vec< Vec<(Column, Vec<u64>)>> = (0..node_number).map(|_| Vec::new()).collect();
+    for (index, (cost, route)) in routes.iter().enumerate()
+    {
+        let mut bits : Vec<u64> = vec![0; words];
+        for &i in route
+        {
+            bits[i / 64] |= 1u64 << (i % 64);
+        }
+        let reduced_cost : f64   = (cost - route.iter().map(|&i| shares[i]).sum::<f64>()).max(0.0);
+        let first        : usize = *route.iter().min().unwrap();
+        columns[first].push((Column {
+            index,
+            cost      : *cost,
+            reduced_cost,
+            signature : bits.iter().fold(0, |signature, word| signature | word),
+        }, bits));
+    }
+    let columns : Vec<(Vec<Column>, Vec<u64>)> = columns.into_iter().map(|mut group| {
+        group.sort_by(|a, b| a.0.reduced_cost.partial_cmp(&b.0.reduced_cost).unwrap());
+        let bits : Vec<u64> = group.iter().flat_map(|(_, bits)| bits.iter().cloned()).collect();
+        (group.into_iter().map(|(column, _)| column).collect(), bits)
+    }).collect();
+
+    /* Bit set of the customers left to serve. */
+    let mut uncovered : Vec<u64> = vec![0; words];
+    for i in 1..node_number
+    {
+        uncovered[i / 64] |= 1u64 << (i % 64);
+    }
+
+    let bound : f64 = shares.iter().sum();
+    return match words
+    {
+        1 => search::<u64>(&columns, &mut uncovered, bound, upper_bound, node_limit, control),
+        2 => search::<u128>(&columns, &mut uncovered, bound, upper_bound, node_limit, control),
+        _ => search::<Vec<u64>>(&columns, &mut uncovered, bound, upper_bound, node_limit, control),
+    };
+
+}
+
+/* Run the branch and bound from all the customers
+ * left to serve, with the given key of their sets. */
+fn search<K : ReachedKey>(
+    columns     : &Vec<(Vec<Column>, Vec<u64>)>,
+    uncovered   : &mut Vec<u64>,
+    bound       : f64,
+    upper_bound : f64,
+    node_limit  : usize,
+    control     : &SolveControl)
+    -> SetPartitioningResult
+{
+
+    let mut search : Search<K> = Search {
+        columns,
+        control,
+        best_cost  : upper_bound,
+        best       : None,
+        current    : Vec::new(),
+        reached    : HashMap::default(),
+        nodes      : 0,
+        node_limit,
+        stopped    : false,
+    };
+
+    search.branch(uncovered, 0.0, bound);
+
+    let best_cost : f64 = search.best_cost;
+
+    return SetPartitioningResult {
+        best      : search.best.map(|selected| (best_cost, selected)),
+        completed : !search.stopped,
+    };
+
+}
+
+/* Shares of the customers, feasible for the dual of
+ * the linear relaxation: no route costs less than
+ * the shares of its customers, so the sum of the
+ * shares of the customers left bounds the cost of
+ * serving them. Starting from the shares in the
+ * cheapest route per customer, each share is raised
+ * in turn by the least reduced cost of the routes
+ * serving the customer. */
+fn dual_ascent(node_number : usize, routes : &[(f64, Vec<usize>)]) -> Vec<f64>
+{
+
+    let mut shares  : Vec<f64>         = vec![f64::INFINITY; node_number];
+    let mut serving : Vec< Vec<usize>> = vec![Vec::new(); node_number];
+    shares[0] = 0.0;
+
+    for (r, (cost, route)) in routes.iter().enumerate()
+    {
+        for &i in route
+        {
+            shares[i] = shares[i].min(cost / route.len() as f64);
+            serving[i].push(r);
+        }
+    }
+
+    let mut reduced_costs : Vec<f64> = routes.iter()
+        .map(|(cost, route)| cost - route.iter().map(|&i| shares[i]).sum::<f64>())
+        .collect();
+
+    for i in 1..node_number
+    {
+        let increase : f64 = serving[i].iter()
+            .map(|&r| reduced_costs[r])
+            .fold(f64::INFINITY, f64::min);
+        if increase.is_finite()
+        {
+            shares[i] += increase;
+            for &r in &serving[i]
+            {
+                reduced_costs[r] -= increase;
+            }
+        }
+    }
+
+    return shares;
+
+}
+
+/* State of the branch and bound. */
+struct Search<'b, K : ReachedKey>
+{
+
+    columns    : &'b Vec<(Vec<Column>, Vec<u64>)>,
+
+    control    : &'b SolveControl<'b>,
+
+    best_cost  : f64,
+
+    best       : Option<Vec<usize>>,
+
+    current    : Vec<usize>,
+
+    /* Lowest cost with which each set of
+     * customers has been left to serve. */
+    reached    : HashMap<K, f64, BuildHasherDefault<BitSetHasher>>,
+
+    nodes      : usize,
+
+    node_limit : usize,
+
+    /* Whether the search ended before completing,
+     * for the node limit or the control. */
+    stopped    : bool,
+
+}
+
+impl<'b, K : ReachedKey> Search<'b, K>
+{
+
+    /* Cover the customers left, choosing the route
+     * of the first of them. The bound is the sum
+     * of the shares of the customers left. */
+    fn branch(&mut self, uncovered : &mut Vec<u64>, cost : f64, bound : f64)
+    {
+
+        let first : Option<usize> = uncovered.iter()
+            .position(|&w| w != 0)
+            .map(|w| w * 64 + uncovered[w].trailing_zeros() as usize);
+
+        let first : usize = match first
+        {
+            Some(i) => i,
+            None =>
+            {
+                if cost < self.best_cost - EPSILON
+                {
+                    self.best_cost = cost;
+                    self.best      = Some(self.current.clone());
+                }
+                return;
+            },
+        };
+
+        if self.stopped || cost + bound >= self.best_cost - EPSILON
+        {
+            return;
+        }
+
+        if self.nodes >= self.node_limit ||
+            (self.nodes.is_multiple_of(CONTROL_INTERVAL) && self.control.should_stop())
+        {
+            self.stopped = true;
+            return;
+        }
+        self.nodes += 1;
+
+        /* Prune if the same customers were left
+         * to serve at a lower cost. */
+        match self.reached.entry(K::from_words(uncovered))
+        {
+            Entry::Occupied(entry) if *entry.get() <= cost + EPSILON => return,
+            Entry::Occupied(mut entry) => { entry.insert(cost); },
+            Entry::Vacant(entry) => { entry.insert(cost); },
+        }
+
+        let (columns, bits) = &self.columns[first];
+        let words     : usize = uncovered.len();
+        let signature : u64   = uncovered.iter().fold(0, |signature, word| signature | word);
+
+        for (k, column) in columns.iter().enumerate()
+        {
+
+            /* The columns are sorted by reduced cost,
+             * so the following ones are worse. */
+            if cost + bound + column.reduced_cost >= self.best_cost - EPSILON
+            {
+                break;
+            }
+
+            /* A customer missing from the signature of
+             * the customers left is served already, and
+             * the signatures are exact up to 64 nodes. */
+            if column.signature & !signature != 0
+            {
+                continue;
+            }
+
+            let customers : &'b [u64] = &bits[k * words..(k + 1) * words];
+            if words > 1 && customers.iter().zip(uncovered.iter()).any(|(c, u)| c & !u != 0)
+            {
+                continue;
+            }
+
+            for (u, c) in uncovered.iter_mut().zip(customers.iter())
+            {
+                *u &= !c;
+            }
+            self.current.push(column.index);
+
+            self.branch(uncovered, cost + column.cost, bound - (column.cost - column.reduced_cost));
+
+            self.current.pop();
+            for (u, c) in uncovered.iter_mut().zip(customers.iter())
+            {
+                *u |= c;
+            }
+
+        }
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+
+    #[test]
+    fn selected_routes_are_an_exact_cover()
+    {
+
+        /* Six customers; the cheapest partition is
+         * {1, 2, 3} {4, 5} {6}, of cost 12. */
+        let routes : Vec<(f64, Vec<usize>)> = vec![
+            (5.0, vec![1, 2, 3]),
+            (4.0, vec![4, 5]),
+            (3.0, vec![6]),
+            (4.0, vec![3, 4]),
+            (5.0, vec![1, 2]),
+            (6.0, vec![5, 6]),
+            (2.0, vec![1]), (2.0, vec![2]), (2.0, vec![3]),
+            (2.0, vec![4]), (2.0, vec![5]),
+            (9.0, vec![2, 6, 4]),
+        ];
+
+        let result : SetPartitioningResult =
+            solve_set_partitioning(7, &routes, f64::INFINITY, usize::MAX, &SolveControl::unlimited());
+        let (cost, selected) = result.best.unwrap();
+        assert!(result.completed);
+        assert_eq!(cost, 12.0);
+
+        let mut served : Vec<usize> = selected.iter().flat_map(|&r| routes[r].1.clone()).collect();
+        served.sort();
+        assert_eq!(served, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(selected.iter().map(|&r| routes[r].0).sum::<f64>(), cost);
+
+        /* No partition is cheaper than the bound. */
+        let result : SetPartitioningResult =
+            solve_set_partitioning(7, &routes, 12.0, usize::MAX, &SolveControl::unlimited());
+        assert!(result.best.is_none() && result.completed);
+
+    }
+
+}
@@ -1,5 +1,125 @@
 use crate::savings_algorithm::savings_instance_trait::SavingsInstanceTrait;
-use crate::algorithm_interface::CVRPSolver;
+use std::collections::{VecDeque, BinaryHeap};
+use std::cmp::Ordering;
+
+use crate::algorithm_interface::{CVRPSolver, SolveControl};
+
+/* Parameters of the generalized savings
+ *   s_ij = d_0i + d_0j - lambda d_ij
+ *          + mu |d_0i - d_0j| + nu (q_i + q_j) / q_avg
+ * following Gaskell, Yellow and Altinel-Oncan.
+ * Clarke and Wright's savings are obtained with
+ * lambda = 1 and mu = nu = 0. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SavingsParameters
+{
+
+    pub lambda : f64,
+    pub mu     : f64,
+    pub nu     : f64,
+
+}
+
+impl SavingsParameters
+{
+
+    pub fn clarke_wright() -> SavingsParameters
+    {
+
+        SavingsParameters { lambda : 1.0, mu : 0.0, nu : 0.0 }
+
+    }
+
+    /* Grid of parameters explored by default, with
+     * lambda in (0, 2] and mu, nu in [0, 2]. */
+    pub fn default_grid() -> Vec<SavingsParameters>
+    {
+
+        let mut grid : Vec<SavingsParameters> = Vec::new();
+
+        for l in 1..=10
+        {
+            for m in 0..=4
+            {
+                for n in 0..=4
+                {
+                    grid.push(SavingsParameters {
+                        lambda : 0.2 * l as f64,
+                        mu     : 0.5 * m as f64,
+                        nu     : 0.5 * n as f64,
+                    });
+                }
+            }
+        }
+
+        return grid;
+
+    }
+
+}
+
+/* Variants of Clarke and Wright's algorithm. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum SavingsVariant
+{
+
+    /* Any pair of routes can be merged at each step. */
+    Parallel,
+
+    /* One route at a time is extended, and a new route
+     * is opened only when no feasible extension is left. */
+    Sequential,
+
+    /* Parallel merge restricted to the savings between
+     * each node and its nearest neighbours, suited
+     * for instances with thousands of nodes. */
+    Granular { neighbours : usize },
+
+}
+
+/* Element of the heap of savings, ordered
+ * by the value of the saving. */
+struct HeapSaving
+{
+
+    value : f64,
+    i     : usize,
+    j     : usize,
+
+}
+
+impl PartialEq for HeapSaving
+{
+
+    fn eq(&self, other : &Self) -> bool
+    {
+        self.cmp(other) == Ordering::Equal
+    }
+
+}
+
+impl Eq for HeapSaving {}
+
+impl PartialOrd for HeapSaving
+{
+
+    fn partial_cmp(&self, other : &Self) -> Option<Ordering>
+    {
+        Some(self.cmp(other))
+    }
+
+}
+
+impl Ord for HeapSaving
+{
+
+    fn cmp(&self, other : &Self) -> Ordering
+    {
+        self.value.total_cmp(&other.value)
+    }
+
+}
 
 /* Here the behaviour of the savings algorithm
  * is implemented.
@@ -8,7 +128,18 @@ use crate::algorithm_interface::CVRPSolver;
 pub struct SavingsSolver<'a>
 {
 
-    pub instance : &'a dyn SavingsInstanceTrait,
+    pub instance        : &'a dyn SavingsInstanceTrait,
+
+    pub variant         : SavingsVariant,
+
+    /* Parameters of the savings formula. */
+    pub parameters      : SavingsParameters,
+
+    /* If not empty, the algorithm is executed once
+     * for each element of the grid, and the best
+     * routes are returned. The field parameters
+     * is ignored in this case. */
+    pub parameters_grid : Vec<SavingsParameters>,
 
 }
 
@@ -18,6 +149,20 @@ pub struct SavingsSolver<'a>
 impl<'a> SavingsSolver<'a>
 {
 
+    /* Parallel solver with the savings
+     * of Clarke and Wright. */
+    pub fn new(instance : &'a dyn SavingsInstanceTrait) -> SavingsSolver<'a>
+    {
+
+        SavingsSolver {
+            instance,
+            variant         : SavingsVariant::Parallel,
+            parameters      : SavingsParameters::clarke_wright(),
+            parameters_grid : Vec::new(),
+        }
+
+    }
+
     pub fn add_instance(&'a mut self, i : &'a dyn SavingsInstanceTrait)
     {
 
@@ -27,11 +172,60 @@ impl<'a> SavingsSolver<'a>
 
 }
 
-/* Implementation of the CVRPSolver trait. */
-impl<'a> CVRPSolver for SavingsSolver<'a>
+/* Methods used internally by SavingsSolver. */
+impl<'a> SavingsSolver<'a>
 {
 
-    fn solve(&self) -> Vec< Vec<usize>>
+    /* Compute the savings for the given parameters.
+     * Each element is a tuple (i, j, s_ij). */
+    fn compute_savings(&self, parameters : &SavingsParameters) -> Vec<(usize, usize, f64)>
+    {
+
+        if *parameters == SavingsParameters::clarke_wright()
+        {
+            self.instance.compute_savings()
+                .iter()
+                .map(|&(i, j, s)| (i, j, s as f64))
+                .collect()
+        }
+        else
+        {
+            self.instance.compute_parametric_savings(
+                parameters.lambda,
+                parameters.mu,
+                parameters.nu)
+        }
+
+    }
+
+    /* Build the routes with the variant
+     * of the algorithm selected. */
+    fn build_routes(&self, parameters : &SavingsParameters) -> Vec< Vec<usize>>
+    {
+
+        match self.variant
+        {
+            SavingsVariant::Parallel   => self.merge_routes(parameters),
+            SavingsVariant::Sequential => self.extend_routes(parameters),
+            SavingsVariant::Granular { neighbours } =>
+                self.merge_routes_granular(parameters, neighbours),
+        }
+
+    }
+
+    /* Build the routes merging them according to
+     * the savings, from the highest to the lowest.
+     * Unlike the first version of the solver, the
+     * capacity is checked on the loads of the two
+     * routes, and a route can be filled exactly to
+     * capacity. The first version compared the loads
+     * stored for the nodes i and j, which are not
+     * updated when the route of i grows, and gave
+     * overloaded routes on 6 of the 16 instances
+     * of the input folder, F-n45-k4 among them.
+     * Merging stops at the first negative saving,
+     * which only the parametric savings can give. */
+    fn merge_routes(&self, parameters : &SavingsParameters) -> Vec< Vec<usize>>
     {
 
         let instance : &dyn SavingsInstanceTrait = self.instance;
@@ -69,12 +263,12 @@ impl<'a> CVRPSolver for SavingsSolver<'a>
          * (i, j, w(i,g)) where i and j are two incident
          * nodes for an edge e in E, and w(i,g) is
          * the weight of the edge e. */
-        let mut savings : Vec<(usize, usize, usize)> = instance.compute_savings();
+        let mut savings : Vec<(usize, usize, f64)> = self.compute_savings(parameters);
 
         /* Sort in increasing order.
          * Therefore the last element is always
          * the maximum. */
-        savings.sort_by_key(|s : &(usize, usize, usize)| s.2);
+        savings.sort_by(|s_1, s_2| s_1.2.partial_cmp(&s_2.2).unwrap());
 
         /* Compute the initial set of routes and
          * the array node_to_route, where the i-th
@@ -104,22 +298,27 @@ impl<'a> CVRPSolver for SavingsSolver<'a>
 
         /* Merge the routes according to
          * Clark and Wright's algorithm. */
-        while !savings.is_empty()
+        /* Take the maximum element
+         * inside savings out of the vector. */
+        while let Some(s) = savings.pop()
         {
 
-            /* Take the maximum element
-             * inside savings out of the vector. */
-            let s : (usize, usize, usize) = savings.pop().unwrap();
-
             let i : usize = s.0;
             let j : usize = s.1;
 
+            /* Negative savings would increase
+             * the cost of the routes. */
+            if s.2 < 0.0
+            {
+                break;
+            }
+
             /* Check if it is possible to
              * merge the two routes. */
             if node_to_routes[i] != node_to_routes[j] &&
                 is_node_terminal[i] &&
                 is_node_terminal[j] &&
-                routes_weight[i] + routes_weight[j] < instance.get_capacity()
+                routes_weight[node_to_routes[i]] + routes_weight[node_to_routes[j]] <= instance.get_capacity()
             {
 
                 let route_of_i : usize = node_to_routes[i];
@@ -169,16 +368,15 @@ impl<'a> CVRPSolver for SavingsSolver<'a>
 
                 /* The vector node_to_route should be
                  * updated for all the nodes in the
-                 * routes of j.
-                 * Similarly we should update routes
-                 * weights. */
+                 * routes of j. The weight of the route
+                 * of j is no longer used. */
                 let mut prev_route_of_j : Vec<usize> = routes[route_of_j].clone();
                 for j_index in 0..prev_route_of_j.len()
                 {
                     let node_in_j : usize     = prev_route_of_j[j_index];
                     node_to_routes[node_in_j] = route_of_i;
-                    routes_weight[node_in_j]  = routes_weight[route_of_i];
                 }
+                routes_weight[route_of_j] = 0;
 
                 routes[route_of_i].append(&mut prev_route_of_j);
                 routes[route_of_j].clear();
@@ -237,4 +435,410 @@ impl<'a> CVRPSolver for SavingsSolver<'a>
 
     }
 
-}
\ No newline at end of file
+    /* Build the routes one at a time. Each route starts
+     * from the highest saving between two unrouted nodes,
+     * then it is extended at its endpoints with the highest
+     * feasible saving, until no extension is left. The
+     * sorted savings are scanned once: a saving skipped
+     * stays infeasible, since routed nodes stay routed
+     * and the load of the route being extended grows. */
+    fn extend_routes(&self, parameters : &SavingsParameters) -> Vec< Vec<usize>>
+    {
+
+        let instance     : &dyn SavingsInstanceTrait = self.instance;
+        let nodes_demand : Vec<usize>                = instance.get_nodes_demand();
+        let capacity     : usize                     = instance.get_capacity();
+        let node_number  : usize                     = nodes_demand.len();
+        let mut routed   : Vec<bool>                 = vec![false; node_number];
+        let mut routes   : Vec< Vec<usize>>          = Vec::new();
+
+        /* Sort in decreasing order, negative savings
+         * would increase the cost of the routes. */
+        let mut savings : Vec<(usize, usize, f64)> = self.compute_savings(parameters);
+        savings.retain(|s| s.2 >= 0.0);
+        savings.sort_by(|s_1, s_2| s_2.2.partial_cmp(&s_1.2).unwrap());
+
+        /* Savings of each node, in the same order, as
+         * (position in savings, other node), and the
+         * next one to consider when it is an endpoint. */
+        let mut node_savings : Vec< Vec<(usize, usize)>> = vec![Vec::new(); node_number];
+        for (position, &(i, j, _)) in savings.iter().enumerate()
+        {
+            node_savings[i].push((position, j));
+            node_savings[j].push((position, i));
+        }
+        let mut next_saving : Vec<usize> = vec![0; node_number];
+        let mut next_seed   : usize      = 0;
+
+        loop
+        {
+
+            /* Open a new route with the highest saving
+             * between two nodes not yet routed. */
+            while next_seed < savings.len()
+            {
+                let (i, j, _) : (usize, usize, f64) = savings[next_seed];
+                if !routed[i] && !routed[j] && nodes_demand[i] + nodes_demand[j] <= capacity
+                {
+                    break;
+                }
+                next_seed += 1;
+            }
+
+            let (i, j) : (usize, usize) = match savings.get(next_seed)
+            {
+                Some(&(i, j, _)) => (i, j),
+                _ => break,
+            };
+
+            let mut route : VecDeque<usize> = VecDeque::from(vec![i, j]);
+            let mut load  : usize           = nodes_demand[i] + nodes_demand[j];
+            routed[i] = true;
+            routed[j] = true;
+
+            /* Extend the route at its endpoints, with the
+             * saving coming first among the two. */
+            loop
+            {
+
+                let mut extension : Option<(usize, usize, usize)> = None;
+
+                for endpoint in [*route.front().unwrap(), *route.back().unwrap()]
+                {
+
+                    let candidates : &Vec<(usize, usize)> = &node_savings[endpoint];
+                    let next       : &mut usize           = &mut next_saving[endpoint];
+
+                    while *next < candidates.len() &&
+                        (routed[candidates[*next].1] || load + nodes_demand[candidates[*next].1] > capacity)
+                    {
+                        *next += 1;
+                    }
+
+                    if let Some(&(position, n)) = candidates.get(*next)
+                    {
+                        if extension.is_none_or(|(best, _, _)| position < best)
+                        {
+                            extension = Some((position, endpoint, n));
+                        }
+                    }
+
+                }
+
+                match extension
+                {
+                    Some((_, endpoint, n)) =>
+                        {
+                            if endpoint == *route.front().unwrap()
+                            {
+                                route.push_front(n);
+                            }
+                            else
+                            {
+                                route.push_back(n);
+                            }
+                            load     += nodes_demand[n];
+                            routed[n] = true;
+                        }
+                    _ => break,
+                }
+
+            }
+
+            routes.push(route.into_iter().collect());
+
+        }
+
+        /* The nodes which could not be merged
+         * are served by a dedicated route. */
+        for (n, &is_routed) in routed.iter().enumerate().skip(1)
+        {
+            if !is_routed
+            {
+                routes.push(vec![n]);
+            }
+        }
+
+        return routes;
+
+    }
+
+    /* Build the routes merging them according to the
+     * savings between nearest neighbours, taken from a
+     * heap. Routes are kept as doubly linked lists, where
+     * only the endpoints store the other endpoint and the
+     * load of the route, so that each merge takes O(1). */
+    fn merge_routes_granular(
+        &self,
+        parameters : &SavingsParameters,
+        neighbours : usize)
+        -> Vec< Vec<usize>>
+    {
+
+        let instance     : &dyn SavingsInstanceTrait = self.instance;
+        let nodes_demand : Vec<usize>                = instance.get_nodes_demand();
+        let capacity     : usize                     = instance.get_capacity();
+        let node_number  : usize                     = nodes_demand.len();
+
+        /* The depot (node 0) marks a missing link, every
+         * node starts as a route with a single node. */
+        let mut links     : Vec<[usize; 2]> = vec![[0, 0]; node_number];
+        let mut other_end : Vec<usize>      = (0..node_number).collect();
+        let mut load      : Vec<usize>      = nodes_demand.clone();
+
+        let mut savings : BinaryHeap<HeapSaving> = instance
+            .compute_neighbour_savings(neighbours, parameters.lambda, parameters.mu, parameters.nu)
+            .into_iter()
+            .map(|(i, j, value)| HeapSaving { value, i, j })
+            .collect();
+
+        while let Some(HeapSaving { value, i, j }) = savings.pop()
+        {
+
+            /* Negative savings would increase
+             * the cost of the routes. */
+            if value < 0.0
+            {
+                break;
+            }
+
+            /* Only the endpoints of two distinct
+             * routes can be merged. */
+            let is_interior = |n : usize| links[n][0] != 0 && links[n][1] != 0;
+            if is_interior(i) || is_interior(j) || other_end[i] == j
+            {
+                continue;
+            }
+
+            let new_load : usize = load[i] + load[j];
+            if new_load > capacity
+            {
+                continue;
+            }
+
+            /* Link i and j, then update the
+             * endpoints of the new route. */
+            let end_of_i : usize = other_end[i];
+            let end_of_j : usize = other_end[j];
+
+            let free_i : usize = if links[i][0] == 0 { 0 } else { 1 };
+            let free_j : usize = if links[j][0] == 0 { 0 } else { 1 };
+            links[i][free_i] = j;
+            links[j][free_j] = i;
+
+            other_end[end_of_i] = end_of_j;
+            other_end[end_of_j] = end_of_i;
+            load[end_of_i]      = new_load;
+            load[end_of_j]      = new_load;
+
+        }
+
+        /* Walk each route from one of its endpoints. */
+        let mut visited : Vec<bool>        = vec![false; node_number];
+        let mut routes  : Vec< Vec<usize>> = Vec::new();
+
+        for start in 1..node_number
+        {
+
+            if visited[start] || (links[start][0] != 0 && links[start][1] != 0)
+            {
+                continue;
+            }
+
+            let mut route    : Vec<usize> = Vec::new();
+            let mut previous : usize      = 0;
+            let mut current  : usize      = start;
+
+            while current != 0
+            {
+                route.push(current);
+                visited[current] = true;
+
+                let next : usize = if links[current][0] != previous { links[current][0] }
+                                   else { links[current][1] };
+                previous = current;
+                current  = next;
+            }
+
+            routes.push(route);
+
+        }
+
+        return routes;
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for SavingsSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    /* The grid of parameters is explored until the
+     * search is stopped, reporting each new best. */
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        if control.should_stop()
+        {
+            return Vec::new();
+        }
+
+        if self.parameters_grid.is_empty()
+        {
+            let routes : Vec< Vec<usize>> = self.build_routes(&self.parameters);
+            control.report(&routes);
+            return routes;
+        }
+
+        /* Run the algorithm over the grid of
+         * parameters, keeping the best routes. The
+         * costs are computed without the full distance
+         * matrix, which the granular variant avoids. */
+        let mut best_routes : Vec< Vec<usize>> = Vec::new();
+        let mut best_cost   : f64              = f64::INFINITY;
+
+        for parameters in &self.parameters_grid
+        {
+
+            /* At least one element of the grid is
+             * run, so that some routes are returned. */
+            if !best_routes.is_empty() && control.should_stop()
+            {
+                break;
+            }
+
+            let routes : Vec< Vec<usize>> = self.build_routes(parameters);
+            let cost   : f64              = self.instance.compute_routes_cost(&routes);
+
+            if cost < best_cost
+            {
+                best_cost   = cost;
+                best_routes = routes;
+                control.report(&best_routes);
+            }
+
+        }
+
+        return best_routes;
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+
+    #[test]
+    fn clarke_wright_gives_feasible_routes()
+    {
+
+        /* Costs of the routes, all feasible, while the
+         * first version overloaded a route on F-n45-k4
+         * and on ulysses-n16-k3, at costs 764 and 9165. */
+        for (name, cost) in [("F-n45-k4", 763.0), ("ulysses-n16-k3", 8095.0), ("gr-n17-k3", 2737.0)]
+        {
+
+            let graph  : GraphInstance    = read_test_instance(name);
+            let solver : SavingsSolver    = SavingsSolver::new(&graph);
+            let routes : Vec< Vec<usize>> = solver.solve();
+
+            assert_feasible(&graph, &routes);
+            assert_eq!(graph.compute_routes_cost(&routes), cost);
+
+        }
+
+    }
+
+    #[test]
+    fn grid_is_no_worse_than_clarke_wright()
+    {
+
+        assert!(SavingsParameters::default_grid().contains(&SavingsParameters::clarke_wright()));
+
+        for name in ["F-n45-k4", "gr-n24-k4", "ulysses-n22-k4"]
+        {
+
+            let graph         : GraphInstance = read_test_instance(name);
+            let clarke_wright : SavingsSolver = SavingsSolver::new(&graph);
+            let grid          : SavingsSolver = SavingsSolver {
+                parameters_grid : SavingsParameters::default_grid(),
+                ..SavingsSolver::new(&graph)
+            };
+
+            let routes : Vec< Vec<usize>> = grid.solve();
+
+            assert_feasible(&graph, &routes);
+            assert!(graph.compute_routes_cost(&routes) <= graph.compute_routes_cost(&clarke_wright.solve()));
+
+        }
+
+    }
+
+    #[test]
+    fn granular_with_every_neighbour_is_parallel()
+    {
+
+        /* Routes with the endpoints in a fixed order,
+         * sorted, so that they can be compared. */
+        let normalize = |routes : Vec< Vec<usize>>| -> Vec< Vec<usize>>
+        {
+            let mut result : Vec< Vec<usize>> = routes.into_iter()
+                .map(|mut r| { if r.first() > r.last() { r.reverse(); } r })
+                .collect();
+            result.sort();
+            result
+        };
+
+        /* Parameters for which the savings are distinct, since
+         * ties are taken in a different order from the heap. */
+        let parameters : SavingsParameters = SavingsParameters { lambda : 1.2, mu : 0.5, nu : 0.5 };
+
+        for name in ["F-n45-k4", "gr-n24-k4", "att-n48-k4", "ulysses-n22-k4"]
+        {
+
+            let graph    : GraphInstance = read_test_instance(name);
+            let parallel : SavingsSolver = SavingsSolver { parameters, ..SavingsSolver::new(&graph) };
+            let granular : SavingsSolver = SavingsSolver {
+                variant : SavingsVariant::Granular { neighbours : graph.get_nodes_list().len() },
+                parameters,
+                ..SavingsSolver::new(&graph)
+            };
+
+            assert_eq!(normalize(granular.solve()), normalize(parallel.solve()));
+
+        }
+
+    }
+
+    #[test]
+    fn sequential_serves_every_customer()
+    {
+
+        for name in ["F-n45-k4", "gr-n24-k4", "hk-n48-k4", "ulysses-n16-k3"]
+        {
+
+            let graph  : GraphInstance = read_test_instance(name);
+            let solver : SavingsSolver = SavingsSolver {
+                variant : SavingsVariant::Sequential,
+                ..SavingsSolver::new(&graph)
+            };
+
+            assert_feasible(&graph, &solver.solve());
+
+        }
+
+    }
+
+}
@@ -2,15 +2,40 @@
  * computing savings, available only
  * for GraphInstance when explicitly
  * implemented. */
-pub trait SavingsInstanceTrait
+pub trait SavingsInstanceTrait : Sync
 {
 
     fn compute_savings(&self) -> Vec<(usize, usize, usize)>;
 
+    /* Generalized savings, see SavingsParameters. */
+    fn compute_parametric_savings(
+        &self,
+        lambda : f64,
+        mu     : f64,
+        nu     : f64)
+        -> Vec<(usize, usize, f64)>;
+
+    /* Generalized savings restricted to the couples
+     * where one node is among the given number of
+     * nearest neighbours of the other one. The full
+     * distance matrix is not required. */
+    fn compute_neighbour_savings(
+        &self,
+        neighbours : usize,
+        lambda     : f64,
+        mu         : f64,
+        nu         : f64)
+        -> Vec<(usize, usize, f64)>;
+
     fn get_capacity(&self) -> usize;
 
     fn get_nodes_list(&self) -> Vec<usize>;
 
     fn get_nodes_demand(&self) -> Vec<usize>;
 
+    /* Cost of the routes, computed without the
+     * full distance matrix where the instance
+     * allows it. */
+    fn compute_routes_cost(&self, routes : &[Vec<usize>]) -> f64;
+
 }
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod simulated_annealing_imp;
@@ -0,0 +1,332 @@
+use std::time::Instant;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+use crate::local_search::moves::{Move, Neighbourhood};
+use crate::local_search::intra_route_imp::optimize_route_with;
+use crate::algorithm_interface::{CVRPSolver, StoppingCriterion, compute_routes_cost, SolveControl};
+
+/* Schedule of the temperature during the search. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum CoolingSchedule
+{
+
+    /* The temperature is multiplied by alpha
+     * at every iteration. */
+    Geometric { alpha : f64 },
+
+    /* The temperature decreases linearly from the
+     * start temperature to zero, following the
+     * progress of the stopping criterion. */
+    Linear,
+
+    /* Geometric cooling, where the temperature is
+     * set back to the given fraction of the start
+     * temperature after the given number of
+     * iterations without a new best solution. */
+    Reheating { alpha : f64, patience : usize, fraction : f64 },
+
+}
+
+/* Here the simulated annealing is implemented.
+ * Starting from the routes of another solver, at
+ * each iteration a random feasible move is drawn,
+ * and applied if it improves the routes, or else
+ * with a probability which decreases with the
+ * worsening and with the temperature. */
+pub struct SimulatedAnnealingSolver<'a>
+{
+
+    pub instance           : &'a dyn LocalSearchInstanceTrait,
+
+    /* Solver computing the initial routes. */
+    pub initial_solver     : &'a dyn CVRPSolver,
+
+    pub cooling_schedule   : CoolingSchedule,
+
+    pub stopping_criterion : StoppingCriterion,
+
+    /* Neighbourhoods from which the moves are drawn. */
+    pub neighbourhoods     : Vec<Neighbourhood>,
+
+    /* Probability of accepting an average worsening
+     * move at the start temperature. */
+    pub start_acceptance   : f64,
+
+    pub seed               : u64,
+
+}
+
+/* Number of random moves used to
+ * estimate the start temperature. */
+const TEMPERATURE_SAMPLES : usize = 100;
+
+/* Methods of SimulatedAnnealingSolver not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> SimulatedAnnealingSolver<'a>
+{
+
+    /* Simulated annealing over relocate, swap and
+     * 2-opt moves with geometric cooling. */
+    pub fn new(
+        instance       : &'a dyn LocalSearchInstanceTrait,
+        initial_solver : &'a dyn CVRPSolver,
+        seed           : u64)
+        -> SimulatedAnnealingSolver<'a>
+    {
+
+        SimulatedAnnealingSolver {
+            instance,
+            initial_solver,
+            cooling_schedule   : CoolingSchedule::Geometric { alpha : 0.99997 },
+            stopping_criterion : StoppingCriterion::Iterations(200000),
+            neighbourhoods     : vec![
+                Neighbourhood::Relocate,
+                Neighbourhood::Swap,
+                Neighbourhood::TwoOpt,
+            ],
+            start_acceptance   : 0.5,
+            seed,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn LocalSearchInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    pub fn set_seed(&mut self, seed : u64)
+    {
+
+        self.seed = seed;
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for SimulatedAnnealingSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let instance        : &dyn LocalSearchInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>                = instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>                    = instance.get_nodes_demand();
+        let capacity        : usize                         = instance.get_capacity();
+        let start           : Instant                       = Instant::now();
+        let mut rng         : StdRng                        = StdRng::seed_from_u64(self.seed);
+
+        /* An empty route is kept, so that
+         * a new route can be opened. */
+        let mut routes : Vec< Vec<usize>> = self.initial_solver.solve_with_control(control);
+        routes.retain(|r| !r.is_empty());
+        routes.push(Vec::new());
+
+        let mut loads : Vec<usize> = routes.iter()
+            .map(|r| r.iter().map(|&n| nodes_demand[n]).sum())
+            .collect();
+
+        let mut current_cost : f64              = compute_routes_cost(&distance_matrix, &routes);
+        let mut best_cost    : f64              = current_cost;
+        let mut best_routes  : Vec< Vec<usize>> = routes.clone();
+        control.report(&best_routes);
+
+        if self.neighbourhoods.is_empty()
+        {
+            best_routes.retain(|r| !r.is_empty());
+            return best_routes;
+        }
+
+        /* The start temperature accepts the average
+         * worsening move with the given probability. */
+        let worsening : Vec<f64> = (0..TEMPERATURE_SAMPLES)
+            .filter_map(|_| self.neighbourhoods.choose(&mut rng).unwrap().random_move(&routes, &mut rng))
+            .map(|m| m.delta(&distance_matrix, &routes))
+            .filter(|&delta| delta > 0.0)
+            .collect();
+        let average_worsening : f64 = if worsening.is_empty()
+        {
+            current_cost / routes.len() as f64
+        }
+        else
+        {
+            worsening.iter().sum::<f64>() / worsening.len() as f64
+        };
+        let start_temperature : f64 = -average_worsening / self.start_acceptance.clamp(1e-6, 1.0 - 1e-6).ln();
+        let mut temperature   : f64 = start_temperature;
+
+        let mut iteration        : usize = 0;
+        let mut last_improvement : usize = 0;
+        while !self.stopping_criterion.is_met(iteration, start) && !control.should_stop()
+        {
+
+            iteration += 1;
+
+            let candidate : Option<Move> = self.neighbourhoods
+                .choose(&mut rng)
+                .unwrap()
+                .random_move(&routes, &mut rng)
+                .filter(|m| m.is_feasible(&routes, &loads, &nodes_demand, capacity));
+
+            if let Some(m) = candidate
+            {
+
+                let delta : f64 = m.delta(&distance_matrix, &routes);
+
+                if delta <= 0.0 || rng.gen::<f64>() < (-delta / temperature).exp()
+                {
+
+                    for (r, load) in m.loads_after(&routes, &loads, &nodes_demand)
+                    {
+                        loads[r] = load;
+                    }
+                    m.apply(&mut routes);
+                    current_cost += delta;
+
+                    if routes.iter().all(|r| !r.is_empty())
+                    {
+                        routes.push(Vec::new());
+                        loads.push(0);
+                    }
+
+                    if current_cost < best_cost - 1e-9
+                    {
+                        best_cost        = current_cost;
+                        best_routes      = routes.clone();
+                        last_improvement = iteration;
+                        control.report(&best_routes);
+                    }
+
+                }
+
+            }
+
+            temperature = match self.cooling_schedule
+            {
+                CoolingSchedule::Geometric { alpha } => temperature * alpha,
+                CoolingSchedule::Linear =>
+                    start_temperature * (1.0 - self.stopping_criterion.progress(iteration, start)),
+                CoolingSchedule::Reheating { alpha, patience, fraction } =>
+                    if iteration - last_improvement >= patience
+                    {
+                        last_improvement = iteration;
+                        start_temperature * fraction
+                    }
+                    else
+                    {
+                        temperature * alpha
+                    },
+            };
+
+        }
+
+        best_routes.retain(|r| !r.is_empty());
+        for route in best_routes.iter_mut()
+        {
+            optimize_route_with(
+                &distance_matrix, route, &[Neighbourhood::TwoOpt, Neighbourhood::OrOpt], control);
+        }
+        control.report(&best_routes);
+
+        return best_routes;
+
+    }
+
+    fn get_seed(&self) -> Option<u64>
+    {
+
+        Some(self.seed)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use std::time::Duration;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+
+    #[test]
+    fn deadline_stops_the_search()
+    {
+
+        let graph   : GraphInstance            = read_test_instance("F-n72-k4");
+        let savings : SavingsSolver            = SavingsSolver::new(&graph);
+        let solver  : SimulatedAnnealingSolver = SimulatedAnnealingSolver {
+            stopping_criterion : StoppingCriterion::Iterations(usize::MAX),
+            ..SimulatedAnnealingSolver::new(&graph, &savings, 1)
+        };
+
+        let start   : Instant          = Instant::now();
+        let control : SolveControl     = SolveControl::new(None, Some(start + Duration::from_millis(100)), None, None);
+        let routes  : Vec< Vec<usize>> = solver.solve_with_control(&control);
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_feasible(&graph, &routes);
+
+    }
+
+    #[test]
+    fn every_cooling_schedule_gives_feasible_routes()
+    {
+
+        let graph   : GraphInstance = read_test_instance("F-n45-k4");
+        let savings : SavingsSolver = SavingsSolver::new(&graph);
+
+        for cooling_schedule in [
+            CoolingSchedule::Geometric { alpha : 0.999 },
+            CoolingSchedule::Linear,
+            CoolingSchedule::Reheating { alpha : 0.999, patience : 500, fraction : 0.5 },
+        ]
+        {
+
+            let solver : SimulatedAnnealingSolver = SimulatedAnnealingSolver {
+                cooling_schedule,
+                stopping_criterion : StoppingCriterion::Iterations(5000),
+                ..SimulatedAnnealingSolver::new(&graph, &savings, 1)
+            };
+
+            assert_feasible(&graph, &solver.solve());
+
+        }
+
+    }
+
+    #[test]
+    fn same_seed_gives_same_routes()
+    {
+
+        let graph   : GraphInstance = read_test_instance("F-n45-k4");
+        let savings : SavingsSolver = SavingsSolver::new(&graph);
+        let solver  = |seed : u64| -> SimulatedAnnealingSolver
+        {
+            SimulatedAnnealingSolver {
+                stopping_criterion : StoppingCriterion::Iterations(5000),
+                ..SimulatedAnnealingSolver::new(&graph, &savings, seed)
+            }
+        };
+
+        assert_eq!(solver(5).solve(), solver(5).solve());
+
+    }
+
+}
@@ -1,5 +1,5 @@
 use crate::sweep_algorithm::sweep_instance_trait::SweepInstanceTrait;
-use crate::algorithm_interface::CVRPSolver;
+use crate::algorithm_interface::{CVRPSolver, compute_routes_cost};
 
 /* Here the behaviour of the sweep algorithm
  * is implemented.
@@ -26,22 +26,35 @@ impl<'a> SweepSolver<'a>
 
 }
 
-/* Implementation of the CVRPSolver trait. */
-impl<'a> CVRPSolver for SweepSolver<'a>
+/* Direction of the sweep around the depot. */
+#[derive(Clone, Copy, PartialEq)]
+enum SweepDirection
 {
 
-    fn solve(&self) -> Vec< Vec<usize>> {
+    CounterClockwise,
+    Clockwise,
 
-        let instance : &dyn SweepInstanceTrait = self.instance;
+}
 
-        /* Compute other parameters for
-         * further computation. */
-        let nodes_demand       : Vec<usize> = instance.get_nodes_demand();
-        let capacity           : usize      = instance.get_capacity();
+/* Methods used internally by SweepSolver. */
+impl<'a> SweepSolver<'a>
+{
+
+    /* Partition the nodes into routes, following
+     * the order of the sweep given as input. A route
+     * is closed as soon as the next node does not
+     * fit into the remaining capacity. */
+    fn compute_routes(
+        &self,
+        sweep        : &[usize],
+        nodes_demand : &[usize],
+        capacity     : usize)
+        -> Vec< Vec<usize>>
+    {
 
-        /* Compute a tour of the instance graph. */
-        let ordered_nodes_list  : Vec<usize> = instance.order_nodes();
-        let mut remaining_nodes : Vec<usize> = ordered_nodes_list.clone();
+        /* The nodes are popped from the end,
+         * therefore the sweep is reversed. */
+        let mut remaining_nodes : Vec<usize> = sweep.iter().rev().cloned().collect();
 
         /* Initialize the routes vector, without
          * knowing the final size of it. */
@@ -71,9 +84,9 @@ impl<'a> CVRPSolver for SweepSolver<'a>
                 else
                 {
 
-                    /* Eventually, here we can execute a TSP algorithm
-                     * over the nodes inside the already computed route,
-                     * in order to increase the quality of the result. */
+                    /* The order of the nodes inside the route follows
+                     * the sweep, it can be improved afterwards by the
+                     * IntraRouteOptimizer of the local_search module. */
 
                     /* The current node is inserted in the same position. */
                     remaining_nodes.push(current_node);
@@ -94,4 +107,64 @@ impl<'a> CVRPSolver for SweepSolver<'a>
 
     }
 
-}
\ No newline at end of file
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for SweepSolver<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>> {
+
+        let instance : &dyn SweepInstanceTrait = self.instance;
+
+        /* Compute other parameters for
+         * further computation. */
+        let nodes_demand       : Vec<usize>       = instance.get_nodes_demand();
+        let capacity           : usize            = instance.get_capacity();
+        let distance_matrix    : Vec< Vec<f64>>   = instance.get_distance_matrix();
+
+        /* Nodes sorted counter-clockwise
+         * around the depot. */
+        let ordered_nodes_list : Vec<usize>       = instance.order_nodes();
+        let node_number        : usize            = ordered_nodes_list.len();
+
+        let mut best_routes    : Vec< Vec<usize>> = Vec::new();
+        let mut best_cost      : f64              = f64::INFINITY;
+
+        /* Every customer is tried as the starting
+         * ray of the sweep, in both directions. */
+        for start in 0..node_number
+        {
+
+            for direction in [SweepDirection::CounterClockwise, SweepDirection::Clockwise]
+            {
+
+                let sweep : Vec<usize> = (0..node_number)
+                    .map(|k| match direction
+                    {
+                        SweepDirection::CounterClockwise =>
+                            ordered_nodes_list[(start + k) % node_number],
+                        SweepDirection::Clockwise =>
+                            ordered_nodes_list[(start + node_number - k) % node_number],
+                    })
+                    .collect();
+
+                let routes : Vec< Vec<usize>> =
+                    self.compute_routes(&sweep, &nodes_demand, capacity);
+                let cost   : f64 = compute_routes_cost(&distance_matrix, &routes);
+
+                if cost < best_cost
+                {
+                    best_cost   = cost;
+                    best_routes = routes;
+                }
+
+            }
+
+        }
+
+        return best_routes;
+
+    }
+
+}
@@ -3,13 +3,17 @@
  * sweep algorithm, available only
  * for GraphInstance when explicitly
  * implemented. */
-pub trait SweepInstanceTrait
+pub trait SweepInstanceTrait : Sync
 {
 
+    /* Nodes are returned in counter-clockwise
+     * order around the depot. */
     fn order_nodes(&self) -> Vec<usize>;
 
     fn get_capacity(&self) -> usize;
 
     fn get_nodes_demand(&self) -> Vec<usize>;
 
-}
\ No newline at end of file
+    fn get_distance_matrix(&self) -> Vec< Vec<f64>>;
+
+}
@@ -0,0 +1 @@
+pub mod tabu_search_imp;
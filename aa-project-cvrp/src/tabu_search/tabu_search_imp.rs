@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+use std::time::Instant;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+use crate::local_search::moves::{Move, Neighbourhood, granular_neighbours};
+use crate::local_search::intra_route_imp::optimize_route_with;
+use crate::algorithm_interface::{CVRPSolver, StoppingCriterion, compute_routes_cost, SolveControl};
+
+/* Here the granular tabu search of Toth and Vigo
+ * is implemented. Starting from the routes of
+ * another solver, at each iteration the best non
+ * tabu move of the sparsified neighbourhoods is
+ * applied, even if it worsens the solution.
+ * Overloaded routes are allowed during the search,
+ * with a penalty on the excess of load. */
+pub struct GranularTabuSearch<'a>
+{
+
+    pub instance           : &'a dyn LocalSearchInstanceTrait,
+
+    /* Solver computing the initial routes. */
+    pub initial_solver     : &'a dyn CVRPSolver,
+
+    pub stopping_criterion : StoppingCriterion,
+
+    /* Only the edges shorter than sparsification times
+     * the average edge of the initial routes are
+     * considered by the moves. */
+    pub sparsification     : f64,
+
+    /* Neighbourhoods explored at each iteration. */
+    pub neighbourhoods     : Vec<Neighbourhood>,
+
+    /* Seed of the random tabu tenure. */
+    pub seed               : u64,
+
+}
+
+/* Bounds of the number of iterations in which
+ * a customer can not return to a route. */
+const MIN_TABU_TENURE : usize = 5;
+const MAX_TABU_TENURE : usize = 10;
+
+/* Factor of the update of the penalty
+ * on the excess of load. */
+const PENALTY_FACTOR  : f64   = 1.5;
+
+/* Bound of the penalty, relative to its
+ * initial value, in both directions. */
+const MIN_PENALTY_RATIO : f64 = 1e-3;
+
+/* Methods of GranularTabuSearch not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> GranularTabuSearch<'a>
+{
+
+    /* Granular tabu search over relocate, swap,
+     * 2-opt* and 2-opt moves, with the
+     * sparsification factor suggested by
+     * Toth and Vigo. */
+    pub fn new(
+        instance       : &'a dyn LocalSearchInstanceTrait,
+        initial_solver : &'a dyn CVRPSolver,
+        seed           : u64)
+        -> GranularTabuSearch<'a>
+    {
+
+        GranularTabuSearch {
+            instance,
+            initial_solver,
+            stopping_criterion : StoppingCriterion::Iterations(2000),
+            sparsification     : 2.5,
+            neighbourhoods     : vec![
+                Neighbourhood::Relocate,
+                Neighbourhood::Swap,
+                Neighbourhood::TwoOptStar,
+                Neighbourhood::TwoOpt,
+            ],
+            seed,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn LocalSearchInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    pub fn set_seed(&mut self, seed : u64)
+    {
+
+        self.seed = seed;
+
+    }
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for GranularTabuSearch<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let instance        : &dyn LocalSearchInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>                = instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>                    = instance.get_nodes_demand();
+        let capacity        : usize                         = instance.get_capacity();
+        let start           : Instant                       = Instant::now();
+        let mut rng         : StdRng                        = StdRng::seed_from_u64(self.seed);
+
+        let mut routes : Vec< Vec<usize>> = self.initial_solver.solve_with_control(control);
+        routes.retain(|r| !r.is_empty());
+
+        let mut best_routes : Vec< Vec<usize>> = routes.clone();
+        let mut best_cost   : f64              = compute_routes_cost(&distance_matrix, &routes);
+        control.report(&best_routes);
+
+        /* Sparsified neighbourhoods. */
+        let edges_number : usize           = routes.iter().map(|r| r.len() + 1).sum();
+        let threshold    : f64             = self.sparsification * best_cost / edges_number.max(1) as f64;
+        let neighbours   : Vec< Vec<usize>> = granular_neighbours(&distance_matrix, threshold);
+
+        /* The penalty starts from the average cost
+         * of a unit of demand. */
+        let total_demand : usize = nodes_demand.iter().sum();
+        let base_penalty : f64   = best_cost / total_demand.max(1) as f64;
+        let mut penalty  : f64   = base_penalty;
+
+        let excess = |load : usize| -> f64 { load.saturating_sub(capacity) as f64 };
+
+        /* Iteration until which a customer can
+         * not be moved back into a route. */
+        let mut tabu : HashMap<(usize, usize), usize> = HashMap::new();
+
+        let mut iteration : usize = 0;
+        while !self.stopping_criterion.is_met(iteration, start) && !control.should_stop()
+        {
+
+            iteration += 1;
+
+            /* Route indices are kept stable during the search,
+             * so that they can be used as tabu attributes,
+             * with an empty route always available. */
+            if routes.iter().all(|r| !r.is_empty())
+            {
+                routes.push(Vec::new());
+            }
+            let empty_route : usize = routes.iter().position(|r| r.is_empty()).unwrap();
+
+            let loads : Vec<usize> = routes.iter()
+                .map(|r| r.iter().map(|&n| nodes_demand[n]).sum())
+                .collect();
+            let current_cost   : f64 = compute_routes_cost(&distance_matrix, &routes);
+            let current_excess : f64 = loads.iter().map(|&l| excess(l)).sum();
+
+            /* The moves to an empty route are not granular. */
+            let mut moves : Vec<Move> = Vec::new();
+            for neighbourhood in &self.neighbourhoods
+            {
+                moves.extend(neighbourhood.granular_moves(&routes, &neighbours));
+            }
+            if self.neighbourhoods.contains(&Neighbourhood::Relocate)
+            {
+                for (from_route, route) in routes.iter().enumerate()
+                {
+                    for from in 0..route.len()
+                    {
+                        moves.push(Move::Relocate { from_route, from, to_route : empty_route, to : 0 });
+                    }
+                }
+            }
+
+            let mut selected       : Option<Move> = None;
+            let mut selected_value : f64          = f64::INFINITY;
+
+            for m in moves
+            {
+
+                let delta        : f64 = m.delta(&distance_matrix, &routes);
+                let excess_delta : f64 = m.loads_after(&routes, &loads, &nodes_demand)
+                    .iter()
+                    .map(|&(r, load)| excess(load) - excess(loads[r]))
+                    .sum();
+                let value        : f64 = delta + penalty * excess_delta;
+
+                if value >= selected_value
+                {
+                    continue;
+                }
+
+                /* A tabu move is accepted only if it
+                 * leads to a new best feasible solution. */
+                let is_tabu : bool = m.moved_customers(&routes)
+                    .iter()
+                    .any(|&(n, _, to)| tabu.get(&(n, to)).is_some_and(|&t| t >= iteration));
+                let aspiration : bool = current_excess + excess_delta == 0.0 &&
+                    current_cost + delta < best_cost - 1e-9;
+
+                if !is_tabu || aspiration
+                {
+                    selected       = Some(m);
+                    selected_value = value;
+                }
+
+            }
+
+            let selected_move : Move = match selected
+            {
+                Some(m) => m,
+                _ => break,
+            };
+
+            for (n, from, _) in selected_move.moved_customers(&routes)
+            {
+                tabu.insert((n, from), iteration + rng.gen_range(MIN_TABU_TENURE..=MAX_TABU_TENURE));
+            }
+            selected_move.apply(&mut routes);
+
+            /* Update the best solution and the penalty,
+             * decreased while the routes are feasible. */
+            let feasible : bool = routes.iter()
+                .all(|r| r.iter().map(|&n| nodes_demand[n]).sum::<usize>() <= capacity);
+
+            if feasible
+            {
+                let cost : f64 = compute_routes_cost(&distance_matrix, &routes);
+                if cost < best_cost - 1e-9
+                {
+                    best_cost   = cost;
+                    best_routes = routes.iter().filter(|r| !r.is_empty()).cloned().collect();
+                    control.report(&best_routes);
+                }
+                penalty = (penalty / PENALTY_FACTOR).max(base_penalty * MIN_PENALTY_RATIO);
+            }
+            else
+            {
+                penalty = (penalty * PENALTY_FACTOR).min(base_penalty / MIN_PENALTY_RATIO);
+            }
+
+        }
+
+        for route in best_routes.iter_mut()
+        {
+            optimize_route_with(
+                &distance_matrix, route, &[Neighbourhood::TwoOpt, Neighbourhood::OrOpt], control);
+        }
+        control.report(&best_routes);
+
+        return best_routes;
+
+    }
+
+    fn get_seed(&self) -> Option<u64>
+    {
+
+        Some(self.seed)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use std::time::Duration;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+
+    #[test]
+    fn no_worse_than_the_initial_routes()
+    {
+
+        for name in ["F-n45-k4", "gr-n24-k4", "ulysses-n22-k4"]
+        {
+
+            let graph   : GraphInstance      = read_test_instance(name);
+            let savings : SavingsSolver      = SavingsSolver::new(&graph);
+            let solver  : GranularTabuSearch = GranularTabuSearch {
+                stopping_criterion : StoppingCriterion::Iterations(300),
+                ..GranularTabuSearch::new(&graph, &savings, 1)
+            };
+
+            let distance_matrix : Vec< Vec<f64>>   = graph.get_distance_matrix();
+            let routes          : Vec< Vec<usize>> = solver.solve();
+
+            assert_feasible(&graph, &routes);
+            assert!(routes.iter().all(|r| !r.is_empty()));
+            assert!(compute_routes_cost(&distance_matrix, &routes) <=
+                compute_routes_cost(&distance_matrix, &savings.solve()));
+
+        }
+
+    }
+
+    #[test]
+    fn deadline_stops_the_search()
+    {
+
+        let graph   : GraphInstance      = read_test_instance("F-n72-k4");
+        let savings : SavingsSolver      = SavingsSolver::new(&graph);
+        let solver  : GranularTabuSearch = GranularTabuSearch {
+            stopping_criterion : StoppingCriterion::Iterations(usize::MAX),
+            ..GranularTabuSearch::new(&graph, &savings, 1)
+        };
+
+        let start   : Instant          = Instant::now();
+        let control : SolveControl     = SolveControl::new(None, Some(start + Duration::from_millis(100)), None, None);
+        let routes  : Vec< Vec<usize>> = solver.solve_with_control(&control);
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_feasible(&graph, &routes);
+
+    }
+
+}
@@ -0,0 +1,290 @@
+/* Parser for the configuration files of the
+ * variable neighbourhood search. Each line is a
+ * couple KEY : VALUE, as in the TSPLIB format,
+ * and the lines starting with # are comments.
+ * For instance:
+ *
+ *   NEIGHBOURHOODS : TWO_OPT RELOCATE SWAP TWO_OPT_STAR
+ *   CHANGE_RULE    : PIPE
+ *   STRATEGY       : BEST_IMPROVEMENT
+ *   SHAKING        : RELOCATE CROSS_EXCHANGE
+ *   SHAKING_MOVES  : 3
+ *   ITERATIONS     : 500
+ *
+ * The keys which are missing keep the
+ * default value of the solver. */
+
+use std::time::Duration;
+use nom::IResult;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{space0, space1, not_line_ending, digit1};
+use nom::combinator::map_res;
+use nom::multi::separated_list1;
+use nom::sequence::tuple;
+
+use crate::local_search::moves::Neighbourhood;
+use crate::local_search::inter_route_imp::ImprovementStrategy;
+use crate::variable_neighbourhood_search::variable_neighbourhood_descent_imp::NeighbourhoodChangeRule;
+use crate::algorithm_interface::StoppingCriterion;
+
+/* The parameters of the search which
+ * can be given in a configuration file. */
+#[derive(Clone, PartialEq, Debug)]
+pub struct VnsConfig
+{
+
+    pub neighbourhoods         : Vec<Neighbourhood>,
+
+    pub change_rule            : NeighbourhoodChangeRule,
+
+    pub strategy               : ImprovementStrategy,
+
+    pub shaking_neighbourhoods : Vec<Neighbourhood>,
+
+    pub shaking_moves          : usize,
+
+    pub stopping_criterion     : StoppingCriterion,
+
+}
+
+impl Default for VnsConfig
+{
+
+    fn default() -> VnsConfig
+    {
+
+        VnsConfig {
+            neighbourhoods         : vec![
+                Neighbourhood::TwoOpt,
+                Neighbourhood::OrOpt,
+                Neighbourhood::Relocate,
+                Neighbourhood::Swap,
+                Neighbourhood::TwoOptStar,
+                Neighbourhood::CrossExchange,
+            ],
+            change_rule            : NeighbourhoodChangeRule::Sequential,
+            strategy               : ImprovementStrategy::FirstImprovement,
+            shaking_neighbourhoods : vec![
+                Neighbourhood::Relocate,
+                Neighbourhood::Swap,
+                Neighbourhood::CrossExchange,
+            ],
+            shaking_moves          : 3,
+            stopping_criterion     : StoppingCriterion::Iterations(100),
+        }
+
+    }
+
+}
+
+fn is_word_char(c : char) -> bool
+{
+
+    c.is_ascii_alphanumeric() || c == '_'
+
+}
+
+/* Parse a line KEY : VALUE, with
+ * the value trimmed of spaces. */
+fn parse_key_value(input : &str) -> IResult<&str, (&str, &str)>
+{
+
+    let (input, (_, key, _, _, _, value)) =
+        tuple((space0, take_while1(is_word_char), space0, tag(":"), space0, not_line_ending))(input)?;
+
+    Ok((input, (key, value.trim_end())))
+
+}
+
+/* Parse a list of words separated by spaces. */
+fn parse_words(input : &str) -> IResult<&str, Vec<&str>>
+{
+
+    separated_list1(space1, take_while1(is_word_char))(input)
+
+}
+
+fn parse_neighbourhood(name : &str) -> Result<Neighbourhood, String>
+{
+
+    match name
+    {
+        "TWO_OPT"        => Ok(Neighbourhood::TwoOpt),
+        "OR_OPT"         => Ok(Neighbourhood::OrOpt),
+        "RELOCATE"       => Ok(Neighbourhood::Relocate),
+        "SWAP"           => Ok(Neighbourhood::Swap),
+        "TWO_OPT_STAR"   => Ok(Neighbourhood::TwoOptStar),
+        "CROSS_EXCHANGE" => Ok(Neighbourhood::CrossExchange),
+        _ => Err(format!("Unknown neighbourhood {}", name)),
+    }
+
+}
+
+fn parse_neighbourhoods(value : &str) -> Result<Vec<Neighbourhood>, String>
+{
+
+    match parse_words(value)
+    {
+        Ok(("", names)) => names.into_iter().map(parse_neighbourhood).collect(),
+        _ => Err(format!("Invalid list of neighbourhoods {}", value)),
+    }
+
+}
+
+fn parse_change_rule(value : &str) -> Result<NeighbourhoodChangeRule, String>
+{
+
+    match value
+    {
+        "SEQUENTIAL" => Ok(NeighbourhoodChangeRule::Sequential),
+        "CYCLIC"     => Ok(NeighbourhoodChangeRule::Cyclic),
+        "PIPE"       => Ok(NeighbourhoodChangeRule::Pipe),
+        _ => Err(format!("Unknown change rule {}", value)),
+    }
+
+}
+
+fn parse_strategy(value : &str) -> Result<ImprovementStrategy, String>
+{
+
+    match value
+    {
+        "FIRST_IMPROVEMENT" => Ok(ImprovementStrategy::FirstImprovement),
+        "BEST_IMPROVEMENT"  => Ok(ImprovementStrategy::BestImprovement),
+        _ => Err(format!("Unknown improvement strategy {}", value)),
+    }
+
+}
+
+/* Parse an unsigned integer, made of digits only. */
+fn parse_number(key : &str, value : &str) -> Result<usize, String>
+{
+
+    let result : IResult<&str, usize> = map_res(digit1, |digits : &str| digits.parse::<usize>())(value);
+
+    match result
+    {
+        Ok(("", number)) => Ok(number),
+        _ => Err(format!("The value of {} should be an unsigned integer", key)),
+    }
+
+}
+
+/* Parse the configuration, starting from the
+ * default one. The time limit is given in
+ * milliseconds, and replaces the iterations.
+ * Return an error describing the first
+ * malformed line, if any. */
+pub fn parse_vns_config(input : &str) -> Result<VnsConfig, String>
+{
+
+    let mut config : VnsConfig = VnsConfig::default();
+
+    for line in input.lines()
+    {
+
+        let trimmed : &str = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "EOF"
+        {
+            continue;
+        }
+
+        let (key, value) : (&str, &str) = match parse_key_value(trimmed)
+        {
+            Ok((_, couple)) => couple,
+            _ => return Err(format!("Invalid configuration line {}", line)),
+        };
+
+        match key
+        {
+            "NEIGHBOURHOODS" => config.neighbourhoods         = parse_neighbourhoods(value)?,
+            "CHANGE_RULE"    => config.change_rule            = parse_change_rule(value)?,
+            "STRATEGY"       => config.strategy               = parse_strategy(value)?,
+            "SHAKING"        => config.shaking_neighbourhoods = parse_neighbourhoods(value)?,
+            "SHAKING_MOVES"  => config.shaking_moves          = parse_number(key, value)?,
+            "ITERATIONS"     => config.stopping_criterion     =
+                StoppingCriterion::Iterations(parse_number(key, value)?),
+            "TIME_LIMIT"     => config.stopping_criterion     =
+                StoppingCriterion::Time(Duration::from_millis(parse_number(key, value)? as u64)),
+            _ => return Err(format!("Unknown configuration key {}", key)),
+        }
+
+    }
+
+    return Ok(config);
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+
+    #[test]
+    fn parse_complete_config()
+    {
+
+        let input : &str = "# Comment\n\
+            NEIGHBOURHOODS : TWO_OPT RELOCATE SWAP TWO_OPT_STAR\n\
+            CHANGE_RULE    : PIPE\n\
+            STRATEGY       : BEST_IMPROVEMENT\n\
+            SHAKING        : RELOCATE CROSS_EXCHANGE\n\
+            SHAKING_MOVES  : 3\n\
+            TIME_LIMIT     : 250\n\
+            EOF\n";
+
+        let expected : VnsConfig = VnsConfig {
+            neighbourhoods         : vec![
+                Neighbourhood::TwoOpt,
+                Neighbourhood::Relocate,
+                Neighbourhood::Swap,
+                Neighbourhood::TwoOptStar,
+            ],
+            change_rule            : NeighbourhoodChangeRule::Pipe,
+            strategy               : ImprovementStrategy::BestImprovement,
+            shaking_neighbourhoods : vec![Neighbourhood::Relocate, Neighbourhood::CrossExchange],
+            shaking_moves          : 3,
+            stopping_criterion     : StoppingCriterion::Time(Duration::from_millis(250)),
+        };
+
+        assert_eq!(parse_vns_config(input), Ok(expected));
+
+    }
+
+    #[test]
+    fn missing_keys_keep_the_default()
+    {
+
+        let config : VnsConfig = parse_vns_config("ITERATIONS : 7\n").unwrap();
+
+        assert_eq!(config.stopping_criterion, StoppingCriterion::Iterations(7));
+        assert_eq!(config.neighbourhoods, VnsConfig::default().neighbourhoods);
+        assert_eq!(parse_vns_config(""), Ok(VnsConfig::default()));
+
+    }
+
+    #[test]
+    fn malformed_configs_are_errors()
+    {
+
+        let malformed : Vec<&str> = vec![
+            "NEIGHBOURHOODS : TWO_OPT THREE_OPT",
+            "NEIGHBOURHOODS :",
+            "CHANGE_RULE : RANDOM",
+            "STRATEGY : WORST_IMPROVEMENT",
+            "SHAKING_MOVES : -3",
+            "ITERATIONS : 10 moves",
+            "TIME_LIMIT : 1.5",
+            "UNKNOWN_KEY : 1",
+            "ITERATIONS 100",
+        ];
+
+        for input in malformed
+        {
+            assert!(parse_vns_config(input).is_err(), "{} should be rejected", input);
+        }
+
+    }
+
+}
@@ -0,0 +1,3 @@
+pub mod variable_neighbourhood_descent_imp;
+pub mod variable_neighbourhood_search_imp;
+pub mod config_parser;
@@ -0,0 +1,160 @@
+use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+use crate::local_search::moves::{Move, Neighbourhood};
+use crate::local_search::inter_route_imp::select_move;
+use crate::variable_neighbourhood_search::config_parser::VnsConfig;
+use crate::algorithm_interface::{CVRPImprover, SolveControl};
+
+/* Rule selecting the next neighbourhood
+ * explored by the descent. */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(dead_code)]
+pub enum NeighbourhoodChangeRule
+{
+
+    /* Restart from the first neighbourhood after an
+     * improvement, else move to the next one. */
+    Sequential,
+
+    /* Move to the next neighbourhood in any case. */
+    Cyclic,
+
+    /* Stay in the same neighbourhood after an
+     * improvement, else move to the next one. */
+    Pipe,
+
+}
+
+/* Here the variable neighbourhood descent is
+ * implemented. The neighbourhoods, both intra
+ * and inter-route, are explored in the given
+ * order, moving from one to another as the
+ * change rule states, until none of them
+ * contains an improving move. */
+pub struct VariableNeighbourhoodDescent<'a>
+{
+
+    pub instance : &'a dyn LocalSearchInstanceTrait,
+
+    /* Neighbourhoods, strategy and change rule of
+     * the descent. The shaking is not used. */
+    pub config   : VnsConfig,
+
+}
+
+/* Methods of VariableNeighbourhoodDescent not
+ * defined by the CVRPImprover trait. */
+#[allow(dead_code)]
+impl<'a> VariableNeighbourhoodDescent<'a>
+{
+
+    /* Sequential descent with first improvement,
+     * from the intra-route neighbourhoods to
+     * the inter-route ones. */
+    pub fn new(instance : &'a dyn LocalSearchInstanceTrait) -> VariableNeighbourhoodDescent<'a>
+    {
+
+        VariableNeighbourhoodDescent {
+            instance,
+            config   : VnsConfig::default(),
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn LocalSearchInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+}
+
+/* Run the descent over the neighbourhoods of the
+ * configuration until as many of them as given are
+ * explored in a row without improvement, or the
+ * control is stopped. An empty route is kept
+ * during the descent, so that a new route can be
+ * opened, and empty routes are removed at the end. */
+pub fn variable_neighbourhood_descent(
+    distance_matrix : &[Vec<f64>],
+    nodes_demand    : &[usize],
+    capacity        : usize,
+    config          : &VnsConfig,
+    routes          : Vec< Vec<usize>>,
+    control         : &SolveControl)
+    -> Vec< Vec<usize>>
+{
+
+    let neighbourhoods : &[Neighbourhood] = &config.neighbourhoods;
+
+    let mut result : Vec< Vec<usize>> = routes;
+    result.retain(|r| !r.is_empty());
+    result.push(Vec::new());
+
+    let mut loads : Vec<usize> = result.iter()
+        .map(|r| r.iter().map(|&n| nodes_demand[n]).sum())
+        .collect();
+
+    let mut k        : usize = 0;
+    let mut failures : usize = 0;
+    while failures < neighbourhoods.len() && !control.should_stop()
+    {
+
+        let selected_move : Option<Move> = select_move(
+            distance_matrix, nodes_demand, capacity, neighbourhoods[k], config.strategy, &result, &loads);
+
+        match selected_move
+        {
+            Some(m) =>
+            {
+                m.apply(&mut result);
+                failures = 0;
+                k = match config.change_rule
+                {
+                    NeighbourhoodChangeRule::Sequential => 0,
+                    NeighbourhoodChangeRule::Cyclic     => (k + 1) % neighbourhoods.len(),
+                    NeighbourhoodChangeRule::Pipe       => k,
+                };
+
+                /* Keep exactly one empty route. */
+                result.retain(|r| !r.is_empty());
+                result.push(Vec::new());
+                loads = result.iter()
+                    .map(|r| r.iter().map(|&n| nodes_demand[n]).sum())
+                    .collect();
+            },
+            None =>
+            {
+                failures += 1;
+                k = (k + 1) % neighbourhoods.len();
+            },
+        }
+
+    }
+
+    result.retain(|r| !r.is_empty());
+
+    return result;
+
+}
+
+/* Implementation of the CVRPImprover trait. */
+impl<'a> CVRPImprover for VariableNeighbourhoodDescent<'a>
+{
+
+    fn improve(&self, routes : Vec< Vec<usize>>, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let instance : &dyn LocalSearchInstanceTrait = self.instance;
+
+        variable_neighbourhood_descent(
+            &instance.get_distance_matrix(),
+            &instance.get_nodes_demand(),
+            instance.get_capacity(),
+            &self.config,
+            routes,
+            control)
+
+    }
+
+}
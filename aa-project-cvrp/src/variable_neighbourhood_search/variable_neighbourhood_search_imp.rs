@@ -0,0 +1,240 @@
+use std::time::Instant;
+use rand::{RngCore, SeedableRng};
+use rand::rngs::StdRng;
+use crate::local_search::local_search_instance_trait::LocalSearchInstanceTrait;
+use crate::local_search::moves::{Move, Neighbourhood};
+use crate::variable_neighbourhood_search::variable_neighbourhood_descent_imp::VariableNeighbourhoodDescent;
+use crate::variable_neighbourhood_search::config_parser::VnsConfig;
+use crate::algorithm_interface::{CVRPSolver, CVRPImprover, StoppingCriterion, compute_routes_cost, SolveControl};
+
+/* Here the general variable neighbourhood search
+ * is implemented. Starting from the routes of
+ * another solver, at each iteration the current
+ * routes are shaken with random moves of the k-th
+ * shaking neighbourhood, and improved with the
+ * variable neighbourhood descent. After an
+ * improvement the search restarts from the first
+ * shaking neighbourhood, else it moves to the
+ * next one. */
+pub struct VariableNeighbourhoodSearch<'a>
+{
+
+    pub instance               : &'a dyn LocalSearchInstanceTrait,
+
+    /* Solver computing the initial routes. */
+    pub initial_solver         : &'a dyn CVRPSolver,
+
+    pub descent                : VariableNeighbourhoodDescent<'a>,
+
+    pub shaking_neighbourhoods : Vec<Neighbourhood>,
+
+    /* Number of random moves of each shaking. */
+    pub shaking_moves          : usize,
+
+    pub stopping_criterion     : StoppingCriterion,
+
+    pub seed                   : u64,
+
+}
+
+/* Number of random moves drawn to find
+ * a feasible one during the shaking. */
+const SHAKING_ATTEMPTS : usize = 20;
+
+/* Methods of VariableNeighbourhoodSearch not
+ * defined by the CVRPSolver trait. */
+#[allow(dead_code)]
+impl<'a> VariableNeighbourhoodSearch<'a>
+{
+
+    /* Search with the default configuration. */
+    pub fn new(
+        instance       : &'a dyn LocalSearchInstanceTrait,
+        initial_solver : &'a dyn CVRPSolver,
+        seed           : u64)
+        -> VariableNeighbourhoodSearch<'a>
+    {
+
+        VariableNeighbourhoodSearch::from_config(instance, initial_solver, &VnsConfig::default(), seed)
+
+    }
+
+    /* Search with the neighbourhoods and the
+     * parameters of the given configuration. */
+    pub fn from_config(
+        instance       : &'a dyn LocalSearchInstanceTrait,
+        initial_solver : &'a dyn CVRPSolver,
+        config         : &VnsConfig,
+        seed           : u64)
+        -> VariableNeighbourhoodSearch<'a>
+    {
+
+        VariableNeighbourhoodSearch {
+            instance,
+            initial_solver,
+            descent                : VariableNeighbourhoodDescent { instance, config : config.clone() },
+            shaking_neighbourhoods : config.shaking_neighbourhoods.clone(),
+            shaking_moves          : config.shaking_moves,
+            stopping_criterion     : config.stopping_criterion,
+            seed,
+        }
+
+    }
+
+    pub fn add_instance(&'a mut self, i : &'a dyn LocalSearchInstanceTrait)
+    {
+
+        self.instance = i;
+
+    }
+
+    pub fn set_seed(&mut self, seed : u64)
+    {
+
+        self.seed = seed;
+
+    }
+
+}
+
+/* Apply the given number of random feasible moves
+ * of the neighbourhood. An empty route is kept, so
+ * that a new route can be opened. */
+fn shake(
+    nodes_demand  : &[usize],
+    capacity      : usize,
+    neighbourhood : Neighbourhood,
+    moves         : usize,
+    routes        : &[Vec<usize>],
+    rng           : &mut dyn RngCore)
+    -> Vec< Vec<usize>>
+{
+
+    let mut result : Vec< Vec<usize>> = routes.to_vec();
+    result.push(Vec::new());
+
+    for _ in 0..moves
+    {
+
+        let loads : Vec<usize> = result.iter()
+            .map(|r| r.iter().map(|&n| nodes_demand[n]).sum())
+            .collect();
+
+        let random_move : Option<Move> = (0..SHAKING_ATTEMPTS)
+            .filter_map(|_| neighbourhood.random_move(&result, rng))
+            .find(|m| m.is_feasible(&result, &loads, nodes_demand, capacity));
+
+        if let Some(m) = random_move
+        {
+            m.apply(&mut result);
+            result.retain(|r| !r.is_empty());
+            result.push(Vec::new());
+        }
+
+    }
+
+    result.retain(|r| !r.is_empty());
+
+    return result;
+
+}
+
+/* Implementation of the CVRPSolver trait. */
+impl<'a> CVRPSolver for VariableNeighbourhoodSearch<'a>
+{
+
+    fn solve(&self) -> Vec< Vec<usize>>
+    {
+
+        self.solve_with_control(&SolveControl::unlimited())
+
+    }
+
+    fn solve_with_control(&self, control : &SolveControl) -> Vec< Vec<usize>>
+    {
+
+        let instance        : &dyn LocalSearchInstanceTrait = self.instance;
+        let distance_matrix : Vec< Vec<f64>>                = instance.get_distance_matrix();
+        let nodes_demand    : Vec<usize>                    = instance.get_nodes_demand();
+        let capacity        : usize                         = instance.get_capacity();
+        let start           : Instant                       = Instant::now();
+        let mut rng         : StdRng                        = StdRng::seed_from_u64(self.seed);
+
+        let mut routes : Vec< Vec<usize>> = self.descent.improve(self.initial_solver.solve_with_control(control), control);
+        let mut cost   : f64              = compute_routes_cost(&distance_matrix, &routes);
+        control.report(&routes);
+
+        if self.shaking_neighbourhoods.is_empty()
+        {
+            return routes;
+        }
+
+        let mut k         : usize = 0;
+        let mut iteration : usize = 0;
+        while !self.stopping_criterion.is_met(iteration, start) && !control.should_stop()
+        {
+
+            iteration += 1;
+
+            let shaken         : Vec< Vec<usize>> = shake(
+                &nodes_demand, capacity, self.shaking_neighbourhoods[k], self.shaking_moves, &routes, &mut rng);
+            let candidate      : Vec< Vec<usize>> = self.descent.improve(shaken, control);
+            let candidate_cost : f64              = compute_routes_cost(&distance_matrix, &candidate);
+
+            if candidate_cost < cost - 1e-9
+            {
+                routes = candidate;
+                cost   = candidate_cost;
+                k      = 0;
+                control.report(&routes);
+            }
+            else
+            {
+                k = (k + 1) % self.shaking_neighbourhoods.len();
+            }
+
+        }
+
+        return routes;
+
+    }
+
+    fn get_seed(&self) -> Option<u64>
+    {
+
+        Some(self.seed)
+
+    }
+
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use super::*;
+    use std::time::Duration;
+    use crate::model::{GraphInstance, read_test_instance, assert_feasible};
+    use crate::savings_algorithm::savings_algorithm_imp::SavingsSolver;
+
+    #[test]
+    fn deadline_stops_the_search()
+    {
+
+        let graph   : GraphInstance                = read_test_instance("F-n72-k4");
+        let savings : SavingsSolver               = SavingsSolver::new(&graph);
+        let solver  : VariableNeighbourhoodSearch = VariableNeighbourhoodSearch {
+            stopping_criterion : StoppingCriterion::Iterations(usize::MAX),
+            ..VariableNeighbourhoodSearch::new(&graph, &savings, 1)
+        };
+
+        let start   : Instant          = Instant::now();
+        let control : SolveControl     = SolveControl::new(None, Some(start + Duration::from_millis(100)), None, None);
+        let routes  : Vec< Vec<usize>> = solver.solve_with_control(&control);
+
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_feasible(&graph, &routes);
+
+    }
+
+}